@@ -0,0 +1,95 @@
+//! Demonstrates keeping the camera on a path with `CameraRail`, for a guided tour of a corridor
+//!
+//! Orbit to look around, and hold W or S to glide along the rail. Panning and zooming also slide
+//! along it.
+
+use bevy::prelude::*;
+use bevy_panorbit_camera::{
+    CameraRail, PanOrbitCamera, PanOrbitCameraPlugin, PanOrbitCameraSystemSet,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(PanOrbitCameraPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(Update, glide.before(PanOrbitCameraSystemSet))
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // help
+    commands.spawn(Text::new(
+        "Hold W/S to glide along the corridor".to_string(),
+    ));
+    // Floor
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(4.0, 30.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.5, 0.3))),
+    ));
+    // Walls
+    let wall = meshes.add(Cuboid::new(0.2, 3.0, 30.0));
+    let wall_material = materials.add(Color::srgb(0.8, 0.8, 0.8));
+    for x in [-2.1, 2.1] {
+        commands.spawn((
+            Mesh3d(wall.clone()),
+            MeshMaterial3d(wall_material.clone()),
+            Transform::from_xyz(x, 1.5, 0.0),
+        ));
+    }
+    // Exhibits along the corridor
+    let exhibit = meshes.add(Cuboid::new(0.5, 1.0, 0.5));
+    let exhibit_material = materials.add(Color::srgb(0.8, 0.7, 0.6));
+    for i in 0..5 {
+        let x = if i % 2 == 0 { -1.4 } else { 1.4 };
+        commands.spawn((
+            Mesh3d(exhibit.clone()),
+            MeshMaterial3d(exhibit_material.clone()),
+            Transform::from_xyz(x, 0.5, 10.0 - i as f32 * 5.0),
+        ));
+    }
+    // Light
+    commands.spawn((
+        DirectionalLight {
+            shadows_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(2.0, 8.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+    // Camera
+    commands.spawn((
+        PanOrbitCamera {
+            // Look a little way ahead, so orbiting feels like turning your head
+            focus: Vec3::new(0.0, 1.6, 12.0),
+            radius: Some(1.0),
+            yaw: Some(0.0),
+            pitch: Some(0.0),
+            ..default()
+        },
+        // Down the middle of the corridor, at eye height
+        CameraRail::new([Vec3::new(0.0, 1.6, 13.0), Vec3::new(0.0, 1.6, -13.0)]),
+    ));
+}
+
+fn glide(
+    key_input: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    mut camera_query: Query<(&mut PanOrbitCamera, &Transform)>,
+) {
+    let direction = match (
+        key_input.pressed(KeyCode::KeyW),
+        key_input.pressed(KeyCode::KeyS),
+    ) {
+        (true, false) => 1.0,
+        (false, true) => -1.0,
+        _ => return,
+    };
+    for (mut camera, transform) in camera_query.iter_mut() {
+        // Move the way the camera is facing, and let the rail keep it in the corridor
+        camera.target_focus += transform.forward() * direction * 3.0 * time.delta_secs();
+    }
+}
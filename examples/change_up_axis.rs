@@ -0,0 +1,75 @@
+//! Demonstrates smoothly changing which direction is up, e.g. for a gravity change effect
+//!
+//! Controls:
+//!   Toggle between Y up and Z up: Space
+
+use bevy::prelude::*;
+use bevy_panorbit_camera::{PanOrbitCamera, PanOrbitCameraPlugin};
+
+const Y_UP: [Vec3; 3] = [Vec3::X, Vec3::Y, Vec3::Z];
+const Z_UP: [Vec3; 3] = [Vec3::X, Vec3::Z, Vec3::NEG_Y];
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(PanOrbitCameraPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(Update, toggle_up_axis)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Ground
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(5.0, 5.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.5, 0.3))),
+    ));
+    // Wall, which becomes the floor when Z is up
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::new(Vec3::Z, Vec2::splat(2.5)).mesh())),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.3, 0.5))),
+        Transform::from_xyz(0.0, 2.5, -2.5),
+    ));
+    // Cube
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::new(1.0, 1.0, 1.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.8, 0.7, 0.6))),
+        Transform::from_xyz(0.0, 0.5, 0.0),
+    ));
+    // Light
+    commands.spawn((
+        PointLight {
+            shadows_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(4.0, 8.0, 4.0),
+    ));
+    // Camera
+    commands.spawn((
+        Transform::from_translation(Vec3::new(3.0, 3.0, 5.0)),
+        PanOrbitCamera {
+            // Slow down the transition so it's easier to see
+            orbit_smoothness: 0.5,
+            ..default()
+        },
+    ));
+}
+
+fn toggle_up_axis(
+    key_input: Res<ButtonInput<KeyCode>>,
+    mut pan_orbit_query: Query<&mut PanOrbitCamera>,
+) {
+    if key_input.just_pressed(KeyCode::Space) {
+        for mut pan_orbit in pan_orbit_query.iter_mut() {
+            pan_orbit.target_axis = if pan_orbit.target_axis == Y_UP {
+                Z_UP
+            } else {
+                Y_UP
+            };
+        }
+    }
+}
@@ -0,0 +1,79 @@
+//! Demonstrates a debug overlay showing `PanOrbitCameraDebugInfo`. Run with:
+//! `cargo run --example debug_info --features debug`
+
+use bevy::prelude::*;
+use bevy_panorbit_camera::{PanOrbitCamera, PanOrbitCameraDebugInfo, PanOrbitCameraPlugin};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(PanOrbitCameraPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(Update, update_overlay)
+        .run();
+}
+
+#[derive(Component)]
+struct Overlay;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Ground
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(5.0, 5.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.5, 0.3))),
+    ));
+    // Cube
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::new(1.0, 1.0, 1.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.8, 0.7, 0.6))),
+        Transform::from_xyz(0.0, 0.5, 0.0),
+    ));
+    // Light
+    commands.spawn((
+        PointLight {
+            shadows_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(4.0, 8.0, 4.0),
+    ));
+    // Camera
+    commands.spawn((
+        Transform::from_translation(Vec3::new(0.0, 1.5, 5.0)),
+        PanOrbitCamera::default(),
+    ));
+    // Overlay
+    commands.spawn((
+        Text::default(),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            left: Val::Px(12.0),
+            ..default()
+        },
+        Overlay,
+    ));
+}
+
+fn update_overlay(
+    debug_info: Res<PanOrbitCameraDebugInfo>,
+    mut overlay: Query<&mut Text, With<Overlay>>,
+) {
+    if !debug_info.is_changed() {
+        return;
+    }
+    let mut text = format!(
+        "Cameras: {} ({} enabled)\nActive: {:?}",
+        debug_info.camera_count, debug_info.enabled_count, debug_info.active_camera
+    );
+    if let Some(state) = debug_info.active_state {
+        text += &format!(
+            "\nYaw: {:.2}\nPitch: {:.2}\nRadius: {:.2}\nFocus: {:.2}",
+            state.yaw, state.pitch, state.radius, state.focus
+        );
+    }
+    overlay.single_mut().0 = text;
+}
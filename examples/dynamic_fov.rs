@@ -0,0 +1,56 @@
+//! Demonstrates widening the field of view while the camera moves quickly, for a sense of speed
+//!
+//! Orbit or zoom quickly to see the field of view widen, then stop to see it return to normal.
+
+use bevy::prelude::*;
+use bevy_panorbit_camera::{DynamicFovConfig, PanOrbitCamera, PanOrbitCameraPlugin};
+use std::f32::consts::PI;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(PanOrbitCameraPlugin)
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Ground
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(5.0, 5.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.5, 0.3))),
+    ));
+    // Cube
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::new(1.0, 1.0, 1.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.8, 0.7, 0.6))),
+        Transform::from_xyz(0.0, 0.5, 0.0),
+    ));
+    // Light
+    commands.spawn((
+        PointLight {
+            shadows_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(4.0, 8.0, 4.0),
+    ));
+    // Camera
+    commands.spawn((
+        Transform::from_translation(Vec3::new(0.0, 1.5, 5.0)),
+        PanOrbitCamera {
+            dynamic_fov: Some(DynamicFovConfig {
+                // The same as the default projection's field of view
+                base_fov: PI / 4.0,
+                max_fov: PI / 2.5,
+                // Orbiting at two turns per second, or zooming as fast, widens it fully
+                full_speed: 4.0 * PI,
+                smoothness: 0.5,
+            }),
+            ..default()
+        },
+    ));
+}
@@ -0,0 +1,81 @@
+//! Demonstrates controlling the camera from on-screen controls, using `CameraInput`
+//!
+//! Drag in the pad to orbit, and use the buttons to zoom.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPlugin};
+use bevy_panorbit_camera::{CameraInput, PanOrbitCamera, PanOrbitCameraPlugin};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(EguiPlugin)
+        .add_plugins(PanOrbitCameraPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(Update, drag_pad)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut camera_input: ResMut<CameraInput>,
+) {
+    // Ground
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(5.0, 5.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.5, 0.3))),
+    ));
+    // Cube
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::new(1.0, 1.0, 1.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.8, 0.7, 0.6))),
+        Transform::from_xyz(0.0, 0.5, 0.0),
+    ));
+    // Light
+    commands.spawn((
+        PointLight {
+            shadows_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(4.0, 8.0, 4.0),
+    ));
+    // Camera
+    let camera = commands
+        .spawn((
+            Transform::from_translation(Vec3::new(0.0, 1.5, 5.0)),
+            PanOrbitCamera::default(),
+        ))
+        .id();
+    // Interacting with egui deactivates the camera, so tell `CameraInput` which camera to control
+    camera_input.camera = Some(camera);
+}
+
+fn drag_pad(mut contexts: EguiContexts, mut camera_input: ResMut<CameraInput>) {
+    egui::Window::new("Camera").show(contexts.ctx_mut(), |ui| {
+        let (rect, response) =
+            ui.allocate_exact_size(egui::vec2(150.0, 150.0), egui::Sense::drag());
+        ui.painter()
+            .rect_filled(rect, 8.0, ui.visuals().extreme_bg_color);
+        ui.painter().text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            "Drag to orbit",
+            egui::FontId::default(),
+            ui.visuals().text_color(),
+        );
+        // Drag deltas are in pixels, just like mouse motion
+        let delta = response.drag_delta();
+        camera_input.orbit += Vec2::new(delta.x, delta.y);
+
+        ui.horizontal(|ui| {
+            if ui.button("Zoom in").clicked() {
+                camera_input.zoom += 1.0;
+            }
+            if ui.button("Zoom out").clicked() {
+                camera_input.zoom -= 1.0;
+            }
+        });
+    });
+}
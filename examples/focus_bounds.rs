@@ -0,0 +1,59 @@
+//! Demonstrates constraining the focus with `FocusBounds`, drawn with `FocusBoundsGizmosPlugin`
+//!
+//! Pan around to see the focus stop at the edges of the bounds. Run with:
+//! `cargo run --example focus_bounds --features debug_gizmos`
+
+use bevy::prelude::*;
+use bevy_panorbit_camera::{
+    FocusBounds, FocusBoundsGizmosPlugin, FocusBoundsShape, PanOrbitCamera, PanOrbitCameraPlugin,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins((PanOrbitCameraPlugin, FocusBoundsGizmosPlugin))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Ground
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(5.0, 5.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.5, 0.3))),
+    ));
+    // Cube
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::new(1.0, 1.0, 1.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.8, 0.7, 0.6))),
+        Transform::from_xyz(0.0, 0.5, 0.0),
+    ));
+    // Light
+    commands.spawn((
+        PointLight {
+            shadows_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(4.0, 8.0, 4.0),
+    ));
+    // Camera
+    commands.spawn((
+        Transform::from_translation(Vec3::new(0.0, 1.5, 5.0)),
+        PanOrbitCamera::default(),
+        // Keep the focus over the ground, or in a sphere around the cube
+        FocusBounds::union([
+            FocusBoundsShape::Cuboid {
+                min: Vec3::new(-2.5, 0.0, -2.5),
+                max: Vec3::new(2.5, 0.5, 2.5),
+            },
+            FocusBoundsShape::Sphere {
+                center: Vec3::new(0.0, 0.5, 0.0),
+                radius: 1.5,
+            },
+        ]),
+    ));
+}
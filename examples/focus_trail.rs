@@ -0,0 +1,88 @@
+//! Demonstrates `FocusTrailGizmosPlugin`, which draws a trail of the camera's recent focus positions
+//!
+//! The focus follows a moving cube with smoothing, so the trail shows how the focus lags behind it,
+//! and any lag over 0.2 is logged. Run with:
+//! `cargo run --example focus_trail --features debug_gizmos`
+
+use bevy::prelude::*;
+use bevy_panorbit_camera::{
+    FocusTrailGizmosPlugin, FocusTrailSettings, PanOrbitCamera, PanOrbitCameraPlugin,
+};
+use std::f32::consts::TAU;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins((PanOrbitCameraPlugin, FocusTrailGizmosPlugin))
+        .insert_resource(FocusTrailSettings {
+            length: 240,
+            log_divergence: Some(0.2),
+        })
+        .add_systems(Startup, setup)
+        .add_systems(Update, (animate_cube, cam_follow).chain())
+        .run();
+}
+
+#[derive(Component)]
+struct Cube;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Ground
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(5.0, 5.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.5, 0.3))),
+    ));
+    // Cube
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::new(0.5, 0.5, 0.5))),
+        MeshMaterial3d(materials.add(Color::srgb(0.8, 0.7, 0.6))),
+        Transform::from_xyz(0.0, 0.25, 0.0),
+        Cube,
+    ));
+    // Light
+    commands.spawn((
+        PointLight {
+            shadows_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(4.0, 8.0, 4.0),
+    ));
+    // Camera
+    commands.spawn((
+        Transform::from_translation(Vec3::new(0.0, 4.0, 7.0)),
+        PanOrbitCamera {
+            // Heavier than default smoothing, to make the lag easy to see
+            pan_smoothness: 0.9,
+            ..default()
+        },
+    ));
+}
+
+/// Move the cube in a figure of eight
+fn animate_cube(
+    time: Res<Time>,
+    mut cube_q: Query<&mut Transform, With<Cube>>,
+    mut angle: Local<f32>,
+) {
+    if let Ok(mut cube_tfm) = cube_q.get_single_mut() {
+        *angle = (*angle + 45f32.to_radians() * time.delta_secs()) % TAU;
+        cube_tfm.translation = Vec3::new(angle.sin() * 2.0, 0.25, (*angle * 2.0).sin());
+    }
+}
+
+/// Set the camera's target focus to the cube's position
+fn cam_follow(mut pan_orbit_q: Query<&mut PanOrbitCamera>, cube_q: Query<&Transform, With<Cube>>) {
+    if let Ok(mut pan_orbit) = pan_orbit_q.get_single_mut() {
+        if let Ok(cube_tfm) = cube_q.get_single() {
+            pan_orbit.target_focus = cube_tfm.translation;
+            // Whenever changing properties manually like this, it's necessary to force
+            // PanOrbitCamera to update this frame (by default it only updates when there are
+            // input events).
+            pan_orbit.force_update = true;
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! Demonstrates zooming to fit a mesh in view with `PanOrbitCamera::frame_bounds`
+//!
+//! Controls:
+//!   Frame the torus: F
+//!   Frame the cube: C
+
+use bevy::math::Mat3A;
+use bevy::prelude::*;
+use bevy::render::primitives::Aabb;
+use bevy_panorbit_camera::{PanOrbitCamera, PanOrbitCameraPlugin};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(PanOrbitCameraPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(Update, frame_on_key)
+        .run();
+}
+
+/// The key that frames this model
+#[derive(Component)]
+struct FrameKey(KeyCode);
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Ground
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(10.0, 10.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.5, 0.3))),
+    ));
+    // Torus, off to one side
+    commands.spawn((
+        Mesh3d(meshes.add(Torus::new(0.5, 1.5))),
+        MeshMaterial3d(materials.add(Color::srgb(0.8, 0.7, 0.6))),
+        Transform::from_xyz(-2.5, 0.25, -1.0),
+        FrameKey(KeyCode::KeyF),
+    ));
+    // Small cube, off to the other side
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::new(0.3, 0.3, 0.3))),
+        MeshMaterial3d(materials.add(Color::srgb(0.6, 0.7, 0.8))),
+        Transform::from_xyz(3.0, 0.15, 2.0),
+        FrameKey(KeyCode::KeyC),
+    ));
+    // Light
+    commands.spawn((
+        PointLight {
+            shadows_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(4.0, 8.0, 4.0),
+    ));
+    // Camera
+    commands.spawn((
+        Transform::from_translation(Vec3::new(0.0, 6.0, 12.0)),
+        PanOrbitCamera::default(),
+    ));
+}
+
+fn frame_on_key(
+    key_input: Res<ButtonInput<KeyCode>>,
+    models: Query<(&FrameKey, &Aabb, &GlobalTransform)>,
+    mut camera_q: Query<(&mut PanOrbitCamera, &Projection, &Camera)>,
+) {
+    let Ok((mut pan_orbit, projection, camera)) = camera_q.get_single_mut() else {
+        return;
+    };
+    let Some(viewport_size) = camera.logical_viewport_size() else {
+        return;
+    };
+    for (key, aabb, transform) in models.iter() {
+        if !key_input.just_pressed(key.0) {
+            continue;
+        }
+        // Bevy computes the Aabb in the mesh's local space, so move it into world space, growing
+        // it to contain the rotated box
+        let matrix = transform.affine().matrix3;
+        let abs_matrix = Mat3A::from_cols(
+            matrix.x_axis.abs(),
+            matrix.y_axis.abs(),
+            matrix.z_axis.abs(),
+        );
+        let world_aabb = Aabb {
+            center: transform.transform_point(aabb.center.into()).into(),
+            half_extents: abs_matrix * aabb.half_extents,
+        };
+        pan_orbit.frame_bounds(world_aabb, projection, viewport_size.x / viewport_size.y);
+    }
+}
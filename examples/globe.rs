@@ -0,0 +1,72 @@
+//! Demonstrates globe mode, which lets you orbit freely over the poles of a sphere
+
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy_panorbit_camera::{PanOrbitCamera, PanOrbitCameraPlugin};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(PanOrbitCameraPlugin)
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut images: ResMut<Assets<Image>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Globe
+    commands.spawn((
+        Mesh3d(meshes.add(Sphere::new(1.0).mesh().uv(64, 32))),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color_texture: Some(images.add(checker_texture())),
+            ..default()
+        })),
+    ));
+    // Light
+    commands.spawn((
+        DirectionalLight::default(),
+        Transform::from_xyz(4.0, 8.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+    // Camera
+    commands.spawn((
+        Transform::from_translation(Vec3::new(0.0, 0.0, 4.0)),
+        PanOrbitCamera {
+            globe_mode: true,
+            zoom_lower_limit: 1.2,
+            // Panning would move the focus away from the center of the globe
+            pan_sensitivity: 0.0,
+            ..default()
+        },
+    ));
+}
+
+/// Creates a checkerboard texture, so it's easy to see the globe rotating
+fn checker_texture() -> Image {
+    const SIZE: usize = 16;
+    let mut data = Vec::with_capacity(SIZE * SIZE * 4);
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            if (x + y) % 2 == 0 {
+                data.extend_from_slice(&[40, 90, 160, 255]);
+            } else {
+                data.extend_from_slice(&[90, 160, 70, 255]);
+            }
+        }
+    }
+    Image::new(
+        Extent3d {
+            width: SIZE as u32,
+            height: SIZE as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
@@ -0,0 +1,197 @@
+//! Demonstrates orbiting and zooming with an on-screen joystick, using `CameraInput`
+//!
+//! Hold and drag the joystick in the bottom left to orbit, and hold the buttons in the bottom
+//! right to zoom. Works with the mouse or touch.
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_panorbit_camera::{
+    CameraInput, ExplicitActiveCamera, PanOrbitCamera, PanOrbitCameraPlugin,
+    PanOrbitCameraSystemSet,
+};
+
+/// The joystick's distance from the corner of the window, in logical pixels
+const JOYSTICK_MARGIN: f32 = 30.0;
+/// The joystick's diameter, in logical pixels
+const JOYSTICK_SIZE: f32 = 150.0;
+/// The knob's diameter, in logical pixels
+const KNOB_SIZE: f32 = 60.0;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(PanOrbitCameraPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(
+            Update,
+            (joystick, zoom_buttons).before(PanOrbitCameraSystemSet),
+        )
+        .run();
+}
+
+#[derive(Component)]
+struct Joystick;
+
+#[derive(Component)]
+struct Knob;
+
+#[derive(Component)]
+struct ZoomButton(f32);
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut camera_input: ResMut<CameraInput>,
+) {
+    // Ground
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(5.0, 5.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.5, 0.3))),
+    ));
+    // Cube
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::new(1.0, 1.0, 1.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.8, 0.7, 0.6))),
+        Transform::from_xyz(0.0, 0.5, 0.0),
+    ));
+    // Light
+    commands.spawn((
+        PointLight {
+            shadows_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(4.0, 8.0, 4.0),
+    ));
+    // Camera
+    let camera = commands
+        .spawn((
+            Transform::from_translation(Vec3::new(0.0, 1.5, 5.0)),
+            PanOrbitCamera::default(),
+        ))
+        .id();
+    // Using the controls deactivates the camera, so tell `CameraInput` which camera to control
+    camera_input.camera = Some(camera);
+
+    // Joystick
+    commands
+        .spawn((
+            Joystick,
+            Button,
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(JOYSTICK_MARGIN),
+                bottom: Val::Px(JOYSTICK_MARGIN),
+                width: Val::Px(JOYSTICK_SIZE),
+                height: Val::Px(JOYSTICK_SIZE),
+                ..default()
+            },
+            BorderRadius::MAX,
+            BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.2)),
+        ))
+        .with_child((
+            Knob,
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px((JOYSTICK_SIZE - KNOB_SIZE) / 2.0),
+                top: Val::Px((JOYSTICK_SIZE - KNOB_SIZE) / 2.0),
+                width: Val::Px(KNOB_SIZE),
+                height: Val::Px(KNOB_SIZE),
+                ..default()
+            },
+            BorderRadius::MAX,
+            BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.6)),
+        ));
+
+    // Zoom buttons
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(JOYSTICK_MARGIN),
+            bottom: Val::Px(JOYSTICK_MARGIN),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(10.0),
+            ..default()
+        })
+        .with_children(|parent| {
+            for (label, axis) in [("+", 1.0), ("-", -1.0)] {
+                parent
+                    .spawn((
+                        ZoomButton(axis),
+                        Button,
+                        Node {
+                            width: Val::Px(KNOB_SIZE),
+                            height: Val::Px(KNOB_SIZE),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        BorderRadius::all(Val::Px(8.0)),
+                        BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.2)),
+                    ))
+                    .with_child(Text::new(label));
+            }
+        });
+}
+
+/// Sets the orbit joystick from the pointer's offset from the joystick's center, while it's held
+fn joystick(
+    joystick_query: Query<&Interaction, With<Joystick>>,
+    mut knob_query: Query<&mut Node, With<Knob>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    touches: Res<Touches>,
+    explicit_active_camera: Option<Res<ExplicitActiveCamera>>,
+    mut camera_input: ResMut<CameraInput>,
+    mut commands: Commands,
+) {
+    let (Ok(interaction), Ok(mut knob), Ok(window)) = (
+        joystick_query.get_single(),
+        knob_query.get_single_mut(),
+        window_query.get_single(),
+    ) else {
+        return;
+    };
+    let pointer = touches
+        .iter()
+        .next()
+        .map(|touch| touch.position())
+        .or_else(|| window.cursor_position());
+    let joystick = match (interaction, pointer) {
+        (Interaction::Pressed, Some(pointer)) => {
+            let radius = JOYSTICK_SIZE / 2.0;
+            let center = Vec2::new(
+                JOYSTICK_MARGIN + radius,
+                window.height() - JOYSTICK_MARGIN - radius,
+            );
+            ((pointer - center) / radius).clamp_length_max(1.0)
+        }
+        _ => Vec2::ZERO,
+    };
+    camera_input.set_orbit_joystick(joystick);
+
+    // Stop the drag from also orbiting the camera directly, by making no camera active
+    let pressed = *interaction == Interaction::Pressed;
+    match (pressed, explicit_active_camera.is_some()) {
+        (true, false) => commands.insert_resource(ExplicitActiveCamera::default()),
+        (false, true) => commands.remove_resource::<ExplicitActiveCamera>(),
+        _ => {}
+    }
+
+    // Move the knob with the pointer
+    let offset = (JOYSTICK_SIZE - KNOB_SIZE) / 2.0;
+    knob.left = Val::Px(offset + joystick.x * offset);
+    knob.top = Val::Px(offset + joystick.y * offset);
+}
+
+/// Sets the zoom axis while a zoom button is held
+fn zoom_buttons(
+    button_query: Query<(&Interaction, &ZoomButton)>,
+    mut camera_input: ResMut<CameraInput>,
+) {
+    let axis = button_query
+        .iter()
+        .filter(|(interaction, _)| **interaction == Interaction::Pressed)
+        .map(|(_, button)| button.0)
+        .sum();
+    camera_input.set_zoom_axis(axis);
+}
@@ -5,6 +5,7 @@
 //!     Pan smoothly: Shift+Arrows
 //!     Pan in 1m increments: Ctrl+Shift+Arrows
 //!     Zoom in/out: Z/X
+//!     Recenter focus: H
 
 use bevy::prelude::*;
 use bevy_panorbit_camera::{PanOrbitCamera, PanOrbitCameraPlugin};
@@ -128,6 +129,11 @@ fn keyboard_controls(
             }
         }
 
+        // Return focus to the origin with H
+        if key_input.just_pressed(KeyCode::KeyH) {
+            pan_orbit.recenter_focus();
+        }
+
         // Force camera to update its transform
         pan_orbit.force_update = true;
     }
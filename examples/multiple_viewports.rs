@@ -1,16 +1,16 @@
-//! Demonstrates usage with multiple viewports
+//! Demonstrates usage with multiple viewports, highlighting the viewport of the active camera
 
 use bevy::prelude::*;
 use bevy::render::camera::Viewport;
 use bevy::window::WindowResized;
-use bevy_panorbit_camera::{PanOrbitCamera, PanOrbitCameraPlugin};
+use bevy_panorbit_camera::{ActivePanOrbitCamera, PanOrbitCamera, PanOrbitCameraPlugin};
 
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
         .add_plugins(PanOrbitCameraPlugin)
         .add_systems(Startup, setup)
-        .add_systems(Update, set_camera_viewports)
+        .add_systems(Update, (set_camera_viewports, highlight_active_minimap))
         .run();
 }
 
@@ -77,3 +77,16 @@ fn set_camera_viewports(
         });
     }
 }
+
+// Give the minimap a background while it's the active camera
+fn highlight_active_minimap(
+    active: ActivePanOrbitCamera,
+    mut minimap_camera: Query<(Entity, &mut Camera), With<MinimapCamera>>,
+) {
+    let (entity, mut camera) = minimap_camera.single_mut();
+    camera.clear_color = if active.is_camera_active(entity) {
+        ClearColorConfig::Custom(Color::srgb(0.2, 0.2, 0.3))
+    } else {
+        ClearColorConfig::None
+    };
+}
@@ -0,0 +1,80 @@
+//! Demonstrates orbiting around whatever is under the cursor, by ray casting from the cursor to
+//! keep `CursorPivot` up to date
+
+use bevy::picking::mesh_picking::ray_cast::{MeshRayCast, RayCastSettings};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_panorbit_camera::{CursorPivot, PanOrbitCamera, PanOrbitCameraPlugin};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(PanOrbitCameraPlugin)
+        .init_resource::<CursorPivot>()
+        .add_systems(Startup, setup)
+        .add_systems(Update, update_cursor_pivot)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Ground
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(5.0, 5.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.5, 0.3))),
+    ));
+    // Cubes, to orbit around
+    for x in [-1.5, 0.0, 1.5] {
+        commands.spawn((
+            Mesh3d(meshes.add(Cuboid::new(1.0, 1.0, 1.0))),
+            MeshMaterial3d(materials.add(Color::srgb(0.8, 0.7, 0.6))),
+            Transform::from_xyz(x, 0.5, 0.0),
+        ));
+    }
+    // Light
+    commands.spawn((
+        PointLight {
+            shadows_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(4.0, 8.0, 4.0),
+    ));
+    // Camera
+    commands.spawn((
+        Transform::from_translation(Vec3::new(0.0, 1.5, 5.0)),
+        PanOrbitCamera {
+            orbit_around_cursor: true,
+            ..default()
+        },
+    ));
+}
+
+fn update_cursor_pivot(
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<PanOrbitCamera>>,
+    mut ray_cast: MeshRayCast,
+    mut cursor_pivot: ResMut<CursorPivot>,
+) {
+    let (Ok(window), Ok((camera, camera_transform))) = (windows.get_single(), cameras.get_single())
+    else {
+        return;
+    };
+    // The camera turns to look at the pivot when orbiting starts, so keep it while orbiting,
+    // rather than chasing whatever is under the cursor next
+    if mouse_input.pressed(MouseButton::Left) {
+        return;
+    }
+    cursor_pivot.point = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world(camera_transform, cursor).ok())
+        .and_then(|ray| {
+            ray_cast
+                .cast_ray(ray, &RayCastSettings::default())
+                .first()
+                .map(|(_, hit)| hit.point)
+        });
+}
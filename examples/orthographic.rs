@@ -2,7 +2,9 @@
 
 use bevy::prelude::*;
 use bevy::render::camera::ScalingMode;
-use bevy_panorbit_camera::{PanOrbitCamera, PanOrbitCameraPlugin, PanOrbitCameraSystemSet};
+use bevy_panorbit_camera::{
+    PanOrbitCamera, PanOrbitCameraPlugin, PanOrbitCameraSystemSet, ProjectionSwitch,
+};
 
 fn main() {
     App::new()
@@ -48,7 +50,11 @@ fn setup(
             },
             ..OrthographicProjection::default_3d()
         }),
-        PanOrbitCamera::default(),
+        PanOrbitCamera {
+            // Keep the cube the same size on screen when switching projection
+            projection_switch: ProjectionSwitch::PreserveApparentSize,
+            ..default()
+        },
     ));
 }
 
@@ -0,0 +1,75 @@
+//! Demonstrates recording camera input and replaying it
+//!
+//! Controls:
+//!   Start recording: R
+//!   Stop recording or playing: S
+//!   Play: P
+//!
+//! Orbit the camera while recording, then move it somewhere else and press P to see the same
+//! orbit again.
+
+use bevy::prelude::*;
+use bevy_panorbit_camera::{
+    CameraInputRecording, ExplicitActiveCamera, PanOrbitCamera, PanOrbitCameraPlugin,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(PanOrbitCameraPlugin)
+        .init_resource::<CameraInputRecording>()
+        .add_systems(Startup, setup)
+        .add_systems(Update, controls)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Ground
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(5.0, 5.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.5, 0.3))),
+    ));
+    // Cube
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::new(1.0, 1.0, 1.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.8, 0.7, 0.6))),
+        Transform::from_xyz(0.0, 0.5, 0.0),
+    ));
+    // Light
+    commands.spawn((
+        PointLight {
+            shadows_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(4.0, 8.0, 4.0),
+    ));
+    // Camera
+    let camera = commands
+        .spawn((
+            Transform::from_translation(Vec3::new(0.0, 1.5, 5.0)),
+            PanOrbitCamera::default(),
+        ))
+        .id();
+    // Replayed input goes to the active camera, so make sure there is one even before the user
+    // has clicked in the window
+    commands.insert_resource(ExplicitActiveCamera::new(camera));
+}
+
+fn controls(key_input: Res<ButtonInput<KeyCode>>, mut recording: ResMut<CameraInputRecording>) {
+    if key_input.just_pressed(KeyCode::KeyR) {
+        recording.start_recording();
+        info!("Recording");
+    }
+    if key_input.just_pressed(KeyCode::KeyS) {
+        recording.stop();
+        info!("Stopped, with {} frames recorded", recording.frames().len());
+    }
+    if key_input.just_pressed(KeyCode::KeyP) {
+        recording.play();
+        info!("Playing");
+    }
+}
@@ -0,0 +1,48 @@
+//! Demonstrates keeping a model framed when the window is resized. Try resizing the window, or
+//! making it tall and narrow.
+
+use bevy::prelude::*;
+use bevy::render::primitives::Aabb;
+use bevy_panorbit_camera::{PanOrbitCamera, PanOrbitCameraPlugin};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(PanOrbitCameraPlugin)
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Model
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::new(3.0, 1.0, 1.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.8, 0.7, 0.6))),
+        Transform::from_xyz(0.0, 0.5, 0.0),
+    ));
+    // Light
+    commands.spawn((
+        PointLight {
+            shadows_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(4.0, 8.0, 4.0),
+    ));
+    // Camera
+    commands.spawn((
+        Transform::from_translation(Vec3::new(0.0, 1.5, 5.0)),
+        PanOrbitCamera {
+            focus: Vec3::new(0.0, 0.5, 0.0),
+            // The bounds of the model, which will be kept in view when the window is resized
+            refit_on_resize: Some(Aabb::from_min_max(
+                Vec3::new(-1.5, 0.0, -0.5),
+                Vec3::new(1.5, 1.0, 0.5),
+            )),
+            ..default()
+        },
+    ));
+}
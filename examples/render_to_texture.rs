@@ -159,6 +159,8 @@ fn setup(
         // size to match the size of the window that you are interacting with.
         viewport_size: Some(Vec2::new(size.width as f32, size.height as f32)),
         window_size: Some(Vec2::new(primary_window.width(), primary_window.height())),
+        // Only needed for `ZoomPivot::Cursor`
+        cursor_position: None,
         // Setting manual to true ensures PanOrbitCameraPlugin will not overwrite this resource
         manual: true,
     });
@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+
+use crate::{ActiveCameraData, PanOrbitCamera, PanOrbitCameraState};
+
+/// A summary of the `PanOrbitCamera`s in the world, for debug overlays, updated every frame after
+/// the cameras move. Requires the `debug` feature, which makes `PanOrbitCameraPlugin` add this
+/// resource and keep it up to date.
+#[derive(Resource, Reflect, Default, Debug, Copy, Clone, PartialEq)]
+pub struct PanOrbitCameraDebugInfo {
+    /// The number of `PanOrbitCamera`s.
+    pub camera_count: usize,
+    /// The number of `PanOrbitCamera`s with `enabled` set to `true`.
+    pub enabled_count: usize,
+    /// The camera that currently receives input, from `ActiveCameraData`.
+    pub active_camera: Option<Entity>,
+    /// The current view of `active_camera`, or `None` if there's no active camera or it hasn't
+    /// been initialized yet.
+    pub active_state: Option<PanOrbitCameraState>,
+}
+
+pub fn update_debug_info(
+    mut debug_info: ResMut<PanOrbitCameraDebugInfo>,
+    active_cam: Res<ActiveCameraData>,
+    orbit_cameras: Query<(Entity, &PanOrbitCamera)>,
+) {
+    let mut info = PanOrbitCameraDebugInfo {
+        active_camera: active_cam.entity,
+        ..default()
+    };
+    for (entity, pan_orbit) in orbit_cameras.iter() {
+        info.camera_count += 1;
+        if pan_orbit.enabled {
+            info.enabled_count += 1;
+        }
+        if active_cam.entity == Some(entity) {
+            if let (Some(yaw), Some(pitch), Some(radius)) =
+                (pan_orbit.yaw, pan_orbit.pitch, pan_orbit.radius)
+            {
+                info.active_state = Some(PanOrbitCameraState {
+                    focus: pan_orbit.focus,
+                    yaw,
+                    pitch,
+                    radius,
+                });
+            }
+        }
+    }
+    debug_info.set_if_neq(info);
+}
@@ -8,8 +8,8 @@ use bevy::prelude::*;
 /// true. If the camera stops taking input only when it returns false, there's one frame
 /// where both egui and the camera are using the input events, which is not desirable.
 ///
-/// This is re-exported in case it's useful. I recommend only using input events if both
-/// `prev` and `curr` are false.
+/// This is re-exported in case it's useful, e.g. to tell whether the camera is ignoring input
+/// because of egui. Use `camera_should_ignore` for that, rather than reading `prev` and `curr`.
 #[derive(Resource, PartialEq, Eq, Default)]
 pub struct EguiWantsFocus {
     /// Whether egui wanted focus on the previous frame
@@ -18,6 +18,14 @@ pub struct EguiWantsFocus {
     pub curr: bool,
 }
 
+impl EguiWantsFocus {
+    /// Whether PanOrbitCamera ignores input events this frame because egui wants them. This is
+    /// true if egui wanted focus on either this frame or the previous one, as explained above.
+    pub fn camera_should_ignore(&self) -> bool {
+        self.prev || self.curr
+    }
+}
+
 /// When true, just hovering over an egui panel/window will prevent PanOrbitCamera
 /// from reacting to input events. This is an optional, and hopefully temporary,
 /// workaround to this issue: https://github.com/Plonq/bevy_panorbit_camera/issues/75.
@@ -28,6 +36,15 @@ pub struct EguiWantsFocus {
 #[derive(Resource, PartialEq, Eq, Default)]
 pub struct EguiFocusIncludesHover(pub bool);
 
+/// The time, in seconds, after egui stops wanting focus during which PanOrbitCamera keeps ignoring
+/// mouse buttons that were already held down while egui had focus. Without this, releasing a
+/// slider with the cursor outside its window, or a frame's delay in egui releasing focus, can make
+/// the camera start orbiting or panning because the button is still down. A fresh press is never
+/// ignored, and once the time is up, held buttons work as normal again.
+/// Defaults to `0.0`, which disables it.
+#[derive(Resource, PartialEq, Default)]
+pub struct EguiInputReacquireDelay(pub f32);
+
 pub fn check_egui_wants_focus(
     mut contexts: bevy_egui::EguiContexts,
     mut wants_focus: ResMut<EguiWantsFocus>,
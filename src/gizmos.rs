@@ -0,0 +1,161 @@
+use std::collections::VecDeque;
+
+use bevy::ecs::entity::EntityHashMap;
+use bevy::prelude::*;
+
+use crate::{FocusBounds, FocusBoundsShape, PanOrbitCamera, PanOrbitCameraSystemSet};
+
+/// Optional plugin that draws the `FocusBounds` of every `PanOrbitCamera` as gizmos, to help you
+/// check your bounds configuration. Each camera's bounds are drawn in a different color, along
+/// with a small sphere at its `target_focus`. Requires the `debug_gizmos` feature.
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_panorbit_camera::{FocusBoundsGizmosPlugin, PanOrbitCameraPlugin};
+/// fn main() {
+///     App::new()
+///         .add_plugins(DefaultPlugins)
+///         .add_plugins((PanOrbitCameraPlugin, FocusBoundsGizmosPlugin))
+///         .run();
+/// }
+/// ```
+pub struct FocusBoundsGizmosPlugin;
+
+impl Plugin for FocusBoundsGizmosPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, draw_focus_bounds);
+    }
+}
+
+fn draw_focus_bounds(
+    mut gizmos: Gizmos,
+    orbit_cameras: Query<(Entity, &PanOrbitCamera, &FocusBounds, Option<&Parent>)>,
+    parents: Query<&GlobalTransform>,
+) {
+    for (entity, pan_orbit, focus_bounds, parent) in orbit_cameras.iter() {
+        // The bounds are relative to the parent, like the focus
+        let parent_global = parent
+            .and_then(|parent| parents.get(parent.get()).ok())
+            .copied()
+            .unwrap_or_default();
+        // Spread the hues out using the golden angle, so they stay distinct with many cameras
+        let color = Color::hsl((entity.index() as f32 * 137.5) % 360.0, 0.8, 0.6);
+        for shape in &focus_bounds.shapes {
+            match *shape {
+                FocusBoundsShape::Sphere { center, radius } => {
+                    let (scale, rotation, translation) = parent_global
+                        .mul_transform(Transform::from_translation(center))
+                        .to_scale_rotation_translation();
+                    gizmos.sphere(
+                        Isometry3d::new(translation, rotation),
+                        radius * scale.max_element(),
+                        color,
+                    );
+                }
+                FocusBoundsShape::Cuboid { min, max } => {
+                    gizmos.cuboid(
+                        parent_global.mul_transform(
+                            Transform::from_translation(min.midpoint(max)).with_scale(max - min),
+                        ),
+                        color,
+                    );
+                }
+            }
+        }
+        gizmos.sphere(
+            Isometry3d::from_translation(parent_global.transform_point(pan_orbit.target_focus)),
+            0.05 * pan_orbit.world_scale,
+            color,
+        );
+    }
+}
+
+/// Optional plugin that draws the recent history of each `PanOrbitCamera`'s `focus` as a gizmo
+/// trail that fades out with age, to help diagnose smoothing and follow issues. Configure it by
+/// inserting a `FocusTrailSettings` resource. Requires the `debug_gizmos` feature.
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_panorbit_camera::{FocusTrailGizmosPlugin, FocusTrailSettings, PanOrbitCameraPlugin};
+/// fn main() {
+///     App::new()
+///         .add_plugins(DefaultPlugins)
+///         .add_plugins((PanOrbitCameraPlugin, FocusTrailGizmosPlugin))
+///         .insert_resource(FocusTrailSettings {
+///             length: 240,
+///             log_divergence: Some(0.1),
+///         })
+///         .run();
+/// }
+/// ```
+pub struct FocusTrailGizmosPlugin;
+
+impl Plugin for FocusTrailGizmosPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FocusTrailSettings>()
+            // Runs after the camera moves so the trail ends at this frame's focus
+            .add_systems(PostUpdate, draw_focus_trail.after(PanOrbitCameraSystemSet));
+    }
+}
+
+/// Settings for `FocusTrailGizmosPlugin`.
+#[derive(Resource, Reflect, Debug, Copy, Clone, PartialEq)]
+pub struct FocusTrailSettings {
+    /// The number of frames of `focus` history to draw.
+    /// Defaults to `120`.
+    pub length: usize,
+    /// If set, logs each frame where a camera's `focus` is further than this from its
+    /// `target_focus`, i.e. while the focus is still catching up with where it's meant to be.
+    /// Defaults to `None`.
+    pub log_divergence: Option<f32>,
+}
+
+impl Default for FocusTrailSettings {
+    fn default() -> Self {
+        FocusTrailSettings {
+            length: 120,
+            log_divergence: None,
+        }
+    }
+}
+
+fn draw_focus_trail(
+    mut gizmos: Gizmos,
+    settings: Res<FocusTrailSettings>,
+    orbit_cameras: Query<(Entity, &PanOrbitCamera, Option<&Parent>)>,
+    parents: Query<&GlobalTransform>,
+    mut trails: Local<EntityHashMap<VecDeque<Vec3>>>,
+) {
+    trails.retain(|entity, _| orbit_cameras.contains(*entity));
+    for (entity, pan_orbit, parent) in orbit_cameras.iter() {
+        if let Some(threshold) = settings.log_divergence {
+            let divergence = pan_orbit.focus.distance(pan_orbit.target_focus);
+            if divergence > threshold {
+                info!(
+                    "Camera {entity} focus {} is {divergence} from target_focus {}",
+                    pan_orbit.focus, pan_orbit.target_focus
+                );
+            }
+        }
+
+        // The focus is relative to the parent, so record it in world space
+        let parent_global = parent
+            .and_then(|parent| parents.get(parent.get()).ok())
+            .copied()
+            .unwrap_or_default();
+        let trail = trails.entry(entity).or_default();
+        trail.push_back(parent_global.transform_point(pan_orbit.focus));
+        while trail.len() > settings.length {
+            trail.pop_front();
+        }
+
+        let color = Color::hsl((entity.index() as f32 * 137.5) % 360.0, 0.8, 0.6);
+        let len = trail.len() as f32;
+        gizmos.linestrip_gradient(
+            trail
+                .iter()
+                .enumerate()
+                .map(|(i, point)| (*point, color.with_alpha((i + 1) as f32 / len))),
+        );
+    }
+}
@@ -1,7 +1,162 @@
 use bevy::input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel};
+use bevy::math::DVec2;
 use bevy::prelude::*;
 
-use crate::{ActiveCameraData, PanOrbitCamera};
+use crate::{ActiveCameraData, PanOrbitCamera, FALLBACK_DT};
+#[cfg(feature = "bevy_egui")]
+use crate::{EguiInputReacquireDelay, EguiWantsFocus};
+
+/// Controls for double tapping a mouse button, which is how trackpad taps are reported, e.g. to
+/// reset the view. Two taps count as a double tap if each is released within `max_interval` of
+/// being pressed, the second is released within `max_interval` of the first, and the cursor
+/// moves no more than `max_movement` during each tap.
+#[derive(Reflect, Debug, Copy, Clone, PartialEq)]
+pub struct DoubleTapControls {
+    /// The button to double tap. Trackpad taps are usually reported as `MouseButton::Left`.
+    pub button: MouseButton,
+    /// What to do when a double tap is detected.
+    pub action: DoubleTapAction,
+    /// The maximum time, in seconds, a tap can last, and between the two taps.
+    pub max_interval: f32,
+    /// The maximum distance, in pixels, the cursor can move during a tap.
+    pub max_movement: f32,
+}
+
+impl Default for DoubleTapControls {
+    fn default() -> Self {
+        Self {
+            button: MouseButton::Left,
+            action: DoubleTapAction::default(),
+            max_interval: 0.3,
+            max_movement: 4.0,
+        }
+    }
+}
+
+/// The action to take when a double tap is detected. See `DoubleTapControls`.
+#[derive(Reflect, Default, Debug, Copy, Clone, PartialEq)]
+pub enum DoubleTapAction {
+    /// Smoothly return to `PanOrbitCamera::home_state`. See `PanOrbitCamera::reset_view`.
+    #[default]
+    ResetView,
+    /// Smoothly pan back to `PanOrbitCamera::home_focus`. See `PanOrbitCamera::recenter_focus`.
+    RecenterFocus,
+    /// Smoothly pan so the point under the cursor becomes the focus, e.g. double click on
+    /// something to orbit around it. The crate doesn't know about the scene's geometry, so the
+    /// point is taken to be on the plane through the current focus, facing the camera. Uses
+    /// `ActiveCameraData::cursor_position`, and does nothing without it.
+    FocusCursor,
+}
+
+/// Which input source wins when mouse and touch input arrive at the same time. See
+/// `PanOrbitCamera::input_priority`.
+#[derive(Reflect, Default, Debug, Copy, Clone, PartialEq)]
+pub enum InputPriority {
+    /// Ignore touch input while the mouse is moving or scrolling.
+    MouseFirst,
+    /// Ignore mouse input while any finger is touching the screen.
+    TouchFirst,
+    /// Apply both, adding them together.
+    #[default]
+    Both,
+}
+
+/// Input from a six degrees of freedom device, like a 3D mouse. Insert this resource and write the
+/// device's deltas to it each frame, and the active `PanOrbitCamera` will consume them:
+///  - `translation.x` and `translation.y` pan the camera, in normalized viewport units like
+///    `PanOrbitCamera::pan_by_ndc`
+///  - `translation.z` zooms, in scroll wheel lines, where positive moves the camera away from the
+///    focus
+///  - `rotation.x` and `rotation.y` orbit the camera, in radians of pitch and yaw respectively
+///  - `rotation.z` rolls the camera around the view direction, in radians, but only when
+///    `PanOrbitCamera::globe_mode` is enabled, as otherwise the horizon is always level. The roll
+///    is limited by `PanOrbitCamera::max_roll`
+///
+/// The camera's sensitivities, `reversed_zoom`, and `orbit_style` apply as for mouse input.
+/// The deltas are reset to zero once consumed, so no device library is needed, just a system
+/// that reads your device and writes to this resource.
+#[derive(Resource, Reflect, Debug, Copy, Clone, PartialEq)]
+pub struct SixDofInput {
+    /// Movement of the camera, relative to the view.
+    pub translation: Vec3,
+    /// Rotation of the camera around the focus, in radians.
+    pub rotation: Vec3,
+    /// Multiplier for each axis of `translation`.
+    /// Defaults to `Vec3::ONE`.
+    pub translation_sensitivity: Vec3,
+    /// Multiplier for each axis of `rotation`.
+    /// Defaults to `Vec3::ONE`.
+    pub rotation_sensitivity: Vec3,
+}
+
+impl Default for SixDofInput {
+    fn default() -> Self {
+        Self {
+            translation: Vec3::ZERO,
+            rotation: Vec3::ZERO,
+            translation_sensitivity: Vec3::ONE,
+            rotation_sensitivity: Vec3::ONE,
+        }
+    }
+}
+
+/// Orbit, pan, and zoom input from your own controls, e.g. an on-screen drag pad or virtual
+/// joystick. Write to this resource each frame, and the camera will treat it like mouse input. It's
+/// additive, so it combines with input from the mouse, touch, and keyboard, and it's in the same
+/// units as accumulated mouse motion, so sensitivities and `reversed_zoom` apply as normal.
+/// The deltas are reset to zero once consumed. `PanOrbitCameraPlugin` adds this resource.
+///
+/// For controls that are held rather than dragged, like a virtual joystick, use
+/// `set_orbit_joystick` and `set_zoom_axis` instead. These are velocities rather than deltas, so
+/// they keep moving the camera at a frame rate independent speed until they're set back to zero.
+#[derive(Resource, Reflect, Default, Debug, Copy, Clone, PartialEq)]
+pub struct CameraInput {
+    /// Orbit, in pixels, like dragging with `PanOrbitCamera::button_orbit`.
+    pub orbit: Vec2,
+    /// Pan, in pixels, like dragging with `PanOrbitCamera::button_pan`.
+    pub pan: Vec2,
+    /// Zoom, in scroll wheel lines, where positive zooms in.
+    pub zoom: f32,
+    /// Continuous orbit, with each axis from `-1.0` to `1.0`. See `set_orbit_joystick`.
+    pub orbit_joystick: Vec2,
+    /// Continuous zoom, from `-1.0` to `1.0`. See `set_zoom_axis`.
+    pub zoom_axis: f32,
+    /// The camera to control. If `None`, the active camera is controlled. Set this if interacting
+    /// with your controls can make no camera active, e.g. when they're drawn with egui and the
+    /// `bevy_egui` feature is enabled.
+    pub camera: Option<Entity>,
+}
+
+impl CameraInput {
+    /// Orbit continuously, e.g. from a virtual joystick, where each axis is from `-1.0` to `1.0`
+    /// and values outside that are clamped. The directions are the same as dragging, in screen
+    /// space, so positive `x` orbits like dragging right, and positive `y` like dragging down. At
+    /// full deflection, the camera orbits at `PanOrbitCamera::joystick_orbit_speed`, scaled by
+    /// `orbit_sensitivity`. This is held until it's set again, so set it to `Vec2::ZERO` when the
+    /// joystick is released.
+    pub fn set_orbit_joystick(&mut self, joystick: Vec2) {
+        self.orbit_joystick = joystick.clamp(Vec2::NEG_ONE, Vec2::ONE);
+    }
+
+    /// Zoom continuously, e.g. from a virtual slider, from `-1.0` to `1.0`, where positive zooms
+    /// in, and values outside that are clamped. At full deflection, the camera zooms at
+    /// `PanOrbitCamera::joystick_zoom_speed`, scaled by `zoom_sensitivity`. This is held until it's
+    /// set again, so set it to `0.0` when the control is released.
+    pub fn set_zoom_axis(&mut self, axis: f32) {
+        self.zoom_axis = axis.clamp(-1.0, 1.0);
+    }
+}
+
+/// The point under the cursor, for cameras with `PanOrbitCamera::orbit_around_cursor` enabled.
+/// Insert this resource and update it each frame, e.g. by ray casting from the cursor, and set
+/// `point` to `None` when nothing is under the cursor. Keep it unchanged while the orbit button is
+/// held, as the camera turns to look at the point. The point is in the same space as
+/// `PanOrbitCamera::focus`, i.e. world space unless the camera has a parent.
+#[derive(Resource, Reflect, Default, Debug, Copy, Clone, PartialEq)]
+pub struct CursorPivot {
+    /// The point under the cursor, if any.
+    pub point: Option<Vec3>,
+}
 
 #[derive(Resource, Default, Debug)]
 pub struct MouseKeyTracker {
@@ -10,8 +165,62 @@ pub struct MouseKeyTracker {
     pub scroll_line: f32,
     pub scroll_pixel: f32,
     pub orbit_button_changed: bool,
+    pub double_tapped: bool,
+    pub double_tap_detector: DoubleTapDetector,
+    /// What was lost converting the mouse motion to f32, carried over to the next frame so slow
+    /// trackpad movements still add up exactly
+    pub(crate) mouse_residual: DVec2,
+    /// Like `mouse_residual`, for the line and pixel scroll
+    pub(crate) scroll_residual: DVec2,
+    /// When a mouse button was last pressed
+    #[cfg(feature = "bevy_egui")]
+    pub last_press_time: Option<f32>,
+    /// When egui last wanted focus
+    #[cfg(feature = "bevy_egui")]
+    pub egui_focus_time: Option<f32>,
 }
 
+/// Tracks presses of a single button to detect double taps
+#[derive(Default, Debug)]
+pub struct DoubleTapDetector {
+    /// When the current press started, and how far the cursor has moved since
+    press: Option<(f32, f32)>,
+    /// When the last tap was released
+    last_tap: Option<f32>,
+}
+
+impl DoubleTapDetector {
+    pub fn press(&mut self, now: f32) {
+        self.press = Some((now, 0.0));
+    }
+
+    pub fn moved(&mut self, distance: f32) {
+        if let Some((_, moved)) = &mut self.press {
+            *moved += distance;
+        }
+    }
+
+    /// Returns `true` if this release completes a double tap
+    pub fn release(&mut self, now: f32, controls: &DoubleTapControls) -> bool {
+        let Some((pressed_at, moved)) = self.press.take() else {
+            return false;
+        };
+        let is_tap = now - pressed_at <= controls.max_interval && moved <= controls.max_movement;
+        if !is_tap {
+            self.last_tap = None;
+            return false;
+        }
+        match self.last_tap.take() {
+            Some(last_tap) if now - last_tap <= controls.max_interval => true,
+            _ => {
+                self.last_tap = Some(now);
+                false
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn mouse_key_tracker(
     mut camera_movement: ResMut<MouseKeyTracker>,
     mouse_input: Res<ButtonInput<MouseButton>>,
@@ -20,7 +229,39 @@ pub fn mouse_key_tracker(
     mut scroll_events: EventReader<MouseWheel>,
     active_cam: Res<ActiveCameraData>,
     orbit_cameras: Query<&PanOrbitCamera>,
+    time: Option<Res<Time>>,
+    mut fallback_elapsed: Local<f32>,
+    #[cfg(feature = "bevy_egui")] egui: (Res<EguiWantsFocus>, Res<EguiInputReacquireDelay>),
 ) {
+    // Without a `Time` resource, count each frame as lasting `FALLBACK_DT`
+    *fallback_elapsed += FALLBACK_DT;
+    let now = time.map_or(*fallback_elapsed, |time| time.elapsed_secs());
+    // Whether to ignore buttons that were held down while egui had focus
+    #[allow(unused_mut)]
+    let mut ignore_held = false;
+    // Whether to ignore clicks for double taps, because they're on egui
+    #[allow(unused_mut, unused_assignments)]
+    let mut ignore_double_tap = false;
+    #[cfg(feature = "bevy_egui")]
+    {
+        let (egui_wants_focus, reacquire_delay) = egui;
+        ignore_double_tap = egui_wants_focus.camera_should_ignore();
+        if mouse_input.get_just_pressed().next().is_some() {
+            camera_movement.last_press_time = Some(now);
+        }
+        if reacquire_delay.0 > 0.0 {
+            if egui_wants_focus.camera_should_ignore() {
+                camera_movement.egui_focus_time = Some(now);
+            }
+            if let Some(focus_time) = camera_movement.egui_focus_time {
+                ignore_held = now - focus_time <= reacquire_delay.0
+                    && camera_movement
+                        .last_press_time
+                        .is_none_or(|press_time| press_time <= focus_time);
+            }
+        }
+    }
+
     if let Some(active_entity) = active_cam.entity {
         if let Ok(pan_orbit) = orbit_cameras.get(active_entity) {
             let mut orbit = Vec2::ZERO;
@@ -29,18 +270,34 @@ pub fn mouse_key_tracker(
             let mut scroll_pixel = 0.0;
             let mut orbit_button_changed = false;
 
-            // Collect input deltas
-            let mouse_delta = mouse_motion.read().map(|event| event.delta).sum::<Vec2>();
-            let (scroll_line_delta, scroll_pixel_delta) = scroll_events
+            // Collect input deltas. High-DPI trackpads send lots of tiny deltas, which are summed
+            // in f64, and the rounding to f32 is carried over to the next frame, so that slow
+            // movements don't lose precision
+            let mouse_delta = mouse_motion
+                .read()
+                .map(|event| event.delta.as_dvec2())
+                .sum::<DVec2>()
+                + camera_movement.mouse_residual;
+            camera_movement.mouse_residual = mouse_delta - mouse_delta.as_vec2().as_dvec2();
+            let mouse_delta = mouse_delta.as_vec2();
+            let scroll_delta = scroll_events
                 .read()
                 .map(|event| match event.unit {
-                    MouseScrollUnit::Line => (event.y, 0.0),
-                    MouseScrollUnit::Pixel => (0.0, event.y * 0.005),
+                    MouseScrollUnit::Line => DVec2::new(event.y as f64, 0.0),
+                    MouseScrollUnit::Pixel => DVec2::new(0.0, event.y as f64 * 0.005),
                 })
-                .fold((0.0, 0.0), |acc, item| (acc.0 + item.0, acc.1 + item.1));
+                .sum::<DVec2>()
+                + camera_movement.scroll_residual;
+            camera_movement.scroll_residual = scroll_delta - scroll_delta.as_vec2().as_dvec2();
+            let Vec2 {
+                x: scroll_line_delta,
+                y: scroll_pixel_delta,
+            } = scroll_delta.as_vec2();
 
             // Orbit and pan
-            if orbit_pressed(pan_orbit, &mouse_input, &key_input) {
+            if ignore_held {
+                // Wait for a fresh press
+            } else if orbit_pressed(pan_orbit, &mouse_input, &key_input) {
                 orbit += mouse_delta;
             } else if pan_pressed(pan_orbit, &mouse_input, &key_input) {
                 // Pan only if we're not rotating at the moment
@@ -58,91 +315,413 @@ pub fn mouse_key_tracker(
                 orbit_button_changed = true;
             }
 
+            let mut double_tapped = false;
+            if ignore_double_tap {
+                camera_movement.double_tap_detector = DoubleTapDetector::default();
+            } else if let Some(double_tap) = pan_orbit.double_tap {
+                let detector = &mut camera_movement.double_tap_detector;
+                if mouse_input.just_pressed(double_tap.button) {
+                    detector.press(now);
+                } else {
+                    detector.moved(mouse_delta.length());
+                }
+                if mouse_input.just_released(double_tap.button) {
+                    double_tapped = detector.release(now, &double_tap);
+                }
+            }
+
             camera_movement.orbit = orbit;
             camera_movement.pan = pan;
             camera_movement.scroll_line = scroll_line;
             camera_movement.scroll_pixel = scroll_pixel;
             camera_movement.orbit_button_changed = orbit_button_changed;
+            camera_movement.double_tapped = double_tapped;
         }
     }
 }
 
+/// Whether the modifier of the other action (orbit or pan) is released, so that holding it
+/// switches to that action. If both actions use the same modifier, holding it can't tell them
+/// apart, so only the buttons are used to decide
+fn other_modifier_released(
+    modifier: Option<KeyCode>,
+    other_modifier: Option<KeyCode>,
+    key_input: &ButtonInput<KeyCode>,
+) -> bool {
+    other_modifier
+        .filter(|other_modifier| Some(*other_modifier) != modifier)
+        .is_none_or(|other_modifier| !key_input.pressed(other_modifier))
+}
+
 pub fn orbit_pressed(
     pan_orbit: &PanOrbitCamera,
-    mouse_input: &Res<ButtonInput<MouseButton>>,
-    key_input: &Res<ButtonInput<KeyCode>>,
+    mouse_input: &ButtonInput<MouseButton>,
+    key_input: &ButtonInput<KeyCode>,
 ) -> bool {
+    if pan_orbit.drag_orbit_anywhere {
+        return mouse_input.pressed(MouseButton::Left);
+    }
     let is_pressed = pan_orbit
         .modifier_orbit
         .map_or(true, |modifier| key_input.pressed(modifier))
         && mouse_input.pressed(pan_orbit.button_orbit);
 
     is_pressed
-        && pan_orbit
-            .modifier_pan
-            .map_or(true, |modifier| !key_input.pressed(modifier))
+        && other_modifier_released(pan_orbit.modifier_orbit, pan_orbit.modifier_pan, key_input)
 }
 
 pub fn orbit_just_pressed(
     pan_orbit: &PanOrbitCamera,
-    mouse_input: &Res<ButtonInput<MouseButton>>,
-    key_input: &Res<ButtonInput<KeyCode>>,
+    mouse_input: &ButtonInput<MouseButton>,
+    key_input: &ButtonInput<KeyCode>,
 ) -> bool {
+    if pan_orbit.drag_orbit_anywhere {
+        return mouse_input.just_pressed(MouseButton::Left);
+    }
     let just_pressed = pan_orbit
         .modifier_orbit
         .map_or(true, |modifier| key_input.pressed(modifier))
         && (mouse_input.just_pressed(pan_orbit.button_orbit));
 
     just_pressed
-        && pan_orbit
-            .modifier_pan
-            .map_or(true, |modifier| !key_input.pressed(modifier))
+        && other_modifier_released(pan_orbit.modifier_orbit, pan_orbit.modifier_pan, key_input)
 }
 
 pub fn orbit_just_released(
     pan_orbit: &PanOrbitCamera,
-    mouse_input: &Res<ButtonInput<MouseButton>>,
-    key_input: &Res<ButtonInput<KeyCode>>,
+    mouse_input: &ButtonInput<MouseButton>,
+    key_input: &ButtonInput<KeyCode>,
 ) -> bool {
+    if pan_orbit.drag_orbit_anywhere {
+        return mouse_input.just_released(MouseButton::Left);
+    }
     let just_released = pan_orbit
         .modifier_orbit
         .map_or(true, |modifier| key_input.pressed(modifier))
         && (mouse_input.just_released(pan_orbit.button_orbit));
 
     just_released
-        && pan_orbit
-            .modifier_pan
-            .map_or(true, |modifier| !key_input.pressed(modifier))
+        && other_modifier_released(pan_orbit.modifier_orbit, pan_orbit.modifier_pan, key_input)
 }
 
 pub fn pan_pressed(
     pan_orbit: &PanOrbitCamera,
-    mouse_input: &Res<ButtonInput<MouseButton>>,
-    key_input: &Res<ButtonInput<KeyCode>>,
+    mouse_input: &ButtonInput<MouseButton>,
+    key_input: &ButtonInput<KeyCode>,
 ) -> bool {
+    if pan_orbit.drag_orbit_anywhere {
+        return false;
+    }
     let is_pressed = pan_orbit
         .modifier_pan
         .map_or(true, |modifier| key_input.pressed(modifier))
         && mouse_input.pressed(pan_orbit.button_pan);
 
     is_pressed
-        && pan_orbit
-            .modifier_orbit
-            .map_or(true, |modifier| !key_input.pressed(modifier))
+        && other_modifier_released(pan_orbit.modifier_pan, pan_orbit.modifier_orbit, key_input)
 }
 
 pub fn pan_just_pressed(
     pan_orbit: &PanOrbitCamera,
-    mouse_input: &Res<ButtonInput<MouseButton>>,
-    key_input: &Res<ButtonInput<KeyCode>>,
+    mouse_input: &ButtonInput<MouseButton>,
+    key_input: &ButtonInput<KeyCode>,
 ) -> bool {
+    if pan_orbit.drag_orbit_anywhere {
+        return false;
+    }
     let just_pressed = pan_orbit
         .modifier_pan
         .map_or(true, |modifier| key_input.pressed(modifier))
         && (mouse_input.just_pressed(pan_orbit.button_pan));
 
     just_pressed
-        && pan_orbit
-            .modifier_orbit
-            .map_or(true, |modifier| !key_input.pressed(modifier))
+        && other_modifier_released(pan_orbit.modifier_pan, pan_orbit.modifier_orbit, key_input)
+}
+
+#[cfg(test)]
+mod mouse_key_tracker_tests {
+    use super::*;
+
+    /// An app that runs just `mouse_key_tracker`, with a camera that's already active
+    pub(super) fn setup_app() -> App {
+        let mut app = App::new();
+        app.init_resource::<MouseKeyTracker>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<Time>()
+            .add_event::<MouseMotion>()
+            .add_event::<MouseWheel>()
+            .add_systems(Update, mouse_key_tracker);
+        #[cfg(feature = "bevy_egui")]
+        app.init_resource::<EguiWantsFocus>()
+            .init_resource::<EguiInputReacquireDelay>();
+        let entity = app.world_mut().spawn(PanOrbitCamera::default()).id();
+        app.insert_resource(ActiveCameraData {
+            entity: Some(entity),
+            ..default()
+        });
+        app
+    }
+
+    #[test]
+    fn tiny_deltas_accumulate_without_loss() {
+        let mut app = setup_app();
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        let window = app.world_mut().spawn_empty().id();
+        for _ in 0..100_000 {
+            app.world_mut().send_event(MouseMotion {
+                delta: Vec2::new(0.01, -0.01),
+            });
+            app.world_mut().send_event(MouseWheel {
+                unit: MouseScrollUnit::Pixel,
+                x: 0.0,
+                y: 0.01,
+                window,
+            });
+        }
+        app.update();
+        let tracker = app.world().resource::<MouseKeyTracker>();
+        // Summing in f32 would be off by around 0.003 for the scroll, and more for the motion
+        assert!(tracker.orbit.abs_diff_eq(Vec2::new(1000.0, -1000.0), 0.001));
+        assert!((tracker.scroll_pixel - 5.0).abs() < 0.00001);
+    }
+
+    #[test]
+    fn tiny_deltas_accumulate_across_frames() {
+        let mut app = setup_app();
+        let window = app.world_mut().spawn_empty().id();
+        let mut scroll_pixel = 0.0;
+        for _ in 0..10_000 {
+            app.world_mut().send_event(MouseWheel {
+                unit: MouseScrollUnit::Pixel,
+                x: 0.0,
+                y: 0.3,
+                window,
+            });
+            app.update();
+            scroll_pixel += app.world().resource::<MouseKeyTracker>().scroll_pixel as f64;
+        }
+        // Rounding each frame's scroll to f32 separately would be off by around 5e-7
+        let expected = 10_000.0 * 0.3f32 as f64 * 0.005;
+        assert!((scroll_pixel - expected).abs() < 1e-9);
+    }
+}
+
+#[cfg(all(test, feature = "bevy_egui"))]
+mod reacquire_delay_tests {
+    use super::mouse_key_tracker_tests::setup_app;
+    use super::*;
+    use std::time::Duration;
+
+    fn drag(app: &mut App) -> Vec2 {
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(0.01));
+        app.world_mut().send_event(MouseMotion {
+            delta: Vec2::new(10.0, 0.0),
+        });
+        app.update();
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .clear();
+        app.world().resource::<MouseKeyTracker>().orbit
+    }
+
+    fn set_egui_focus(app: &mut App, focus: bool) {
+        let mut egui_wants_focus = app.world_mut().resource_mut::<EguiWantsFocus>();
+        egui_wants_focus.prev = egui_wants_focus.curr;
+        egui_wants_focus.curr = focus;
+    }
+
+    #[test]
+    fn held_button_ignored_after_ui_release_until_pressed_again() {
+        let mut app = setup_app();
+        app.insert_resource(EguiInputReacquireDelay(0.1));
+        // Start dragging a slider
+        set_egui_focus(&mut app, true);
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        drag(&mut app);
+        // egui lets go, but the button is still down
+        set_egui_focus(&mut app, false);
+        drag(&mut app);
+        set_egui_focus(&mut app, false);
+        assert_eq!(drag(&mut app), Vec2::ZERO);
+        assert_eq!(drag(&mut app), Vec2::ZERO);
+        // A fresh press orbits straight away
+        let mut mouse_input = app.world_mut().resource_mut::<ButtonInput<MouseButton>>();
+        mouse_input.release(MouseButton::Left);
+        mouse_input.press(MouseButton::Left);
+        assert_eq!(drag(&mut app), Vec2::new(10.0, 0.0));
+    }
+
+    #[test]
+    fn held_button_works_again_after_delay() {
+        let mut app = setup_app();
+        app.insert_resource(EguiInputReacquireDelay(0.1));
+        set_egui_focus(&mut app, true);
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        drag(&mut app);
+        set_egui_focus(&mut app, false);
+        drag(&mut app);
+        set_egui_focus(&mut app, false);
+        for _ in 0..10 {
+            drag(&mut app);
+        }
+        assert_eq!(drag(&mut app), Vec2::new(10.0, 0.0));
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let mut app = setup_app();
+        set_egui_focus(&mut app, true);
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        drag(&mut app);
+        set_egui_focus(&mut app, false);
+        assert_eq!(drag(&mut app), Vec2::new(10.0, 0.0));
+    }
+}
+
+#[cfg(test)]
+mod modifier_tests {
+    use super::*;
+
+    fn input(
+        buttons: &[MouseButton],
+        keys: &[KeyCode],
+    ) -> (ButtonInput<MouseButton>, ButtonInput<KeyCode>) {
+        let mut mouse_input = ButtonInput::default();
+        for button in buttons {
+            mouse_input.press(*button);
+        }
+        let mut key_input = ButtonInput::default();
+        for key in keys {
+            key_input.press(*key);
+        }
+        (mouse_input, key_input)
+    }
+
+    #[test]
+    fn different_modifiers_are_exclusive() {
+        let pan_orbit = PanOrbitCamera {
+            button_pan: MouseButton::Left,
+            modifier_orbit: Some(KeyCode::AltLeft),
+            modifier_pan: Some(KeyCode::ShiftLeft),
+            ..default()
+        };
+        let (mouse, keys) = input(&[MouseButton::Left], &[KeyCode::AltLeft]);
+        assert!(orbit_pressed(&pan_orbit, &mouse, &keys));
+        assert!(!pan_pressed(&pan_orbit, &mouse, &keys));
+        let (mouse, keys) = input(&[MouseButton::Left], &[KeyCode::ShiftLeft]);
+        assert!(!orbit_pressed(&pan_orbit, &mouse, &keys));
+        assert!(pan_pressed(&pan_orbit, &mouse, &keys));
+        let (mouse, keys) = input(
+            &[MouseButton::Left],
+            &[KeyCode::AltLeft, KeyCode::ShiftLeft],
+        );
+        assert!(!orbit_pressed(&pan_orbit, &mouse, &keys));
+        assert!(!pan_pressed(&pan_orbit, &mouse, &keys));
+    }
+
+    #[test]
+    fn drag_orbit_anywhere_ignores_configured_buttons() {
+        let pan_orbit = PanOrbitCamera {
+            button_orbit: MouseButton::Right,
+            button_pan: MouseButton::Left,
+            modifier_orbit: Some(KeyCode::AltLeft),
+            drag_orbit_anywhere: true,
+            ..default()
+        };
+        let (mouse, keys) = input(&[MouseButton::Left], &[]);
+        assert!(orbit_pressed(&pan_orbit, &mouse, &keys));
+        assert!(orbit_just_pressed(&pan_orbit, &mouse, &keys));
+        assert!(!pan_pressed(&pan_orbit, &mouse, &keys));
+        assert!(!pan_just_pressed(&pan_orbit, &mouse, &keys));
+        let (mouse, keys) = input(&[MouseButton::Right], &[KeyCode::AltLeft]);
+        assert!(!orbit_pressed(&pan_orbit, &mouse, &keys));
+        assert!(!pan_pressed(&pan_orbit, &mouse, &keys));
+    }
+
+    #[test]
+    fn same_modifier_uses_buttons() {
+        let pan_orbit = PanOrbitCamera {
+            modifier_orbit: Some(KeyCode::ShiftLeft),
+            modifier_pan: Some(KeyCode::ShiftLeft),
+            ..default()
+        };
+        let (mouse, keys) = input(&[MouseButton::Left], &[KeyCode::ShiftLeft]);
+        assert!(orbit_pressed(&pan_orbit, &mouse, &keys));
+        assert!(!pan_pressed(&pan_orbit, &mouse, &keys));
+        let (mouse, keys) = input(&[MouseButton::Right], &[KeyCode::ShiftLeft]);
+        assert!(!orbit_pressed(&pan_orbit, &mouse, &keys));
+        assert!(pan_pressed(&pan_orbit, &mouse, &keys));
+        // The modifier is still required
+        let (mouse, keys) = input(&[MouseButton::Left, MouseButton::Right], &[]);
+        assert!(!orbit_pressed(&pan_orbit, &mouse, &keys));
+        assert!(!pan_pressed(&pan_orbit, &mouse, &keys));
+    }
+
+    #[test]
+    fn one_modifier_switches_shared_button() {
+        let pan_orbit = PanOrbitCamera {
+            button_pan: MouseButton::Left,
+            modifier_pan: Some(KeyCode::ShiftLeft),
+            ..default()
+        };
+        let (mouse, keys) = input(&[MouseButton::Left], &[]);
+        assert!(orbit_pressed(&pan_orbit, &mouse, &keys));
+        assert!(!pan_pressed(&pan_orbit, &mouse, &keys));
+        let (mouse, keys) = input(&[MouseButton::Left], &[KeyCode::ShiftLeft]);
+        assert!(!orbit_pressed(&pan_orbit, &mouse, &keys));
+        assert!(pan_pressed(&pan_orbit, &mouse, &keys));
+    }
+}
+
+#[cfg(test)]
+mod double_tap_detector_tests {
+    use super::*;
+
+    fn tap(detector: &mut DoubleTapDetector, at: f32, duration: f32) -> bool {
+        detector.press(at);
+        detector.release(at + duration, &DoubleTapControls::default())
+    }
+
+    #[test]
+    fn quick_taps_are_a_double_tap() {
+        let mut detector = DoubleTapDetector::default();
+        assert!(!tap(&mut detector, 0.0, 0.05));
+        assert!(tap(&mut detector, 0.2, 0.05));
+        // The double tap is consumed, so a third tap starts over
+        assert!(!tap(&mut detector, 0.4, 0.05));
+    }
+
+    #[test]
+    fn slow_double_click_is_not_a_double_tap() {
+        let mut detector = DoubleTapDetector::default();
+        assert!(!tap(&mut detector, 0.0, 0.05));
+        assert!(!tap(&mut detector, 0.5, 0.05));
+    }
+
+    #[test]
+    fn long_press_is_not_a_tap() {
+        let mut detector = DoubleTapDetector::default();
+        assert!(!tap(&mut detector, 0.0, 0.05));
+        assert!(!tap(&mut detector, 0.1, 0.5));
+    }
+
+    #[test]
+    fn moving_during_tap_is_not_a_tap() {
+        let mut detector = DoubleTapDetector::default();
+        assert!(!tap(&mut detector, 0.0, 0.05));
+        detector.press(0.1);
+        detector.moved(10.0);
+        assert!(!detector.release(0.15, &DoubleTapControls::default()));
+    }
 }
@@ -3,29 +3,51 @@
 
 use std::f32::consts::{PI, TAU};
 
+use bevy::ecs::entity::EntityHashMap;
+use bevy::ecs::system::SystemParam;
 use bevy::input::mouse::MouseWheel;
 use bevy::prelude::*;
-use bevy::render::camera::{CameraUpdateSystem, RenderTarget};
+use bevy::render::camera::{CameraProjection, CameraUpdateSystem, RenderTarget};
+use bevy::render::primitives::Aabb;
 use bevy::transform::TransformSystem;
 use bevy::window::{PrimaryWindow, WindowRef};
 #[cfg(feature = "bevy_egui")]
 use bevy_egui::EguiSet;
 
+#[cfg(feature = "debug")]
+pub use crate::debug::PanOrbitCameraDebugInfo;
 #[cfg(feature = "bevy_egui")]
-pub use crate::egui::{EguiFocusIncludesHover, EguiWantsFocus};
+pub use crate::egui::{EguiFocusIncludesHover, EguiInputReacquireDelay, EguiWantsFocus};
+#[cfg(feature = "debug_gizmos")]
+pub use crate::gizmos::{FocusBoundsGizmosPlugin, FocusTrailGizmosPlugin, FocusTrailSettings};
 use crate::input::{mouse_key_tracker, MouseKeyTracker};
+pub use crate::input::{
+    CameraInput, CursorPivot, DoubleTapAction, DoubleTapControls, InputPriority, SixDofInput,
+};
+pub use crate::numpad::NumpadViewsPlugin;
+use crate::recording::record_camera_input;
+pub use crate::recording::{CameraInputFrame, CameraInputRecording};
 pub use crate::touch::TouchControls;
-use crate::touch::{touch_tracker, TouchGestures, TouchTracker};
+use crate::touch::{touch_tracker, TouchGestures, TouchTracker, TwoFingerGestures};
 use crate::traits::OptionalClamp;
 
+#[cfg(feature = "debug")]
+mod debug;
 #[cfg(feature = "bevy_egui")]
 mod egui;
+#[cfg(feature = "debug_gizmos")]
+mod gizmos;
 mod input;
+mod numpad;
+mod recording;
 mod touch;
 mod traits;
 mod util;
 
 /// Bevy plugin that contains the systems for controlling `PanOrbitCamera` components.
+/// The cameras are smoothed using the `Time` resource, which `MinimalPlugins` and `DefaultPlugins`
+/// add. Without it, e.g. in a stripped-down app or test, every frame is treated as lasting a
+/// sixtieth of a second.
 /// # Example
 /// ```no_run
 /// # use bevy::prelude::*;
@@ -44,27 +66,76 @@ impl Plugin for PanOrbitCameraPlugin {
         app.init_resource::<ActiveCameraData>()
             .init_resource::<MouseKeyTracker>()
             .init_resource::<TouchTracker>()
+            .init_resource::<DisableSmoothing>()
+            .init_resource::<CameraInput>()
+            .add_event::<PanOrbitCameraChanged>()
             .add_systems(
                 PostUpdate,
                 (
                     (
-                        active_viewport_data
-                            .run_if(|active_cam: Res<ActiveCameraData>| !active_cam.manual),
+                        active_viewport_data.run_if(
+                            |active_cam: Res<ActiveCameraData>,
+                             explicit: Option<Res<ExplicitActiveCamera>>| {
+                                !active_cam.manual && explicit.is_none()
+                            },
+                        ),
+                        explicit_active_camera_data.run_if(
+                            |active_cam: Res<ActiveCameraData>,
+                             explicit: Option<Res<ExplicitActiveCamera>>| {
+                                !active_cam.manual && explicit.is_some()
+                            },
+                        ),
+                        auto_fill_viewport_sizes.run_if(
+                            |active_cam: Res<ActiveCameraData>,
+                             auto_fill: Option<Res<AutoFillViewportSizes>>| {
+                                active_cam.manual && auto_fill.is_some()
+                            },
+                        ),
                         mouse_key_tracker,
-                        touch_tracker,
+                        active_cursor_position
+                            .run_if(
+                                |active_cam: Res<ActiveCameraData>,
+                                 auto_fill: Option<Res<AutoFillViewportSizes>>| {
+                                    !active_cam.manual || auto_fill.is_some()
+                                },
+                            )
+                            .after(active_viewport_data)
+                            .after(explicit_active_camera_data)
+                            .after(auto_fill_viewport_sizes),
+                        // Needs this frame's active camera to filter touches by its viewport
+                        touch_tracker
+                            .after(active_viewport_data)
+                            .after(explicit_active_camera_data),
                     ),
+                    record_camera_input,
+                    follow_targets,
                     pan_orbit_camera,
+                    send_change_events,
                 )
                     .chain()
                     .in_set(PanOrbitCameraSystemSet)
                     .before(TransformSystem::TransformPropagate)
                     .before(CameraUpdateSystem),
+            )
+            .add_systems(
+                PostUpdate,
+                (warn_invalid_config, insert_missing_projection).before(PanOrbitCameraSystemSet),
+            )
+            // Runs after the camera system so it sees this frame's viewport size
+            .add_systems(PostUpdate, refit_on_resize.after(CameraUpdateSystem))
+            .configure_sets(
+                PostUpdate,
+                PanOrbitCameraPostProcessSet
+                    .after(PanOrbitCameraSystemSet)
+                    .before(TransformSystem::TransformPropagate)
+                    .before(CameraUpdateSystem),
             );
 
         #[cfg(feature = "bevy_egui")]
         {
             app.init_resource::<EguiWantsFocus>()
                 .init_resource::<EguiFocusIncludesHover>()
+                .init_resource::<EguiInputReacquireDelay>()
                 .add_systems(
                     PostUpdate,
                     egui::check_egui_wants_focus
@@ -72,13 +143,54 @@ impl Plugin for PanOrbitCameraPlugin {
                         .before(PanOrbitCameraSystemSet),
                 );
         }
+
+        #[cfg(feature = "debug")]
+        {
+            app.init_resource::<PanOrbitCameraDebugInfo>().add_systems(
+                PostUpdate,
+                debug::update_debug_info.after(PanOrbitCameraSystemSet),
+            );
+        }
     }
 }
 
+/// When true, all `PanOrbitCamera`s move instantly to their target values every frame, as if
+/// `orbit_smoothness`, `pan_smoothness`, and `zoom_smoothness` were all `0.0`, and
+/// `max_pan_speed` and transitions are ignored. This makes each camera's state a deterministic
+/// function of its inputs, independent of frame timing, e.g. for lockstep networking or replays.
+#[derive(Resource, PartialEq, Eq, Default)]
+pub struct DisableSmoothing(pub bool);
+
 /// Base system set to allow ordering of `PanOrbitCamera`
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
 pub struct PanOrbitCameraSystemSet;
 
+/// System set, in `PostUpdate`, that runs after `PanOrbitCamera` has updated the camera's
+/// `Transform`, but before transforms are propagated. Add systems to this set to apply your own
+/// constraints to the final transform, e.g. snapping the camera to a grid.
+/// Note that changes made to the `Transform` here don't feed back into `PanOrbitCamera`, so the
+/// camera's `yaw`, `pitch`, `radius`, and `focus` won't reflect them.
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_panorbit_camera::{PanOrbitCameraPlugin, PanOrbitCamera, PanOrbitCameraPostProcessSet};
+/// fn main() {
+///     App::new()
+///         .add_plugins(DefaultPlugins)
+///         .add_plugins(PanOrbitCameraPlugin)
+///         .add_systems(PostUpdate, snap_to_grid.in_set(PanOrbitCameraPostProcessSet))
+///         .run();
+/// }
+///
+/// fn snap_to_grid(mut cameras: Query<&mut Transform, With<PanOrbitCamera>>) {
+///     for mut transform in cameras.iter_mut() {
+///         transform.translation = transform.translation.round();
+///     }
+/// }
+/// ```
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
+pub struct PanOrbitCameraPostProcessSet;
+
 /// Tags an entity as capable of panning and orbiting, and provides a way to configure the
 /// camera's behaviour and controls.
 /// # Example
@@ -106,18 +218,23 @@ pub struct PanOrbitCamera {
     /// The point to orbit around, and what the camera looks at. Updated automatically.
     /// If you want to change the focus programmatically after initialization, set `target_focus`
     /// instead.
+    /// Like the camera's `Transform`, this is relative to the parent entity, if there is one. Use
+    /// `set_focus_world` to focus on a point given in world space.
     /// Defaults to `Vec3::ZERO`.
     pub focus: Vec3,
     /// The radius of the orbit, or the distance from the `focus` point.
-    /// For orthographic projection, this is ignored, and the projection's `scale` is used instead.
+    /// For orthographic projection, this is used as the projection's `scale` instead, so it
+    /// controls the zoom rather than the distance. Use `focus_distance` to get the actual distance
+    /// from the camera to the focus for either projection.
     /// If set to `None`, it will be calculated from the camera's current position during
     /// initialization.
     /// Automatically updated.
     /// Defaults to `None`.
     pub radius: Option<f32>,
-    /// Rotation in radians around the global Y axis (longitudinal). Updated automatically.
+    /// Rotation in radians around the up axis, `axis[1]` (longitudinal). Updated automatically.
     /// If both `yaw` and `pitch` are `0.0`, then the camera will be looking forward, i.e. in
-    /// the `Vec3::NEG_Z` direction, with up being `Vec3::Y`.
+    /// the `-axis[2]` direction (`Vec3::NEG_Z` by default), with up being `axis[1]` (`Vec3::Y`
+    /// by default).
     /// If set to `None`, it will be calculated from the camera's current position during
     /// initialization.
     /// You should not update this after initialization - use `target_yaw` instead.
@@ -125,17 +242,47 @@ pub struct PanOrbitCamera {
     pub yaw: Option<f32>,
     /// Rotation in radians around the local X axis (latitudinal). Updated automatically.
     /// If both `yaw` and `pitch` are `0.0`, then the camera will be looking forward, i.e. in
-    /// the `Vec3::NEG_Z` direction, with up being `Vec3::Y`.
+    /// the `-axis[2]` direction (`Vec3::NEG_Z` by default), with up being `axis[1]` (`Vec3::Y`
+    /// by default).
     /// If set to `None`, it will be calculated from the camera's current position during
     /// initialization.
     /// You should not update this after initialization - use `target_pitch` instead.
     /// Defaults to `None`.
     pub pitch: Option<f32>,
+    /// The axes of the camera's coordinate system, in the order `[x, y, z]`. `axis[1]` is the up
+    /// direction that `yaw` rotates around, and `pitch` is measured from the plane of `axis[0]`
+    /// and `axis[2]`. Use this to change which way is up, e.g. `[Vec3::X, Vec3::Z, Vec3::NEG_Y]`
+    /// for Z up. The axes must be orthonormal and right handed.
+    /// You should not update this after initialization - use `target_axis` instead.
+    /// Defaults to `[Vec3::X, Vec3::Y, Vec3::Z]`.
+    pub axis: [Vec3; 3],
+    /// The target axis. The camera will smoothly transition to this value, using
+    /// `orbit_smoothness`. Because `yaw` and `pitch` are relative to `axis`, they are recalculated
+    /// during the transition so that the camera stays where it is while its notion of up rotates
+    /// towards the new axis, e.g. for a gravity change effect. Note that this normalizes `pitch` to
+    /// be within `-PI / 2.0..=PI / 2.0`.
+    /// Defaults to `[Vec3::X, Vec3::Y, Vec3::Z]`.
+    pub target_axis: [Vec3; 3],
     /// The target focus point. The camera will smoothly transition to this value. Updated
     /// automatically, but you can also update it manually to control the camera independently of
     /// the mouse controls, e.g. with the keyboard.
+    /// This is relative to the parent entity, if there is one.
     /// Defaults to `Vec3::ZERO`.
     pub target_focus: Vec3,
+    /// The focus point to return to when calling `recenter_focus`.
+    /// Defaults to `Vec3::ZERO`.
+    pub home_focus: Vec3,
+    /// The view to return to when calling `reset_view`. If `None`, this is set to the camera's
+    /// initial view when it's initialized.
+    /// Defaults to `None`.
+    pub home_state: Option<PanOrbitCameraState>,
+    /// Where the camera starts when it's initialized. If set, the camera starts at this view and
+    /// smoothly transitions to its configured view, e.g. for an intro animation that swoops in on
+    /// startup. The speed of the transition is controlled by the usual smoothness values, or use
+    /// `transition_with` after spawning for a slower one. If `None`, the camera starts at its
+    /// configured view.
+    /// Defaults to `None`.
+    pub intro_from: Option<PanOrbitCameraState>,
     /// The target yaw value. The camera will smoothly transition to this value. Updated
     /// automatically, but you can also update it manually to control the camera independently of
     /// the mouse controls, e.g. with the keyboard.
@@ -152,11 +299,11 @@ pub struct PanOrbitCamera {
     /// Defaults to `1.0`.
     pub target_radius: f32,
     /// Upper limit on the `yaw` value, in radians. Use this to restrict the maximum rotation
-    /// around the global Y axis.
+    /// around the up axis, `axis[1]`.
     /// Defaults to `None`.
     pub yaw_upper_limit: Option<f32>,
     /// Lower limit on the `yaw` value, in radians. Use this to restrict the maximum rotation
-    /// around the global Y axis.
+    /// around the up axis, `axis[1]`.
     /// Defaults to `None`.
     pub yaw_lower_limit: Option<f32>,
     /// Upper limit on the `pitch` value, in radians. Use this to restrict the maximum rotation
@@ -167,6 +314,32 @@ pub struct PanOrbitCamera {
     /// around the local X axis.
     /// Defaults to `None`.
     pub pitch_lower_limit: Option<f32>,
+    /// Limits `yaw` to within this many radians either side of its initial value. When the camera
+    /// is initialized, this sets `yaw_upper_limit` and `yaw_lower_limit` around the starting yaw,
+    /// overriding any set before, which is handy when the starting yaw comes from the camera's
+    /// spawn transform.
+    /// Defaults to `None`.
+    pub yaw_limit_relative: Option<f32>,
+    /// Limits `pitch` to within this many radians either side of its initial value. When the
+    /// camera is initialized, this sets `pitch_upper_limit` and `pitch_lower_limit` around the
+    /// starting pitch, overriding any set before.
+    /// Defaults to `None`.
+    pub pitch_limit_relative: Option<f32>,
+    /// If set, limits orbiting to a cone, given as `(axis, half_angle)`, so the camera's view
+    /// direction always stays within `half_angle` radians of `axis`, e.g. for a "peek" camera
+    /// that can only look around a little. This gives a circular range of views, unlike the yaw
+    /// and pitch limits, which give a rectangular one. Like `focus`, `axis` is relative to the
+    /// parent entity, if there is one. Applied along with the yaw and pitch limits. Has no effect
+    /// in globe mode.
+    /// Defaults to `None`.
+    pub orbit_cone: Option<(Vec3, f32)>,
+    /// A band of `pitch` values, in radians, that the camera skips over, e.g. to avoid views where
+    /// the scene looks flat. Given as `(lower, upper)`. Orbiting into the band jumps straight
+    /// across it to the other edge, rather than stopping inside it, and a pitch that's set inside
+    /// the band moves to the nearer edge. With smoothing, the camera eases up to the band, then
+    /// jumps across it, so it's never seen from inside the band.
+    /// Defaults to `None`.
+    pub pitch_keepout: Option<(f32, f32)>,
     /// Upper limit on the zoom. This applies to `radius`, in the case of using a perspective
     /// camera, or the projection's scale in the case of using an orthographic camera.
     /// Defaults to `None`.
@@ -176,14 +349,97 @@ pub struct PanOrbitCamera {
     /// Should always be >0 otherwise you'll get stuck at 0.
     /// Defaults to `0.05`.
     pub zoom_lower_limit: f32,
+    /// Whether the yaw, pitch, and zoom limits are enforced. Set this to `false` temporarily to
+    /// stop scripted moves, e.g. in a cutscene, from being clamped by the limits, then set it back
+    /// to `true` to enforce them again. This is simpler than clearing and restoring each limit.
+    /// Note that `allow_upside_down` still applies.
+    /// Defaults to `true`.
+    pub limits_enabled: bool,
+    /// The rough size of the scene, in world units, for adapting the camera to very small or very
+    /// large scenes, e.g. millimeter scale engineering models or kilometer scale terrain. This
+    /// scales the distance below which `radius` and `focus` snap to their target values, which is
+    /// `0.001 * world_scale`. Without this, smoothing stops too early in tiny scenes, and never
+    /// quite stops in huge ones.
+    /// To also scale the default `zoom_lower_limit`, use `PanOrbitCamera::with_world_scale`.
+    /// Other distances like pan speed and pinch zoom are already relative to the radius, so they
+    /// don't need scaling.
+    /// Defaults to `1.0`.
+    pub world_scale: f32,
+    /// How far beyond `focus` the orbit is centered, e.g. for a chase camera that circles a point
+    /// ahead of the vehicle it looks at. The pivot is this far from `focus` along the view direction
+    /// at zero yaw and pitch, i.e. `-Z` relative to `axis`, and stays there while orbiting. The
+    /// camera orbits the pivot at a distance of `radius`, while always turning to look at `focus`,
+    /// so its distance from `focus` changes around the orbit: it's closer when looking along the
+    /// pivot direction, and further when looking back against it. Unlike moving `focus` itself,
+    /// the camera keeps looking at the same point. A negative value centers the orbit behind
+    /// `focus`. Zoom limits and the radius-relative pan speed still use `radius`.
+    /// Defaults to `0.0`.
+    pub pivot_forward_offset: f32,
+    /// If set, the camera always looks at this point, instead of at `focus`, e.g. to keep a
+    /// subject centered in a showcase. Orbiting, panning, and zooming still move the camera around
+    /// `focus` as usual, and only its direction is overridden, so panning moves the center of the
+    /// orbit while the view stays on this point. This means `focus` is no longer kept at the
+    /// center of the view, and can go off-screen, or even end up behind the camera, unless
+    /// `keep_focus_onscreen` is set. Like `focus`, this is relative to the parent entity, if there
+    /// is one.
+    /// Defaults to `None`.
+    pub look_at_fixed: Option<Vec3>,
+    /// Whether to stop panning from moving `focus` off-screen while `look_at_fixed` is set, so the
+    /// pivot can't end up out of view, or behind the camera. The focus is kept out of the outer
+    /// tenth of the view on each side. Without `look_at_fixed`, the focus is always at the center
+    /// of the view, so this has no effect. If the focus is already off-screen, e.g. because
+    /// `look_at_fixed` was just set, panning isn't limited until it's back in view.
+    /// Ignored while `limits_enabled` is `false`.
+    /// Defaults to `false`.
+    pub keep_focus_onscreen: bool,
     /// The sensitivity of the orbiting motion. A value of `0.0` disables orbiting.
     /// Defaults to `1.0`.
     pub orbit_sensitivity: f32,
+    /// Whether the orbit sensitivity should scale with the zoom level, so that orbiting is finer
+    /// when zoomed in and faster when zoomed out. When enabled, the orbit sensitivity is
+    /// multiplied by `(radius / sensitivity_zoom_reference).powf(sensitivity_zoom_exponent)`.
+    /// Note that panning already scales with `radius`, so this only affects orbiting.
+    /// Defaults to `false`.
+    pub sensitivity_scales_with_zoom: bool,
+    /// The `radius` (or orthographic scale) at which `orbit_sensitivity` is applied unchanged when
+    /// `sensitivity_scales_with_zoom` is enabled.
+    /// Defaults to `5.0`.
+    pub sensitivity_zoom_reference: f32,
+    /// Controls the curve of the scaling when `sensitivity_scales_with_zoom` is enabled. `1.0`
+    /// scales linearly with `radius`, while smaller values make the effect more subtle.
+    /// Defaults to `1.0`.
+    pub sensitivity_zoom_exponent: f32,
     /// How much smoothing is applied to the orbit motion. A value of `0.0` disables smoothing,
     /// so there's a 1:1 mapping of input to camera position. A value of `1.0` is infinite
     /// smoothing.
     /// Defaults to `0.8`.
     pub orbit_smoothness: f32,
+    /// If set, overrides `orbit_smoothness` on frames where the target yaw or pitch changes, e.g.
+    /// while dragging, so the camera can respond quickly to input. See `orbit_smoothness_out`.
+    /// Defaults to `None`.
+    pub orbit_smoothness_in: Option<f32>,
+    /// If set, overrides `orbit_smoothness` once the target yaw or pitch stops changing, while the
+    /// camera settles into it, e.g. for a snappy start with a smooth stop. See
+    /// `orbit_smoothness_in`.
+    /// Defaults to `None`.
+    pub orbit_smoothness_out: Option<f32>,
+    /// Whether mouse motion orbits by the same angle per pixel both horizontally and vertically.
+    /// By default, dragging across the whole width of the window orbits a full turn, and dragging
+    /// across the whole height orbits half a turn, so on wide windows a diagonal drag orbits
+    /// more vertically than horizontally. If `true`, both directions are scaled by the window
+    /// height, at half a turn per window height, so diagonal drags trace a straight arc.
+    /// Defaults to `false`.
+    pub orbit_uniform_scale: bool,
+    /// If set, orbiting ratchets in steps of this many radians, e.g. for viewing from a set of
+    /// fixed angles. The orbit input still accumulates as usual, but `target_yaw` and
+    /// `target_pitch` only move when it crosses a multiple of the step, and then snap to it, so
+    /// the camera eases from one detent to the next as you drag. Has no effect in globe mode.
+    /// Defaults to `None`.
+    pub orbit_ratchet: Option<f32>,
+    /// Which way dragging orbits the camera. See `OrbitStyle`. This applies to mouse, touch, and
+    /// `CameraInput` orbiting.
+    /// Defaults to `OrbitStyle::Camera`.
+    pub orbit_style: OrbitStyle,
     /// The sensitivity of the panning motion. A value of `0.0` disables panning.
     /// Defaults to `1.0`.
     pub pan_sensitivity: f32,
@@ -192,6 +448,67 @@ pub struct PanOrbitCamera {
     /// smoothing.
     /// Defaults to `0.6`.
     pub pan_smoothness: f32,
+    /// If set, overrides `pan_smoothness` on frames where `target_focus` changes, e.g. while
+    /// dragging, so the camera can respond quickly to input. See `pan_smoothness_out`.
+    /// Defaults to `None`.
+    pub pan_smoothness_in: Option<f32>,
+    /// If set, overrides `pan_smoothness` once `target_focus` stops changing, while the camera
+    /// settles into it, e.g. for a snappy start with a smooth stop. See `pan_smoothness_in`.
+    /// Defaults to `None`.
+    pub pan_smoothness_out: Option<f32>,
+    /// How `focus` moves towards `target_focus`. See `PanSmoothing`.
+    /// Defaults to `PanSmoothing::Exponential`.
+    pub pan_smoothing: PanSmoothing,
+    /// How quickly continuous panning speeds up while it's held, so it starts slow for precise
+    /// adjustments and gets faster for travelling, like holding a scroll key. The pan speed is
+    /// multiplied by `1.0 + pan_acceleration * held_secs.powi(2)`, e.g. with a value of `1.0` it's
+    /// twice as fast after one second and five times as fast after two.
+    /// This only applies to continuous pan input, i.e. `SixDofInput`, which is how keyboard and
+    /// gamepad panning should be fed in. Mouse and touch drags already map 1:1 to the pointer's
+    /// motion, so they are never accelerated. A value of `0.0` disables acceleration.
+    /// Defaults to `0.0`.
+    pub pan_acceleration: f32,
+    /// The maximum speed, in world units per second, that `focus` moves towards `target_focus`.
+    /// Use this to avoid a disorienting fly-through when jumping to a far away focus. Long jumps
+    /// travel at this constant speed, while short moves still ease in according to
+    /// `pan_smoothness`. If `None`, there is no limit.
+    /// Defaults to `None`.
+    pub max_pan_speed: Option<f32>,
+    /// If set, `target_focus` is kept at this height, measured along `axis[1]`, so panning moves
+    /// the focus across a horizontal plane regardless of the camera's pitch. Combine this with
+    /// pitch limits for a classic RTS camera. Note that this also applies to changes made to
+    /// `target_focus` directly.
+    /// Defaults to `None`.
+    pub lock_focus_height: Option<f32>,
+    /// If set, `target_focus` follows this entity's `GlobalTransform`, so the camera orbits around
+    /// it as it moves. The focus is moved back to the target every frame, so you'll probably want
+    /// to disable panning by setting `pan_sensitivity` to `0.0`. The entity's transform is read
+    /// before it's updated for the current frame, so the camera lags behind by one frame.
+    /// Defaults to `None`.
+    pub follow_target: Option<Entity>,
+    /// When `follow_target` changes to a different entity, the time in seconds that `focus` takes
+    /// to glide to the new target, easing in and out. This overrides `pan_smoothness` for the
+    /// duration of the handoff, so it always takes the same time however far away the new target
+    /// is. A value of `0.0` uses `pan_smoothness` instead.
+    /// Defaults to `0.0`.
+    pub follow_transition_duration: f32,
+    /// What to do when `follow_target` is despawned, or no longer has a `GlobalTransform`. See
+    /// `FollowTargetLost`.
+    /// Defaults to `FollowTargetLost::KeepFocus`.
+    pub follow_target_lost: FollowTargetLost,
+    /// If set, `target_radius` is recalculated whenever the camera's viewport changes size, e.g.
+    /// when the window is resized, so that these bounds stay in view. The camera smoothly zooms
+    /// to the new radius, according to `zoom_smoothness`. The bounds are treated as a sphere
+    /// around `target_focus` that contains them, so they'll fit regardless of the camera's angle.
+    /// For orthographic projection, this recalculates the scale instead.
+    /// Defaults to `None`.
+    pub refit_on_resize: Option<Aabb>,
+    /// The margin left around bounds when fitting them in view, e.g. with `refit_on_resize` or
+    /// `frame_bounds`, as a fraction of the viewport. It's applied symmetrically, so a value of
+    /// `0.1` leaves 10% of the viewport empty on each side, and the bounds fill the middle 80%.
+    /// Values are clamped to just under `0.5`.
+    /// Defaults to `0.1`.
+    pub frame_padding: f32,
     /// The sensitivity of moving the camera closer or further way using the scroll wheel.
     /// A value of `0.0` disables zooming.
     /// Defaults to `1.0`.
@@ -203,6 +520,46 @@ pub struct PanOrbitCamera {
     /// Note that this setting does not apply to pixel-based scroll events, as they are typically
     /// already smooth. It only applies to line-based scroll events.
     pub zoom_smoothness: f32,
+    /// If set, overrides `zoom_smoothness` on frames where `target_radius` changes, e.g. while
+    /// scrolling, so the camera can respond quickly to input. See `zoom_smoothness_out`.
+    /// Defaults to `None`.
+    pub zoom_smoothness_in: Option<f32>,
+    /// If set, overrides `zoom_smoothness` once `target_radius` stops changing, while the camera
+    /// settles into it, e.g. for a snappy start with a smooth stop. See `zoom_smoothness_in`.
+    /// Defaults to `None`.
+    pub zoom_smoothness_out: Option<f32>,
+    /// How much pixel-based zooming, e.g. from a trackpad or pinch gesture, keeps coasting after
+    /// the input stops, so a quick flick gradually slows to a stop. Works like the smoothness
+    /// values: `0.0` disables momentum, and values closer to `1.0` coast for longer.
+    /// Defaults to `0.0`.
+    pub zoom_momentum: f32,
+    /// How much each line of scrolling changes the distance to the focus. See `ZoomScaling`.
+    /// Defaults to `ZoomScaling::Proportional`.
+    pub zoom_scaling: ZoomScaling,
+    /// The point that stays fixed when zooming. See `ZoomPivot`.
+    /// Defaults to `ZoomPivot::Focus`.
+    pub zoom_pivot: ZoomPivot,
+    /// What zooming changes, either the distance to the focus or the field of view. See
+    /// `ZoomMode`.
+    /// Defaults to `ZoomMode::Radius`.
+    pub zoom_mode: ZoomMode,
+    /// How `radius` is converted when the camera's `Projection` is switched between perspective
+    /// and orthographic. See `ProjectionSwitch`.
+    /// Defaults to `ProjectionSwitch::RadiusAsScale`.
+    pub projection_switch: ProjectionSwitch,
+    /// Widens the field of view while the camera moves quickly, and narrows it back when it stops,
+    /// for a sense of speed. See `DynamicFovConfig`. Has no effect on orthographic cameras, or
+    /// with `ZoomMode::Fov`, which controls the field of view itself.
+    /// If `None`, the field of view is left alone.
+    /// Defaults to `None`.
+    pub dynamic_fov: Option<DynamicFovConfig>,
+    /// The maximum time step, in seconds, used when smoothing motion. Use this to stop the camera
+    /// jumping towards its target after a long frame, e.g. when the window regains focus after
+    /// being throttled in the background. If set to `None`, the frame's full time step is used.
+    /// Smoothing is calculated exactly for the time step, rather than integrated frame by frame, so
+    /// it never overshoots the target however long a frame is. This only limits how far it moves.
+    /// Defaults to `None`.
+    pub max_smoothing_dt: Option<f32>,
     /// Button used to orbit the camera.
     /// Defaults to `Button::Left`.
     pub button_orbit: MouseButton,
@@ -210,20 +567,100 @@ pub struct PanOrbitCamera {
     /// Defaults to `Button::Right`.
     pub button_pan: MouseButton,
     /// Key that must be pressed for `button_orbit` to work.
+    /// Holding `modifier_pan` prevents orbiting, unless both modifiers are the same key, in which
+    /// case `button_orbit` and `button_pan` alone decide whether to orbit or pan, so they should be
+    /// different buttons.
     /// Defaults to `None` (no modifier).
     pub modifier_orbit: Option<KeyCode>,
     /// Key that must be pressed for `button_pan` to work.
+    /// Holding `modifier_orbit` prevents panning, unless both modifiers are the same key.
     /// Defaults to `None` (no modifier).
     pub modifier_pan: Option<KeyCode>,
+    /// If `true`, dragging with the primary (left) mouse button always orbits, ignoring
+    /// `button_orbit` and `modifier_orbit`. Touch input emulated as a mouse also uses this
+    /// button, so any single press-and-drag orbits, e.g. for a kiosk. Mouse panning is disabled
+    /// entirely, so `button_pan` and `modifier_pan` have no effect, but touch gestures still pan
+    /// according to `touch_controls`.
+    /// Defaults to `false`.
+    pub drag_orbit_anywhere: bool,
+    /// If `true`, orbiting circles whatever is under the cursor, as given by the `CursorPivot`
+    /// resource, which you need to keep up to date, e.g. by ray casting. Every frame the camera
+    /// orbits, `focus` is set to the point under the cursor, and `yaw`, `pitch`, and `radius` are
+    /// recomputed from the camera's position, so the camera stays where it is but turns to look at
+    /// the point. Recomputing these each frame is cheap, but the ray cast to keep `CursorPivot` up
+    /// to date can be costly in complex scenes. As the point moves to the center of the view,
+    /// stop updating `CursorPivot` while the orbit button is held, otherwise the next ray cast
+    /// finds a new point under the cursor and the camera keeps turning towards it.
+    /// Panning and zooming work as usual, relative to the most recent pivot. Only applies to
+    /// perspective projection, and only while `CursorPivot::point` is set and in front of the
+    /// camera. Zoom limits aren't applied when the pivot moves, so the camera doesn't jump.
+    /// Defaults to `false`.
+    pub orbit_around_cursor: bool,
+    /// Key that zooms in continuously while held, like holding down the scroll wheel.
+    /// Like the mouse controls, this only applies to the active camera.
+    /// Defaults to `None`.
+    pub key_zoom_in: Option<KeyCode>,
+    /// Key that zooms out continuously while held, like holding down the scroll wheel.
+    /// Like the mouse controls, this only applies to the active camera.
+    /// Defaults to `None`.
+    pub key_zoom_out: Option<KeyCode>,
+    /// How fast `key_zoom_in` and `key_zoom_out` zoom, in scroll wheel lines per second.
+    /// `zoom_sensitivity` and `zoom_smoothness` also apply, but `reversed_zoom` does not.
+    /// Defaults to `5.0`.
+    pub key_zoom_speed: f32,
+    /// How fast the camera orbits when `CameraInput::orbit_joystick` is at full deflection, in
+    /// radians per second. `orbit_sensitivity` and `orbit_smoothness` also apply.
+    /// Defaults to `PI`, i.e. half a turn per second.
+    pub joystick_orbit_speed: f32,
+    /// How fast the camera zooms when `CameraInput::zoom_axis` is at full deflection, in scroll
+    /// wheel lines per second. `zoom_sensitivity`, `zoom_smoothness`, and `reversed_zoom` also
+    /// apply.
+    /// Defaults to `5.0`.
+    pub joystick_zoom_speed: f32,
     /// Whether touch controls are enabled.
     /// Defaults to `true`.
     pub touch_enabled: bool,
-    /// The control scheme for touch inputs.
+    /// The control scheme for touch inputs. Changes take effect on the next fresh touch, so
+    /// switching schemes while fingers are down doesn't make the camera jump mid-gesture.
     /// Defaults to `TouchControls::OneFingerOrbit`.
     pub touch_controls: TouchControls,
+    /// The sensitivity of orbiting with touch gestures. Touch motion tends to need a different
+    /// sensitivity than mouse motion, so this lets you tune it without affecting the mouse.
+    /// If `None`, `orbit_sensitivity` is used.
+    /// Defaults to `None`.
+    pub touch_orbit_sensitivity: Option<f32>,
+    /// The sensitivity of panning with touch gestures. If `None`, `pan_sensitivity` is used.
+    /// Defaults to `None`.
+    pub touch_pan_sensitivity: Option<f32>,
+    /// How long, in seconds, to ignore pinch zooming after a second finger touches down. The
+    /// first frames of a pinch are often noisy while the fingers settle, which makes the zoom
+    /// jump. Panning or orbiting with two fingers isn't delayed. Any small positive value, e.g.
+    /// `0.001`, ignores just the first frame.
+    /// Defaults to `0.0`, i.e. no delay.
+    pub touch_pinch_delay: f32,
+    /// Which of mouse and touch input wins when both are used at the same time, e.g. on 2-in-1
+    /// laptops that emit both, which would otherwise move the camera twice as much.
+    /// Defaults to `InputPriority::Both`, which applies both.
+    pub input_priority: InputPriority,
+    /// If set, the camera only becomes active when the cursor is in this window, instead of the
+    /// window it renders to. If the camera renders to this window, the cursor must also be within
+    /// the camera's viewport, otherwise anywhere in the window will do, which is useful when
+    /// rendering to a texture that's displayed in another window.
+    /// Defaults to `None`.
+    pub input_window: Option<Entity>,
+    /// Double tapping a mouse button, e.g. tapping twice on a trackpad, triggers an action like
+    /// resetting the view. See `DoubleTapControls`. If `None`, double taps do nothing.
+    /// Defaults to `None`.
+    pub double_tap: Option<DoubleTapControls>,
     /// Whether to reverse the zoom direction.
     /// Defaults to `false`.
     pub reversed_zoom: bool,
+    /// Whether panning drags the scene along with the cursor, as if grabbing it. If `false`, the
+    /// camera moves with the cursor instead, so the scene moves the opposite way, like scrolling.
+    /// The direction is relative to the screen, so it doesn't depend on the camera's pitch or
+    /// projection.
+    /// Defaults to `true`.
+    pub pan_grab_mode: bool,
     /// Whether the camera is currently upside down. Updated automatically.
     /// This is used to determine which way to orbit, because it's more intuitive to reverse the
     /// orbit direction when upside down.
@@ -233,6 +670,58 @@ pub struct PanOrbitCamera {
     /// Whether to allow the camera to go upside down.
     /// Defaults to `false`.
     pub allow_upside_down: bool,
+    /// What happens to horizontal orbiting when the camera is looking straight up or down, i.e.
+    /// when it's at a pole. See `PoleBehavior`.
+    /// Defaults to `PoleBehavior::Free`.
+    pub pole_behavior: PoleBehavior,
+    /// Whether to use globe style navigation, e.g. for orbiting around a planet. When `true`,
+    /// orbiting rotates the camera incrementally around its own horizontal and vertical axes
+    /// (like a trackball) instead of around the up axis, `axis[1]`, so you can orbit freely over
+    /// the poles without getting stuck. `focus` and `radius` behave as normal.
+    /// This also means that horizontal orbiting yaws around the camera's current view up, rather
+    /// than `axis[1]`, which can feel more natural when the camera is tilted. The
+    /// trade-off is that the horizon won't stay level, because the camera is free to roll.
+    /// While enabled, `yaw` and `pitch` are still updated, but they can't represent the camera's
+    /// roll, so the yaw and pitch limits and `allow_upside_down` have no effect. When disabled
+    /// again, the camera returns to the orientation described by `yaw` and `pitch`.
+    /// Defaults to `false`.
+    pub globe_mode: bool,
+    /// Whether `yaw` and `pitch` just add up the orbit input in globe mode, rather than being
+    /// recalculated from the camera's rotation every frame. The rotation itself is unaffected,
+    /// but recalculating yaw and pitch from it loses information: `pitch` can never go past the
+    /// pole, so orbiting over the top makes `pitch` turn around and `yaw` jump by half a turn.
+    /// When `true`, vertical orbiting keeps increasing `pitch` smoothly, and horizontal orbiting
+    /// only changes `yaw`, with no values wrapped or clamped.
+    /// The limitation is that they no longer describe the camera's orientation exactly, because
+    /// globe mode rotates around the camera's own axes, so anything derived from them, e.g.
+    /// `PanOrbitCameraChanged` events, or the orientation the camera returns to when `globe_mode`
+    /// is disabled, is only approximate after orbiting both horizontally and vertically. Outside
+    /// of globe mode, `yaw` and `pitch` are always used directly, so this has no effect.
+    /// Defaults to `false`.
+    pub accumulate_yaw_pitch: bool,
+    /// The maximum tilt of the horizon, in radians, when `globe_mode` is enabled. In globe mode the
+    /// camera is free to roll, so certain combinations of orbiting can tilt the horizon a lot, or
+    /// even turn it upside down. If set, the roll relative to `axis[1]` is clamped to
+    /// `[-max_roll, max_roll]`, e.g. `Some(0.0)` keeps the horizon level. Outside of globe mode,
+    /// the horizon is always level, so this has no effect. Ignored while `limits_enabled` is
+    /// `false`.
+    /// Defaults to `None`.
+    pub max_roll: Option<f32>,
+    /// The rotation of the camera around `focus` when `globe_mode` is enabled. Updated
+    /// automatically, and set to `None` when `globe_mode` is disabled.
+    /// Defaults to `None`.
+    pub globe_rotation: Option<Quat>,
+    /// The target rotation of the camera around `focus` when `globe_mode` is enabled. The camera
+    /// will smoothly transition to this value, using `orbit_smoothness`. Updated automatically, and
+    /// set to `None` when `globe_mode` is disabled.
+    /// Defaults to `None`.
+    pub target_globe_rotation: Option<Quat>,
+    /// The size used to scale orbit and pan motion when `ActiveCameraData` is missing its
+    /// `window_size` or `viewport_size`, e.g. if you are managing it manually and forgot to set
+    /// them. A warning is logged when this fallback is used.
+    /// If set to `None`, orbiting and panning will do nothing when the sizes are missing.
+    /// Defaults to `Some(Vec2::new(1000.0, 1000.0))`.
+    pub fallback_viewport_size: Option<Vec2>,
     /// If `false`, disable control of the camera. Defaults to `true`.
     pub enabled: bool,
     /// Whether `PanOrbitCamera` has been initialized with the initial config.
@@ -244,6 +733,22 @@ pub struct PanOrbitCamera {
     /// This will be automatically set back to `false` after one frame.
     /// Defaults to `false`.
     pub force_update: bool,
+    /// A temporary smoothness that overrides `orbit_smoothness`, `pan_smoothness`, and
+    /// `zoom_smoothness` until the camera reaches its target values. Set with `transition_with`.
+    /// Automatically cleared when the transition finishes.
+    /// Defaults to `None`.
+    pub transition: Option<SmoothnessOverride>,
+    /// How far the camera must turn, in radians, before another `PanOrbitCameraChanged` event is
+    /// sent. The event is sent once the yaw or pitch has changed by more than this amount since
+    /// the last event, so small smoothing steps are accumulated instead of each sending an event.
+    /// Once the camera comes to rest, an event is always sent if it has moved at all, so listeners
+    /// end up with its final state. A value of `0.0` sends an event on every change.
+    /// Defaults to `0.0`.
+    pub change_event_angle_threshold: f32,
+    /// Like `change_event_angle_threshold`, but for the radius and each component of the focus,
+    /// in world units. For orthographic projection, the radius is the projection's scale.
+    /// Defaults to `0.0`.
+    pub change_event_distance_threshold: f32,
 }
 
 impl Default for PanOrbitCamera {
@@ -251,22 +756,74 @@ impl Default for PanOrbitCamera {
         PanOrbitCamera {
             focus: Vec3::ZERO,
             target_focus: Vec3::ZERO,
+            home_focus: Vec3::ZERO,
+            home_state: None,
+            axis: [Vec3::X, Vec3::Y, Vec3::Z],
+            target_axis: [Vec3::X, Vec3::Y, Vec3::Z],
             radius: None,
             is_upside_down: false,
             allow_upside_down: false,
+            pole_behavior: PoleBehavior::Free,
+            globe_mode: false,
+            accumulate_yaw_pitch: false,
+            max_roll: None,
+            globe_rotation: None,
+            target_globe_rotation: None,
             orbit_sensitivity: 1.0,
+            sensitivity_scales_with_zoom: false,
+            sensitivity_zoom_reference: 5.0,
+            sensitivity_zoom_exponent: 1.0,
             orbit_smoothness: 0.1,
+            orbit_smoothness_in: None,
+            orbit_smoothness_out: None,
+            orbit_uniform_scale: false,
+            orbit_ratchet: None,
+            orbit_style: OrbitStyle::Camera,
             pan_sensitivity: 1.0,
             pan_smoothness: 0.02,
+            pan_smoothness_in: None,
+            pan_smoothness_out: None,
+            pan_acceleration: 0.0,
+            pan_smoothing: PanSmoothing::Exponential,
+            max_pan_speed: None,
+            lock_focus_height: None,
+            follow_target: None,
+            follow_transition_duration: 0.0,
+            follow_target_lost: FollowTargetLost::KeepFocus,
+            refit_on_resize: None,
+            frame_padding: 0.1,
             zoom_sensitivity: 1.0,
+            zoom_scaling: ZoomScaling::Proportional,
+            zoom_momentum: 0.0,
+            zoom_pivot: ZoomPivot::Focus,
+            zoom_mode: ZoomMode::Radius,
+            projection_switch: ProjectionSwitch::RadiusAsScale,
+            dynamic_fov: None,
             zoom_smoothness: 0.1,
+            zoom_smoothness_in: None,
+            zoom_smoothness_out: None,
+            max_smoothing_dt: None,
             button_orbit: MouseButton::Left,
             button_pan: MouseButton::Right,
             modifier_orbit: None,
             modifier_pan: None,
+            drag_orbit_anywhere: false,
+            orbit_around_cursor: false,
+            key_zoom_in: None,
+            key_zoom_out: None,
+            key_zoom_speed: 5.0,
+            joystick_orbit_speed: PI,
+            joystick_zoom_speed: 5.0,
             touch_enabled: true,
             touch_controls: TouchControls::OneFingerOrbit,
+            touch_orbit_sensitivity: None,
+            touch_pan_sensitivity: None,
+            touch_pinch_delay: 0.0,
+            input_priority: InputPriority::Both,
+            input_window: None,
+            double_tap: None,
             reversed_zoom: false,
+            pan_grab_mode: true,
             enabled: true,
             yaw: None,
             pitch: None,
@@ -274,386 +831,7326 @@ impl Default for PanOrbitCamera {
             target_pitch: 0.0,
             target_radius: 1.0,
             initialized: false,
+            intro_from: None,
             yaw_upper_limit: None,
             yaw_lower_limit: None,
             pitch_upper_limit: None,
             pitch_lower_limit: None,
+            yaw_limit_relative: None,
+            pitch_limit_relative: None,
+            orbit_cone: None,
+            pitch_keepout: None,
             zoom_upper_limit: None,
             zoom_lower_limit: 0.05,
+            limits_enabled: true,
+            world_scale: 1.0,
+            pivot_forward_offset: 0.0,
+            look_at_fixed: None,
+            keep_focus_onscreen: false,
+            fallback_viewport_size: Some(Vec2::new(1000.0, 1000.0)),
             force_update: false,
+            transition: None,
+            change_event_angle_threshold: 0.0,
+            change_event_distance_threshold: 0.0,
         }
     }
 }
 
-/// Tracks which `PanOrbitCamera` is active (should handle input events), along with the window
-/// and viewport dimensions, which are used for scaling mouse motion.
-/// `PanOrbitCameraPlugin` manages this resource automatically, in order to support multiple
-/// viewports/windows. However, if this doesn't work for you, you can take over and manage it
-/// yourself, e.g. when you want to control a camera that is rendering to a texture.
-#[derive(Resource, Default, Debug, PartialEq)]
-pub struct ActiveCameraData {
-    /// ID of the entity with `PanOrbitCamera` that will handle user input. In other words, this
-    /// is the camera that will move when you orbit/pan/zoom.
-    pub entity: Option<Entity>,
-    /// The viewport size. This is only used to scale the panning mouse motion. I recommend setting
-    /// this to the actual render target dimensions (e.g. the image or viewport), and changing
-    /// `PanOrbitCamera::pan_sensitivity` to adjust the sensitivity if required.
-    pub viewport_size: Option<Vec2>,
-    /// The size of the window. This is only used to scale the orbit mouse motion. I recommend
-    /// setting this to actual dimensions of the window that you want to control the camera from,
-    /// and changing `PanOrbitCamera::orbit_sensitivity` to adjust the sensitivity if required.
-    pub window_size: Option<Vec2>,
-    /// Indicates to `PanOrbitCameraPlugin` that it should not update/overwrite this resource.
-    /// If you are manually updating this resource you should set this to `true`.
-    /// Note that setting this to `true` will effectively break multiple viewport/window support
-    /// unless you manually reimplement it.
-    pub manual: bool,
-}
+impl PanOrbitCamera {
+    /// Create a `PanOrbitCamera` with default values suited to a scene of the given scale. This
+    /// sets `world_scale`, and scales the default `zoom_lower_limit` to match.
+    /// # Example
+    /// ```
+    /// # use bevy_panorbit_camera::PanOrbitCamera;
+    /// // A scene measured in millimeters
+    /// let pan_orbit = PanOrbitCamera {
+    ///     allow_upside_down: true,
+    ///     ..PanOrbitCamera::with_world_scale(0.001)
+    /// };
+    /// ```
+    pub fn with_world_scale(world_scale: f32) -> Self {
+        let default = Self::default();
+        Self {
+            world_scale,
+            zoom_lower_limit: default.zoom_lower_limit * world_scale,
+            ..default
+        }
+    }
 
-/// Gather data about the active viewport, i.e. the viewport the user is interacting with.
-/// Enables multiple viewports/windows.
-#[allow(clippy::too_many_arguments)]
-fn active_viewport_data(
-    mut active_cam: ResMut<ActiveCameraData>,
-    mouse_input: Res<ButtonInput<MouseButton>>,
-    key_input: Res<ButtonInput<KeyCode>>,
-    scroll_events: EventReader<MouseWheel>,
-    touches: Res<Touches>,
-    primary_windows: Query<&Window, With<PrimaryWindow>>,
-    other_windows: Query<&Window, Without<PrimaryWindow>>,
-    orbit_cameras: Query<(Entity, &Camera, &PanOrbitCamera)>,
-    #[cfg(feature = "bevy_egui")] egui_wants_focus: Res<EguiWantsFocus>,
-) {
-    let mut new_resource = ActiveCameraData::default();
-    let mut max_cam_order = 0;
+    /// Move the focus back to `home_focus`, without changing the camera's angle or zoom.
+    /// The camera will smoothly pan back, according to `pan_smoothness`.
+    pub fn recenter_focus(&mut self) {
+        self.target_focus = self.home_focus;
+        self.force_update = true;
+    }
 
-    let mut has_input = false;
-    for (entity, camera, pan_orbit) in orbit_cameras.iter() {
-        let input_just_activated = input::orbit_just_pressed(pan_orbit, &mouse_input, &key_input)
-            || input::pan_just_pressed(pan_orbit, &mouse_input, &key_input)
-            || !scroll_events.is_empty()
-            || (touches.iter_just_pressed().count() > 0
-                && touches.iter_just_pressed().count() == touches.iter().count());
+    /// The distance from the camera to `focus`, e.g. for setting the focal distance of a depth of
+    /// field effect. For perspective projection, this is `radius`. For orthographic projection,
+    /// `radius` controls the projection's scale instead, and the camera is kept halfway between the
+    /// projection's near and far planes. With a `pivot_forward_offset`, the camera orbits a point
+    /// other than `focus`, so this is the distance to `focus` from where it is on that orbit.
+    /// Returns `None` if the camera hasn't been initialized yet.
+    pub fn focus_distance(&self, projection: &Projection) -> Option<f32> {
+        let distance = match projection {
+            Projection::Perspective(_) => self.radius,
+            Projection::Orthographic(p) => self.radius.map(|_| util::ortho_focus_distance(p)),
+        };
+        distance
+            .map(|distance| (self.pivot_offset() + self.rotation() * Vec3::Z * distance).length())
+    }
 
-        if input_just_activated {
-            has_input = true;
-            #[allow(unused_mut, unused_assignments)]
-            let mut should_get_input = true;
-            #[cfg(feature = "bevy_egui")]
-            {
-                should_get_input = !egui_wants_focus.prev && !egui_wants_focus.curr;
-            }
-            if should_get_input {
-                // First check if cursor is in the same window as this camera
-                if let RenderTarget::Window(win_ref) = camera.target {
-                    let Some(window) = (match win_ref {
-                        WindowRef::Primary => primary_windows.get_single().ok(),
-                        WindowRef::Entity(entity) => other_windows.get(entity).ok(),
-                    }) else {
-                        // Window does not exist - maybe it was closed and the camera not cleaned up
-                        continue;
-                    };
+    /// The transform the camera will have once it reaches its target values, e.g. for baking
+    /// camera positions into a scene without waiting for smoothing to finish. Also returns the
+    /// projection's scale for orthographic projection, which `target_radius` controls instead of
+    /// the distance, or `None` for perspective projection.
+    /// Note that an in-progress change of `axis` is not taken into account.
+    pub fn target_transform(&self, projection: &Projection) -> (Transform, Option<f32>) {
+        let rotation = util::axis_rotation(self.axis)
+            * self.target_globe_rotation.unwrap_or_else(|| {
+                util::rotation_from_yaw_pitch(self.target_yaw, self.target_pitch)
+            });
+        let mut transform = Transform::IDENTITY;
+        let mut projection = projection.clone();
+        util::update_orbit_transform_from_rotation(
+            rotation,
+            self.target_radius,
+            self.target_focus,
+            self.pivot_offset(),
+            &mut transform,
+            &mut projection,
+        );
+        if let Some(point) = self.look_at_fixed {
+            util::look_at_point(&mut transform, point);
+        }
+        let scale = match projection {
+            Projection::Orthographic(p) => Some(p.scale),
+            Projection::Perspective(_) => None,
+        };
+        (transform, scale)
+    }
 
-                    // Is the cursor/touch in this window?
-                    // Note: there's a bug in winit that causes `window.cursor_position()` to return
-                    // a `Some` value even if the cursor is not in this window, in very specific cases.
-                    // See: https://github.com/Plonq/bevy_panorbit_camera/issues/22
-                    if let Some(input_position) = window.cursor_position().or(touches
-                        .iter_just_pressed()
-                        .collect::<Vec<_>>()
-                        .first()
-                        .map(|touch| touch.position()))
-                    {
-                        // Now check if cursor is within this camera's viewport
-                        if let Some(Rect { min, max }) = camera.logical_viewport_rect() {
-                            // Window coordinates have Y starting at the bottom, so we need to reverse
-                            // the y component before comparing with the viewport rect
-                            let cursor_in_vp = input_position.x > min.x
-                                && input_position.x < max.x
-                                && input_position.y > min.y
-                                && input_position.y < max.y;
-
-                            // Only set if camera order is higher. This may overwrite a previous value
-                            // in the case the viewport is overlapping another viewport.
-                            if cursor_in_vp && camera.order >= max_cam_order {
-                                new_resource = ActiveCameraData {
-                                    entity: Some(entity),
-                                    viewport_size: camera.logical_viewport_size(),
-                                    window_size: Some(Vec2::new(window.width(), window.height())),
-                                    manual: false,
-                                };
-                                max_cam_order = camera.order;
-                            }
-                        }
-                    }
-                }
-            }
+    /// Where `focus` appears in the viewport, in logical pixels from the top left like
+    /// `Camera::world_to_viewport`, e.g. for drawing a pivot indicator. This is calculated from the
+    /// camera's current values, so it matches the camera's transform once it has been updated this
+    /// frame. `projection` should be the camera's own, which Bevy keeps in sync with the viewport's
+    /// aspect ratio. Returns `None` if the camera hasn't been initialized yet, or the focus is
+    /// behind the camera, e.g. because of `look_at_fixed`.
+    pub fn focus_screen_position(
+        &self,
+        viewport_size: Vec2,
+        projection: &Projection,
+    ) -> Option<Vec2> {
+        let radius = self.radius?;
+        let mut projection = projection.clone();
+        let transform = self.current_transform(radius, &mut projection);
+        let view_from_world = transform.compute_matrix().inverse();
+        let clip = projection.get_clip_from_view() * view_from_world * self.focus.extend(1.0);
+        if clip.w <= 0.0 {
+            return None;
         }
+        let ndc = clip.truncate() / clip.w;
+        Some(Vec2::new(ndc.x + 1.0, 1.0 - ndc.y) / 2.0 * viewport_size)
     }
 
-    if has_input {
-        active_cam.set_if_neq(new_resource);
+    /// Create a camera from a view matrix, i.e. the inverse of the camera's transform, e.g. from an
+    /// external camera solver. See `set_from_view_matrix`.
+    pub fn from_view_matrix(view: Mat4, distance: f32) -> Self {
+        let mut pan_orbit = Self::default();
+        pan_orbit.set_from_view_matrix(view, distance);
+        pan_orbit
     }
-}
 
-/// Main system for processing input and converting to transformations
-fn pan_orbit_camera(
-    active_cam: Res<ActiveCameraData>,
-    mouse_key_tracker: Res<MouseKeyTracker>,
-    touch_tracker: Res<TouchTracker>,
-    mut orbit_cameras: Query<(Entity, &mut PanOrbitCamera, &mut Transform, &mut Projection)>,
-    time: Res<Time>,
-) {
-    for (entity, mut pan_orbit, mut transform, mut projection) in orbit_cameras.iter_mut() {
-        // Closures that apply limits to the yaw, pitch, and zoom values
-        let apply_zoom_limits = {
-            let zoom_upper_limit = pan_orbit.zoom_upper_limit;
-            let zoom_lower_limit = pan_orbit.zoom_lower_limit;
-            move |zoom: f32| zoom.clamp_optional(Some(zoom_lower_limit), zoom_upper_limit)
-        };
+    /// Immediately move the camera to match a view matrix, i.e. the inverse of the camera's
+    /// transform, e.g. from a photogrammetry or AR camera solver. A view matrix doesn't say what
+    /// the camera is looking at, so `focus` is placed `distance` in front of the camera, and
+    /// `radius` is set accordingly, i.e. to the distance from the pivot if there's a
+    /// `pivot_forward_offset`. Any scale in the matrix is ignored.
+    /// `yaw` and `pitch` are relative to `axis`, so set `axis` first if needed. They can't
+    /// represent roll, so any roll in the matrix is discarded, unless `globe_mode` is enabled and
+    /// the camera has been initialized, in which case `globe_rotation` keeps it.
+    /// This assumes perspective projection. For orthographic projection, `radius` controls the
+    /// scale rather than the distance, so it will need setting separately.
+    pub fn set_from_view_matrix(&mut self, view: Mat4, distance: f32) {
+        let (_, rotation, eye) = view.inverse().to_scale_rotation_translation();
+        let mut rotation = rotation.normalize();
+        let focus = eye + rotation * Vec3::NEG_Z * distance;
+        let mut radius = distance;
+        let pivot_offset = self.pivot_offset();
+        if pivot_offset != Vec3::ZERO {
+            // The camera orbits the pivot rather than the focus, so turn it to face away from the
+            // pivot, keeping its roll
+            let orbit = eye - focus - pivot_offset;
+            radius = orbit.length();
+            if let Some(direction) = orbit.try_normalize() {
+                rotation = Quat::from_rotation_arc(rotation * Vec3::Z, direction) * rotation;
+            }
+        }
+        let local_rotation = util::axis_rotation(self.axis).inverse() * rotation;
+        let (yaw, pitch) = util::yaw_pitch_from_rotation(local_rotation);
+        self.yaw = Some(yaw);
+        self.pitch = Some(pitch);
+        self.radius = Some(radius);
+        self.focus = focus;
+        self.target_yaw = yaw;
+        self.target_pitch = pitch;
+        self.target_radius = radius;
+        self.target_focus = focus;
+        if self.globe_rotation.is_some() {
+            self.globe_rotation = Some(local_rotation);
+            self.target_globe_rotation = Some(local_rotation);
+        }
+        self.force_update = true;
+    }
 
-        let apply_yaw_limits = {
-            let yaw_upper_limit = pan_orbit.yaw_upper_limit;
-            let yaw_lower_limit = pan_orbit.yaw_lower_limit;
-            move |yaw: f32| yaw.clamp_optional(yaw_lower_limit, yaw_upper_limit)
-        };
+    /// The view matrix for the camera's current values, i.e. the inverse of its transform, e.g.
+    /// for passing to an external camera solver. Like `set_from_view_matrix`, this assumes
+    /// perspective projection. For orthographic projection, use the inverse of the camera's
+    /// `Transform` instead.
+    pub fn view_matrix(&self) -> Mat4 {
+        let mut transform = Transform::IDENTITY;
+        util::update_orbit_transform_from_rotation(
+            self.rotation(),
+            self.radius.unwrap_or(self.target_radius),
+            self.focus,
+            self.pivot_offset(),
+            &mut transform,
+            &mut Projection::Perspective(default()),
+        );
+        Mat4::from_rotation_translation(transform.rotation, transform.translation).inverse()
+    }
 
-        let apply_pitch_limits = {
-            let pitch_upper_limit = pan_orbit.pitch_upper_limit;
-            let pitch_lower_limit = pan_orbit.pitch_lower_limit;
-            move |pitch: f32| pitch.clamp_optional(pitch_lower_limit, pitch_upper_limit)
-        };
+    /// Smoothly orbit to one of the cardinal views, e.g. looking at the focus from directly above.
+    /// The view is relative to `axis`, and the camera takes the shortest way round.
+    pub fn set_view(&mut self, view: CardinalView) {
+        let (yaw, pitch) = view.yaw_pitch();
+        self.set_target_yaw_pitch(yaw, pitch);
+    }
 
-        if !pan_orbit.initialized {
-            // Calculate yaw, pitch, and radius from the camera's position. If user sets all
-            // these explicitly, this calculation is wasted, but that's okay since it will only run
-            // once on init.
-            let (yaw, pitch, radius) =
-                util::calculate_from_translation_and_focus(transform.translation, pan_orbit.focus);
-            let &mut mut yaw = pan_orbit.yaw.get_or_insert(yaw);
-            let &mut mut pitch = pan_orbit.pitch.get_or_insert(pitch);
-            let &mut mut radius = pan_orbit.radius.get_or_insert(radius);
+    /// Smoothly orbit to look at the focus from `direction`, i.e. looking along `-direction`, e.g.
+    /// `Vec3::Y` for a top view. This is the building block for a CAD style view cube, where the
+    /// faces, edges, and corners are the 26 directions with components of `-1.0`, `0.0`, or `1.0`.
+    /// Unlike `set_view`, `direction` is in the same space as `focus`, i.e. relative to the parent
+    /// entity if there is one, rather than relative to `axis`, so it matches the scene's axes
+    /// whichever way is up. Use `CardinalView::direction` for the six faces. The camera takes the
+    /// shortest way round. Does nothing if `direction` is zero.
+    pub fn view_from(&mut self, direction: Vec3) {
+        let direction = util::axis_rotation(self.axis).inverse() * direction;
+        if direction.length_squared() == 0.0 {
+            return;
+        }
+        let (yaw, pitch) = util::yaw_pitch_from_direction(direction);
+        self.set_target_yaw_pitch(yaw, pitch);
+    }
 
-            // Apply limits
-            yaw = apply_yaw_limits(yaw);
-            pitch = apply_pitch_limits(pitch);
-            radius = apply_zoom_limits(radius);
+    /// Where the orbit is centered relative to `focus`. See `pivot_forward_offset`.
+    fn pivot_offset(&self) -> Vec3 {
+        util::pivot_offset(self.axis, self.pivot_forward_offset)
+    }
 
-            // Set initial values
-            pan_orbit.yaw = Some(yaw);
-            pan_orbit.pitch = Some(pitch);
-            pan_orbit.radius = Some(radius);
-            pan_orbit.target_yaw = yaw;
-            pan_orbit.target_pitch = pitch;
-            pan_orbit.target_radius = radius;
-            pan_orbit.target_focus = pan_orbit.focus;
+    /// The camera's position for its current values, assuming perspective projection
+    fn eye(&self) -> Vec3 {
+        self.focus
+            + self.pivot_offset()
+            + self.rotation() * Vec3::Z * self.radius.unwrap_or(self.target_radius)
+    }
 
-            util::update_orbit_transform(
-                yaw,
-                pitch,
-                radius,
-                pan_orbit.focus,
-                &mut transform,
-                &mut projection,
-            );
+    /// Set the target yaw and pitch, taking the shortest way round, and keeping the target globe
+    /// rotation in sync
+    fn set_target_yaw_pitch(&mut self, yaw: f32, pitch: f32) {
+        self.target_yaw = util::wrap_angle_near(yaw, self.target_yaw);
+        self.target_pitch = pitch;
+        if self.target_globe_rotation.is_some() {
+            self.target_globe_rotation = Some(util::rotation_from_yaw_pitch(
+                self.target_yaw,
+                self.target_pitch,
+            ));
+        }
+        self.force_update = true;
+    }
 
-            pan_orbit.initialized = true;
+    /// Smoothly turn to look at `point` without changing the distance, i.e. orbit-only framing.
+    /// `point` becomes the new `target_focus`, and `target_yaw` and `target_pitch` are set so the
+    /// camera looks at it from the direction of its current position. `target_radius` is kept, so
+    /// the camera moves along the line from `point` through its current position, until it's
+    /// `target_radius` away from `point`. With a `pivot_forward_offset`, the line starts at the
+    /// new pivot instead of `point`. If the camera is already exactly there, only the focus
+    /// changes.
+    /// Like `target_focus`, `point` is relative to the parent entity, if there is one.
+    pub fn aim_at(&mut self, point: Vec3) {
+        let dir =
+            util::axis_rotation(self.axis).inverse() * (self.eye() - point - self.pivot_offset());
+        if dir.length_squared() > 0.0 {
+            let (yaw, pitch) = util::yaw_pitch_from_direction(dir);
+            self.target_yaw = util::wrap_angle_near(yaw, self.target_yaw);
+            self.target_pitch = pitch;
+            if self.target_globe_rotation.is_some() {
+                self.target_globe_rotation = Some(util::rotation_from_yaw_pitch(
+                    self.target_yaw,
+                    self.target_pitch,
+                ));
+            }
         }
+        self.target_focus = point;
+        self.force_update = true;
+    }
 
-        // 1 - Get Input
+    /// The camera's current azimuth, in radians from `0` to `TAU`, for those who think in
+    /// astronomy or GIS terms. This is the compass direction of the camera from the focus, measured
+    /// in the ground plane from `axis[0]` (north) towards `axis[2]`, which is clockwise when
+    /// looking down from above. It's `PI / 2 - yaw`, wrapped into range, so north is a yaw of
+    /// `PI / 2`.
+    pub fn azimuth(&self) -> f32 {
+        let dir = util::axis_rotation(self.axis).inverse() * self.rotation() * Vec3::Z;
+        dir.z.atan2(dir.x).rem_euclid(TAU)
+    }
 
-        let mut orbit = Vec2::ZERO;
-        let mut pan = Vec2::ZERO;
-        let mut scroll_line = 0.0;
-        let mut scroll_pixel = 0.0;
-        let mut orbit_button_changed = false;
+    /// The camera's current elevation, in radians, which is its angle above the ground plane as
+    /// seen from the focus, from `-PI / 2` to `PI / 2`. This is the same as `pitch`, except it
+    /// stays in range when `allow_upside_down` lets the pitch go past the poles.
+    pub fn elevation(&self) -> f32 {
+        let dir = util::axis_rotation(self.axis).inverse() * self.rotation() * Vec3::Z;
+        dir.y.atan2(Vec2::new(dir.x, dir.z).length())
+    }
 
-        // The reason we only skip getting input if the camera is inactive/disabled is because
-        // it might still be moving (lerping towards target values) when the user is not
-        // actively controlling it.
-        if pan_orbit.enabled && active_cam.entity == Some(entity) {
-            let zoom_direction = match pan_orbit.reversed_zoom {
-                true => -1.0,
-                false => 1.0,
-            };
+    /// Smoothly orbit to `azimuth`, in radians. See `azimuth` for how it maps to yaw. The camera
+    /// takes the shortest way round.
+    pub fn set_azimuth(&mut self, azimuth: f32) {
+        self.set_target_yaw_pitch(PI / 2.0 - azimuth, self.target_pitch);
+    }
 
-            orbit = mouse_key_tracker.orbit * pan_orbit.orbit_sensitivity;
-            pan = mouse_key_tracker.pan * pan_orbit.pan_sensitivity;
-            scroll_line =
-                mouse_key_tracker.scroll_line * zoom_direction * pan_orbit.zoom_sensitivity;
-            scroll_pixel =
-                mouse_key_tracker.scroll_pixel * zoom_direction * pan_orbit.zoom_sensitivity;
-            orbit_button_changed = mouse_key_tracker.orbit_button_changed;
+    /// Smoothly orbit to `elevation`, in radians. This sets the target pitch. See `elevation`.
+    pub fn set_elevation(&mut self, elevation: f32) {
+        self.set_target_yaw_pitch(self.target_yaw, elevation);
+    }
 
-            if pan_orbit.touch_enabled {
-                let (touch_orbit, touch_pan, touch_zoom_pixel) = match pan_orbit.touch_controls {
-                    TouchControls::OneFingerOrbit => match touch_tracker.get_touch_gestures() {
-                        TouchGestures::None => (Vec2::ZERO, Vec2::ZERO, 0.0),
-                        TouchGestures::OneFinger(one_finger_gestures) => {
-                            (one_finger_gestures.motion, Vec2::ZERO, 0.0)
-                        }
-                        TouchGestures::TwoFinger(two_finger_gestures) => (
-                            Vec2::ZERO,
-                            two_finger_gestures.motion,
-                            two_finger_gestures.pinch * 0.015,
-                        ),
-                    },
-                    TouchControls::TwoFingerOrbit => match touch_tracker.get_touch_gestures() {
-                        TouchGestures::None => (Vec2::ZERO, Vec2::ZERO, 0.0),
-                        TouchGestures::OneFinger(one_finger_gestures) => {
-                            (Vec2::ZERO, one_finger_gestures.motion, 0.0)
-                        }
-                        TouchGestures::TwoFinger(two_finger_gestures) => (
-                            two_finger_gestures.motion,
-                            Vec2::ZERO,
-                            two_finger_gestures.pinch * 0.015,
-                        ),
-                    },
-                };
+    /// Smoothly move the focus to `world_point`, given in world space, for a camera that is a child
+    /// of another entity. `parent_global` is the parent's `GlobalTransform`, which is used to
+    /// convert the point into the camera's local space. For a camera without a parent, set
+    /// `target_focus` directly instead.
+    pub fn set_focus_world(&mut self, world_point: Vec3, parent_global: &GlobalTransform) {
+        self.target_focus = parent_global
+            .affine()
+            .inverse()
+            .transform_point3(world_point);
+        self.force_update = true;
+    }
 
-                orbit += touch_orbit * pan_orbit.orbit_sensitivity;
-                pan += touch_pan * pan_orbit.pan_sensitivity;
-                scroll_pixel += touch_zoom_pixel * zoom_direction * pan_orbit.zoom_sensitivity;
+    /// Smoothly return to `home_state`, i.e. the camera's initial view unless `home_state` was set
+    /// explicitly. Does nothing if the camera hasn't been initialized yet.
+    pub fn reset_view(&mut self) {
+        if let Some(home_state) = self.home_state {
+            self.target_focus = home_state.focus;
+            self.target_yaw = home_state.yaw;
+            self.target_pitch = home_state.pitch;
+            if self.target_globe_rotation.is_some() {
+                self.target_globe_rotation = Some(util::rotation_from_yaw_pitch(
+                    home_state.yaw,
+                    home_state.pitch,
+                ));
             }
+            self.target_radius = home_state.radius;
+            self.force_update = true;
         }
+    }
 
-        // 2 - Process input into target yaw/pitch, or focus, radius
+    /// Smoothly zoom to fit `aabb` in view, e.g. for a "zoom to fit" button. `target_focus` is
+    /// moved to the centre of `aabb`, and `target_radius` is set so that a sphere containing it
+    /// fits in the field of view, leaving `frame_padding` around it. The viewing angle doesn't
+    /// change. `aspect_ratio` is the width of the viewport divided by its height, which is used
+    /// in place of the perspective projection's, in case that hasn't been updated yet, e.g. on the
+    /// first frame. For orthographic projection, the scale is set instead, using the projection's
+    /// area for its shape. An empty `aabb` zooms in as far as `zoom_lower_limit` allows.
+    /// Like `target_focus`, `aabb` is relative to the parent entity, if there is one.
+    pub fn frame_bounds(&mut self, aabb: Aabb, projection: &Projection, aspect_ratio: f32) {
+        let mut projection = projection.clone();
+        if let Projection::Perspective(ref mut p) = projection {
+            p.aspect_ratio = aspect_ratio;
+        }
+        self.target_focus = aabb.center.into();
+        let bounds_radius = Vec3::from(aabb.half_extents).length();
+        if let Some(radius) = util::radius_to_fit(bounds_radius, &projection, self.frame_padding) {
+            self.target_radius = radius.max(self.zoom_lower_limit);
+        }
+        self.force_update = true;
+    }
 
-        if orbit_button_changed {
-            // Only check for upside down when orbiting started or ended this frame,
-            // so we don't reverse the yaw direction while the user is still dragging
-            let wrapped_pitch = (pan_orbit.target_pitch % TAU).abs();
-            pan_orbit.is_upside_down = wrapped_pitch > TAU / 4.0 && wrapped_pitch < 3.0 * TAU / 4.0;
+    /// Jump straight to `state`, setting both the current and target values, so smoothing doesn't
+    /// apply. This is for driving the camera yourself, e.g. with `PanOrbitCameraState::lerp`
+    /// each frame. Limits still apply.
+    pub fn set_state(&mut self, state: PanOrbitCameraState) {
+        self.focus = state.focus;
+        self.target_focus = state.focus;
+        self.yaw = Some(state.yaw);
+        self.target_yaw = state.yaw;
+        self.pitch = Some(state.pitch);
+        self.target_pitch = state.pitch;
+        self.radius = Some(state.radius);
+        self.target_radius = state.radius;
+        if self.target_globe_rotation.is_some() {
+            let rotation = util::rotation_from_yaw_pitch(state.yaw, state.pitch);
+            self.globe_rotation = Some(rotation);
+            self.target_globe_rotation = Some(rotation);
         }
+        self.force_update = true;
+    }
 
-        let mut has_moved = false;
+    /// Smoothly transition to `target_state` using the given `smoothness` instead of the usual
+    /// `orbit_smoothness`, `pan_smoothness`, and `zoom_smoothness`. This is useful for e.g. a
+    /// slower, more cinematic transition when jumping to a saved view.
+    /// The override is cleared as soon as the camera arrives, or after `duration` seconds,
+    /// whichever comes first, after which the usual smoothness values apply again.
+    pub fn transition_with(
+        &mut self,
+        target_state: PanOrbitCameraState,
+        smoothness: f32,
+        duration: f32,
+    ) {
+        self.target_focus = target_state.focus;
+        self.set_target_yaw_pitch(target_state.yaw, target_state.pitch);
+        self.target_radius = target_state.radius;
+        self.transition = Some(SmoothnessOverride {
+            smoothness,
+            remaining: duration,
+        });
+        self.force_update = true;
+    }
+
+    /// Set `pitch_lower_limit` and `pitch_upper_limit` from a `PitchRange` preset.
+    /// # Example
+    /// ```
+    /// # use bevy_panorbit_camera::{PanOrbitCamera, PitchRange};
+    /// let mut pan_orbit = PanOrbitCamera::default();
+    /// // Never let the camera go below the focus
+    /// pan_orbit.set_pitch_range(PitchRange::above_horizon());
+    /// ```
+    pub fn set_pitch_range(&mut self, range: PitchRange) {
+        self.pitch_lower_limit = range.lower;
+        self.pitch_upper_limit = range.upper;
+    }
+
+    /// Orbit the camera by `delta`, given in normalized viewport units, where `1.0` is the full
+    /// width or height of the viewport. Moving the mouse across the whole window orbits by the same
+    /// amount, because mouse motion is converted to these units by dividing by the window size.
+    /// Use this to orbit programmatically in a way that's independent of resolution.
+    /// `orbit_style` is applied, so this orbits the same way as dragging does.
+    /// Note that `orbit_sensitivity` is not applied.
+    pub fn orbit_by_ndc(&mut self, delta: Vec2) {
+        let delta = delta * self.orbit_style.direction();
+        let delta_x = delta.x * PI * 2.0;
+        let delta_y = delta.y * PI;
+        if let Some(target_rotation) = self.target_globe_rotation {
+            // Rotate around the camera's local axes, so there are no poles to get stuck at
+            self.target_globe_rotation = Some(
+                target_rotation * Quat::from_rotation_y(-delta_x) * Quat::from_rotation_x(-delta_y),
+            );
+            if self.accumulate_yaw_pitch {
+                self.target_yaw -= delta_x;
+                self.target_pitch += delta_y;
+            }
+        } else {
+            let delta_x = if self.is_upside_down {
+                -delta_x
+            } else {
+                delta_x
+            };
+            let freeze_yaw = self.pole_behavior == PoleBehavior::FreezeYaw
+                && util::is_near_pole(self.target_pitch);
+            if !freeze_yaw {
+                self.target_yaw -= delta_x;
+            }
+            self.target_pitch += delta_y;
+        }
+    }
+
+    /// Pan the camera by `delta`, given in normalized viewport units, where `1.0` is the full
+    /// width or height of the viewport. Mouse motion is converted to these units by dividing by
+    /// the viewport size, so panning by `Vec2::X` moves the focus by the same amount as dragging
+    /// across the whole viewport. Use this to pan programmatically in a way that's independent of
+    /// resolution.
+    /// Note that `pan_sensitivity` and `pan_grab_mode` are not applied, so a positive `delta` always
+    /// drags the scene along with it, as when panning with the default `pan_grab_mode` of `true`.
+    pub fn pan_by_ndc(&mut self, delta: Vec2, projection: &Projection) {
+        let mut multiplier = 1.0;
+        let pan = match *projection {
+            Projection::Perspective(ref p) => {
+                // Make panning proportional to distance away from focus point
+                if let Some(radius) = self.radius {
+                    multiplier = radius;
+                }
+                delta * Vec2::new(p.fov * p.aspect_ratio, p.fov)
+            }
+            Projection::Orthographic(ref p) => delta * Vec2::new(p.area.width(), p.area.height()),
+        };
+        // Translate by the camera's local axes, as it may not be looking along `rotation`
+        let radius = self.radius.unwrap_or(self.target_radius);
+        let rotation = self
+            .current_transform(radius, &mut projection.clone())
+            .rotation;
+        let right = rotation * Vec3::X * -pan.x;
+        let up = rotation * Vec3::Y * pan.y;
+        self.target_focus += (right + up) * multiplier;
+    }
+
+    /// Check for configurations that make some of the controls unreachable or broken, e.g. using the
+    /// same button and modifier for both orbiting and panning. `PanOrbitCameraPlugin` logs a warning
+    /// when a camera with an invalid configuration is added.
+    /// Note that a sensitivity of `0.0` is not an error, because that's how an action is disabled.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.button_orbit == self.button_pan && self.modifier_orbit == self.modifier_pan {
+            return Err(ConfigError::UnreachablePan);
+        }
+        if self.key_zoom_in.is_some() && self.key_zoom_in == self.key_zoom_out {
+            return Err(ConfigError::ConflictingZoomKeys);
+        }
+        let inverted = |lower: Option<f32>, upper: Option<f32>| match (lower, upper) {
+            (Some(lower), Some(upper)) => lower > upper,
+            _ => false,
+        };
+        if inverted(self.yaw_lower_limit, self.yaw_upper_limit) {
+            return Err(ConfigError::InvertedYawLimits);
+        }
+        if inverted(self.pitch_lower_limit, self.pitch_upper_limit) {
+            return Err(ConfigError::InvertedPitchLimits);
+        }
+        if inverted(Some(self.zoom_lower_limit), self.zoom_upper_limit) {
+            return Err(ConfigError::InvertedZoomLimits);
+        }
+        Ok(())
+    }
+
+    /// The camera's transform for its current values, with `radius` in place of `self.radius`.
+    /// Unlike `rotation`, this takes `pivot_forward_offset` and `look_at_fixed` into account.
+    fn current_transform(&self, radius: f32, projection: &mut Projection) -> Transform {
+        let mut transform = Transform::IDENTITY;
+        util::update_orbit_transform_from_rotation(
+            self.rotation(),
+            radius,
+            self.focus,
+            self.pivot_offset(),
+            &mut transform,
+            projection,
+        );
+        if let Some(point) = self.look_at_fixed {
+            util::look_at_point(&mut transform, point);
+        }
+        transform
+    }
+
+    /// Whether `target_focus` is within the view at the camera's target values, leaving a margin of
+    /// `KEEP_FOCUS_ONSCREEN_MARGIN` at the edges. See `keep_focus_onscreen`.
+    fn is_target_focus_onscreen(&self, projection: &Projection) -> bool {
+        let (transform, _) = self.target_transform(projection);
+        let view_from_world = transform.compute_matrix().inverse();
+        let clip =
+            projection.get_clip_from_view() * view_from_world * self.target_focus.extend(1.0);
+        let ndc = clip.truncate() / clip.w;
+        clip.w > 0.0 && ndc.truncate().abs().max_element() <= 1.0 - KEEP_FOCUS_ONSCREEN_MARGIN
+    }
+
+    /// The camera's current rotation around the focus
+    fn rotation(&self) -> Quat {
+        util::axis_rotation(self.axis)
+            * self.globe_rotation.unwrap_or_else(|| {
+                util::rotation_from_yaw_pitch(
+                    self.yaw.unwrap_or(self.target_yaw),
+                    self.pitch.unwrap_or(self.target_pitch),
+                )
+            })
+    }
+
+    /// Change the rotation of the axis from `from` to `to`, while recalculating yaw and pitch
+    /// (and their targets) so that the camera stays in the same place
+    fn reorient_axis(&mut self, from: Quat, to: Quat) {
+        let change = to.inverse() * from;
+        let reorient = |yaw: f32, pitch: f32| {
+            let dir = change * util::rotation_from_yaw_pitch(yaw, pitch) * Vec3::Z;
+            let (new_yaw, new_pitch) = util::yaw_pitch_from_direction(dir);
+            // Avoid yaw jumping by a full turn, which would cause it to lerp the long way round
+            (util::wrap_angle_near(new_yaw, yaw), new_pitch)
+        };
+        if let (Some(yaw), Some(pitch)) = (self.yaw, self.pitch) {
+            let (yaw, pitch) = reorient(yaw, pitch);
+            self.yaw = Some(yaw);
+            self.pitch = Some(pitch);
+        }
+        (self.target_yaw, self.target_pitch) = reorient(self.target_yaw, self.target_pitch);
+        self.globe_rotation = self.globe_rotation.map(|rotation| change * rotation);
+        self.target_globe_rotation = self.target_globe_rotation.map(|rotation| change * rotation);
+        self.axis = util::axis_from_rotation(to);
+    }
+
+    /// Whether the current values have reached the target values
+    fn is_at_target(&self) -> bool {
+        self.focus == self.target_focus
+            && self.yaw == Some(self.target_yaw)
+            && self.pitch == Some(self.target_pitch)
+            && self.radius == Some(self.target_radius)
+            && self.globe_rotation == self.target_globe_rotation
+            && self.axis == self.target_axis
+    }
+}
+
+/// The values that define the position and orientation of a `PanOrbitCamera`.
+#[derive(Reflect, Copy, Clone, Debug, PartialEq)]
+pub struct PanOrbitCameraState {
+    /// The point the camera orbits around and looks at.
+    pub focus: Vec3,
+    /// Rotation in radians around the up axis, `PanOrbitCamera::axis[1]`.
+    pub yaw: f32,
+    /// Rotation in radians around the local X axis.
+    pub pitch: f32,
+    /// The distance from `focus`, or the projection's scale for an orthographic camera.
+    pub radius: f32,
+}
+
+impl PanOrbitCameraState {
+    /// Blend between this state and `other`, where a `t` of `0.0` gives this state and `1.0` gives
+    /// `other`. Use this with `PanOrbitCamera::set_state` to build your own transitions, e.g. to
+    /// follow a timeline or an easing curve. `focus`, `pitch`, and `radius` are interpolated
+    /// linearly. `yaw` takes the shortest way round, so blending from just under `PI` to just
+    /// over `-PI` crosses `PI` rather than turning almost all the way round, and the result is
+    /// within `PI` of this state's yaw.
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        let other_yaw = util::wrap_angle_near(other.yaw, self.yaw);
+        Self {
+            focus: self.focus.lerp(other.focus, t),
+            yaw: self.yaw.lerp(other_yaw, t),
+            pitch: self.pitch.lerp(other.pitch, t),
+            radius: self.radius.lerp(other.radius, t),
+        }
+    }
+
+    /// The same as `lerp`, except `radius` is interpolated logarithmically, i.e. multiplied by the
+    /// same factor for each equal step in `t`. This zooms at an even pace when the radii are very
+    /// different, e.g. when flying from an overview of a whole planet down to a building, where
+    /// linear interpolation would rush through the close up part. Both radii must be positive.
+    pub fn lerp_log_radius(&self, other: &Self, t: f32) -> Self {
+        Self {
+            radius: self.radius * (other.radius / self.radius).powf(t),
+            ..self.lerp(other, t)
+        }
+    }
+}
+
+/// A range of allowed pitch values, to be used with `PanOrbitCamera::set_pitch_range`. The presets
+/// save you from having to reason about which sign means what. For reference, a positive pitch
+/// means the camera is above the focus (looking down at it), and a negative pitch means it's below
+/// (looking up at it). A pitch of `0.0` is level with the focus, i.e. the horizon.
+#[derive(Reflect, Copy, Clone, Debug, PartialEq)]
+pub struct PitchRange {
+    /// The lower pitch limit, in radians.
+    pub lower: Option<f32>,
+    /// The upper pitch limit, in radians.
+    pub upper: Option<f32>,
+}
+
+impl PitchRange {
+    /// The camera always stays above the focus, looking down at it or horizontally, but never
+    /// up. Useful for aerial or map viewers. Equivalent to a pitch range of `0.0..=PI / 2.0`.
+    pub fn above_horizon() -> Self {
+        Self {
+            lower: Some(0.0),
+            upper: Some(PI / 2.0),
+        }
+    }
+
+    /// The camera always stays below the focus, looking up at it or horizontally, but never down.
+    /// Equivalent to a pitch range of `-PI / 2.0..=0.0`.
+    pub fn below_horizon() -> Self {
+        Self {
+            lower: Some(-PI / 2.0),
+            upper: Some(0.0),
+        }
+    }
+}
+
+/// A problem with a `PanOrbitCamera`'s configuration. See `PanOrbitCamera::validate`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    /// `button_orbit` and `button_pan` are the same, and so are `modifier_orbit` and
+    /// `modifier_pan`, so there's no way to pan.
+    UnreachablePan,
+    /// `key_zoom_in` and `key_zoom_out` are the same key, so holding it doesn't zoom.
+    ConflictingZoomKeys,
+    /// `yaw_lower_limit` is greater than `yaw_upper_limit`. They're used the other way round.
+    InvertedYawLimits,
+    /// `pitch_lower_limit` is greater than `pitch_upper_limit`. They're used the other way round.
+    InvertedPitchLimits,
+    /// `zoom_lower_limit` is greater than `zoom_upper_limit`. They're used the other way round.
+    InvertedZoomLimits,
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            ConfigError::UnreachablePan => {
+                "orbit and pan use the same button and modifier, so panning is unreachable"
+            }
+            ConfigError::ConflictingZoomKeys => "key_zoom_in and key_zoom_out are the same key",
+            ConfigError::InvertedYawLimits => "yaw_lower_limit is greater than yaw_upper_limit",
+            ConfigError::InvertedPitchLimits => {
+                "pitch_lower_limit is greater than pitch_upper_limit"
+            }
+            ConfigError::InvertedZoomLimits => "zoom_lower_limit is greater than zoom_upper_limit",
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Controls how horizontal orbiting behaves when the camera is at a pole, i.e. looking straight up
+/// or down. At the poles, changing the yaw just spins the view around its center, which can be
+/// disorienting, and the view will appear to snap when the camera leaves the pole.
+#[derive(Reflect, Default, Debug, Copy, Clone, PartialEq)]
+pub enum PoleBehavior {
+    /// Horizontal orbiting changes the yaw as normal, spinning the view.
+    #[default]
+    Free,
+    /// Horizontal orbiting is ignored while the camera is at a pole, so the yaw stays the same as
+    /// when the camera arrived at the pole, and the view doesn't snap when leaving it.
+    FreezeYaw,
+}
+
+/// How `focus` moves towards `target_focus`. See `PanOrbitCamera::pan_smoothing`.
+#[derive(Reflect, Default, Debug, Copy, Clone, PartialEq)]
+pub enum PanSmoothing {
+    /// Move a fraction of the remaining distance each frame, according to `pan_smoothness`. This
+    /// feels responsive when dragging, but long focus moves take longer the further they go, and
+    /// slowly creep up on the target at the end.
+    #[default]
+    Exponential,
+    /// Ease out from the current focus to `target_focus` over `duration` seconds, however far
+    /// away it is, so long focus moves finish in a predictable time. Each change to
+    /// `target_focus` starts a new ease from wherever the focus is, so this is best suited to
+    /// jumping to points programmatically; while dragging, the focus trails the pointer slightly.
+    /// `pan_smoothness` is ignored, but `max_pan_speed` still applies, and `transition_with` and
+    /// `DisableSmoothing` take priority.
+    Timed {
+        /// How long moving to a new `target_focus` takes, in seconds.
+        duration: f32,
+    },
+}
+
+/// Controls which point stays fixed when zooming.
+#[derive(Reflect, Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ZoomPivot {
+    /// Zooming changes `radius`, moving the camera towards or away from the focus, so the focus
+    /// stays where it is and things around it appear larger or smaller.
+    #[default]
+    Focus,
+    /// Zooming moves `focus` along the view direction, taking the camera with it, while `radius`
+    /// stays the same. This flies the camera forwards and backwards through the scene, so the
+    /// orbit pivot travels with it. Zoom limits don't apply, because the radius doesn't change,
+    /// so the camera keeps moving at the same rate even when the radius is at a limit.
+    /// This has no visible effect for orthographic projection, so `Focus` is used instead.
+    Eye,
+    /// Zooming changes `radius` like `Focus`, but also shifts `focus` so that whatever is under
+    /// the cursor stays under it, as in most CAD and 3D modelling apps. The point under the cursor
+    /// is taken to be on the plane through the focus, facing the camera. Uses
+    /// `ActiveCameraData::cursor_position`, and falls back to `Focus` without it. Only applies to
+    /// scrolling with the mouse, as the cursor has nothing to do with zooming by keys, touch, or
+    /// `CameraInput`, which zoom around the focus instead.
+    Cursor,
+}
+
+/// Controls which way dragging to orbit moves the camera. The two styles turn the view in
+/// opposite directions on both axes, so they're different from just inverting one axis.
+#[derive(Reflect, Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OrbitStyle {
+    /// The usual behavior, where dragging right orbits the camera to the left around the focus,
+    /// and dragging down orbits it up, so the scene turns along with the cursor.
+    #[default]
+    Camera,
+    /// Negates both yaw and pitch compared to `Camera`, as some CAD tools do, e.g. dragging right
+    /// orbits the camera to the right, so the scene turns against the cursor.
+    Object,
+}
+
+impl OrbitStyle {
+    /// The factor to multiply orbit input by
+    fn direction(self) -> f32 {
+        match self {
+            OrbitStyle::Camera => 1.0,
+            OrbitStyle::Object => -1.0,
+        }
+    }
+}
+
+/// Controls what happens when a camera's `follow_target` is despawned.
+#[derive(Reflect, Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FollowTargetLost {
+    /// Keep `follow_target` as it is, and leave the focus where the target was last seen. The
+    /// camera carries on following if the entity gets a `GlobalTransform` again.
+    #[default]
+    KeepFocus,
+    /// Set `follow_target` to `None`, leaving the focus where the target was last seen, so you
+    /// can tell that the target has gone.
+    ClearTarget,
+}
+
+/// Controls what zooming changes.
+#[derive(Reflect, Default, Debug, Copy, Clone, PartialEq)]
+pub enum ZoomMode {
+    /// Zooming changes `radius`, moving the camera towards or away from the focus, like a dolly.
+    #[default]
+    Radius,
+    /// Zooming narrows or widens the perspective projection's `fov`, like a telephoto lens, while
+    /// the camera stays where it is, so the perspective doesn't change. `zoom_sensitivity`,
+    /// `zoom_smoothness`, and `reversed_zoom` apply as usual, but `ZoomLevels` and `zoom_scaling`
+    /// don't. This has no effect for orthographic projection, so `Radius` is used instead.
+    Fov {
+        /// The narrowest field of view, in radians, i.e. the most zoomed in.
+        min_fov: f32,
+        /// The widest field of view, in radians, i.e. the most zoomed out.
+        max_fov: f32,
+    },
+}
+
+/// Controls how much each line of scrolling changes `radius`. Pixel-based scrolling, e.g. from a
+/// trackpad, changes it by the matching fraction of a line.
+#[derive(Reflect, Default, Debug, Copy, Clone, PartialEq)]
+pub enum ZoomScaling {
+    /// Each line changes the distance to the focus by 20% of the target distance, so zooming
+    /// slows down as the camera gets closer.
+    #[default]
+    Proportional,
+    /// Each line multiplies the distance to the focus by `factor`. For example, a factor of `0.9`
+    /// moves the camera 10% closer per scroll wheel notch when zooming in, and zooming out by one
+    /// notch undoes it exactly. This gives consistent zoom steps at all distances, so zooming from
+    /// `0.1` to `1000.0` feels uniform. `zoom_sensitivity` still applies, as an exponent.
+    Logarithmic {
+        /// The factor the distance is multiplied by per line of scrolling.
+        factor: f32,
+    },
+    /// Each line changes the distance to the focus by a fixed amount. This suits scenes where the
+    /// camera stays within a narrow range of distances.
+    Linear {
+        /// The distance the camera moves per line of scrolling.
+        step: f32,
+    },
+}
+
+/// Controls how `radius` is converted when the camera's `Projection` is switched between
+/// perspective and orthographic. For orthographic projection, `radius` is used as the projection's
+/// `scale`, which usually means something quite different to a distance.
+#[derive(Reflect, Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ProjectionSwitch {
+    /// `radius` is kept as it is, so a perspective radius of `50.0` becomes an orthographic scale
+    /// of `50.0`, and vice versa. Whether this looks similar depends on the scene and the
+    /// orthographic `scaling_mode`.
+    #[default]
+    RadiusAsScale,
+    /// `radius` is converted so that things at the focus appear the same size on screen after the
+    /// switch, using the perspective projection's vertical field of view and the orthographic
+    /// projection's size per unit of `scale`. The orthographic projection's `area` must be up to
+    /// date for this, e.g. because it was previously rendered, otherwise `radius` is kept as is.
+    /// Zoom limits still apply to the converted `radius`.
+    PreserveApparentSize,
+}
+
+/// The six views looking along the axes, named from the point of view of someone facing the
+/// default camera. See `PanOrbitCamera::set_view`.
+#[derive(Reflect, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CardinalView {
+    /// Looking from the positive Z side, which is where the camera is when yaw and pitch are zero.
+    Front,
+    /// Looking from the negative Z side.
+    Back,
+    /// Looking from the positive X side.
+    Right,
+    /// Looking from the negative X side.
+    Left,
+    /// Looking down from the positive Y side.
+    Top,
+    /// Looking up from the negative Y side.
+    Bottom,
+}
+
+impl CardinalView {
+    /// The yaw and pitch of this view, in radians.
+    pub fn yaw_pitch(self) -> (f32, f32) {
+        match self {
+            CardinalView::Front => (0.0, 0.0),
+            CardinalView::Back => (PI, 0.0),
+            CardinalView::Right => (PI / 2.0, 0.0),
+            CardinalView::Left => (-PI / 2.0, 0.0),
+            CardinalView::Top => (0.0, PI / 2.0),
+            CardinalView::Bottom => (0.0, -PI / 2.0),
+        }
+    }
+
+    /// The direction from the focus to the camera in this view, e.g. `Vec3::Y` for `Top`. Pass
+    /// this to `PanOrbitCamera::view_from` to look along the scene's axes rather than `axis`.
+    pub fn direction(self) -> Vec3 {
+        match self {
+            CardinalView::Front => Vec3::Z,
+            CardinalView::Back => Vec3::NEG_Z,
+            CardinalView::Right => Vec3::X,
+            CardinalView::Left => Vec3::NEG_X,
+            CardinalView::Top => Vec3::Y,
+            CardinalView::Bottom => Vec3::NEG_Y,
+        }
+    }
+}
+
+/// Add this component alongside `PanOrbitCamera` to restrict zooming to a set of discrete levels,
+/// like a web map. Each line of scrolling moves `target_radius` to the next level, and the camera
+/// eases between levels according to `zoom_smoothness`. Scrolling past the smallest or largest
+/// level does nothing. Note that `zoom_lower_limit` and `zoom_upper_limit` still apply.
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_panorbit_camera::{PanOrbitCamera, ZoomLevels};
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((
+///         PanOrbitCamera::default(),
+///         ZoomLevels::new([1.0, 2.0, 4.0, 8.0, 16.0]),
+///     ));
+/// }
+/// ```
+#[derive(Component, Reflect, Clone, Debug, PartialEq)]
+pub struct ZoomLevels {
+    levels: Vec<f32>,
+    pending_steps: f32,
+}
+
+impl ZoomLevels {
+    /// Create `ZoomLevels` from a list of radius values, in any order.
+    pub fn new(levels: impl IntoIterator<Item = f32>) -> Self {
+        let mut levels: Vec<f32> = levels.into_iter().collect();
+        levels.sort_by(f32::total_cmp);
+        levels.dedup();
+        Self {
+            levels,
+            pending_steps: 0.0,
+        }
+    }
+
+    /// The zoom levels, sorted from smallest to largest radius.
+    pub fn levels(&self) -> &[f32] {
+        &self.levels
+    }
+}
+
+/// Add this component alongside `PanOrbitCamera` to keep `target_focus` within one or more shapes,
+/// e.g. to stop users panning away from the scene. When there are several shapes, `combine`
+/// decides how they constrain the focus. Ignored while `PanOrbitCamera::limits_enabled` is `false`.
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_panorbit_camera::{FocusBounds, FocusBoundsShape, PanOrbitCamera};
+/// fn setup(mut commands: Commands) {
+///     // An L-shaped area made of two rooms
+///     commands.spawn((
+///         PanOrbitCamera::default(),
+///         FocusBounds::union([
+///             FocusBoundsShape::Cuboid {
+///                 min: Vec3::new(0.0, 0.0, 0.0),
+///                 max: Vec3::new(10.0, 3.0, 4.0),
+///             },
+///             FocusBoundsShape::Cuboid {
+///                 min: Vec3::new(0.0, 0.0, 0.0),
+///                 max: Vec3::new(4.0, 3.0, 10.0),
+///             },
+///         ]),
+///     ));
+/// }
+/// ```
+#[derive(Component, Reflect, Clone, Debug, PartialEq)]
+pub struct FocusBounds {
+    /// The shapes that constrain the focus.
+    pub shapes: Vec<FocusBoundsShape>,
+    /// How the shapes are combined when there's more than one.
+    pub combine: FocusBoundsCombine,
+    /// If set, the zoom can't go out further than `bounding_radius()` times this factor, so the
+    /// camera stays framed on the area the focus is allowed in. This lowers
+    /// `PanOrbitCamera::zoom_upper_limit` when that's set too. For orthographic projection, this
+    /// limits the projection's scale, like `zoom_upper_limit` does.
+    /// Defaults to `None`.
+    pub zoom_limit_factor: Option<f32>,
+}
+
+impl FocusBounds {
+    /// Create `FocusBounds` with a single shape.
+    pub fn new(shape: FocusBoundsShape) -> Self {
+        Self::union([shape])
+    }
+
+    /// Create `FocusBounds` that allow the focus anywhere inside any of the shapes.
+    pub fn union(shapes: impl IntoIterator<Item = FocusBoundsShape>) -> Self {
+        Self {
+            shapes: shapes.into_iter().collect(),
+            combine: FocusBoundsCombine::Union,
+            zoom_limit_factor: None,
+        }
+    }
+
+    /// Create `FocusBounds` that only allow the focus where all the shapes overlap.
+    pub fn intersection(shapes: impl IntoIterator<Item = FocusBoundsShape>) -> Self {
+        Self {
+            shapes: shapes.into_iter().collect(),
+            combine: FocusBoundsCombine::Intersection,
+            zoom_limit_factor: None,
+        }
+    }
+
+    /// Set `zoom_limit_factor`, to stop the zoom going out further than the bounds' size times
+    /// `factor`.
+    pub fn with_zoom_limit_factor(mut self, factor: f32) -> Self {
+        self.zoom_limit_factor = Some(factor);
+        self
+    }
+
+    /// The radius of a sphere that encloses the area the focus is allowed in. For a union, this
+    /// encloses every shape. For an intersection, this is the smallest shape's, since the overlap
+    /// can't be bigger than that. Returns `0.0` if there are no shapes.
+    pub fn bounding_radius(&self) -> f32 {
+        let spheres = self.shapes.iter().map(FocusBoundsShape::bounding_sphere);
+        match self.combine {
+            FocusBoundsCombine::Union => {
+                let Some((min, max)) = spheres.clone().fold(None, |acc, (center, radius)| {
+                    let (min, max) = acc.unwrap_or((center, center));
+                    Some((min.min(center - radius), max.max(center + radius)))
+                }) else {
+                    return 0.0;
+                };
+                let center = min.midpoint(max);
+                spheres
+                    .map(|(shape_center, radius)| center.distance(shape_center) + radius)
+                    .fold(0.0, f32::max)
+            }
+            FocusBoundsCombine::Intersection => spheres
+                .map(|(_, radius)| radius)
+                .reduce(f32::min)
+                .unwrap_or(0.0),
+        }
+    }
+
+    /// The point within the bounds that's closest to `point`, or `point` itself if it's already
+    /// within them. See `FocusBoundsCombine` for how this works with several shapes. If there are
+    /// no shapes, the focus is unconstrained.
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        match (self.shapes.as_slice(), self.combine) {
+            ([], _) => point,
+            ([shape], _) => shape.closest_point(point),
+            (shapes, FocusBoundsCombine::Union) => shapes
+                .iter()
+                .map(|shape| shape.closest_point(point))
+                .min_by(|a, b| {
+                    a.distance_squared(point)
+                        .total_cmp(&b.distance_squared(point))
+                })
+                .unwrap_or(point),
+            (shapes, FocusBoundsCombine::Intersection) => {
+                // Clamping into each shape in turn converges on the overlap, because the shapes
+                // are convex
+                let mut point = point;
+                for _ in 0..FOCUS_BOUNDS_INTERSECTION_ITERATIONS {
+                    for shape in shapes {
+                        point = shape.closest_point(point);
+                    }
+                }
+                point
+            }
+        }
+    }
+}
+
+/// How many times to clamp through all the shapes of an intersection
+const FOCUS_BOUNDS_INTERSECTION_ITERATIONS: usize = 8;
+
+/// How the shapes of `FocusBounds` are combined.
+#[derive(Reflect, Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FocusBoundsCombine {
+    /// The focus can be anywhere inside any of the shapes. Outside them, it moves to the nearest
+    /// point on any of the shapes, so it can slide along the outside of an L-shaped area and
+    /// round the corner into the other shape.
+    #[default]
+    Union,
+    /// The focus can only be where all the shapes overlap. Outside, it's clamped into each shape
+    /// in turn, which finds a point in the overlap close to, but not always exactly the nearest
+    /// to, the original. If the shapes don't overlap, the focus ends up between them.
+    Intersection,
+}
+
+/// A shape that constrains the focus. See `FocusBounds`.
+#[derive(Reflect, Debug, Copy, Clone, PartialEq)]
+pub enum FocusBoundsShape {
+    /// A sphere, centered on `center`.
+    Sphere {
+        /// The center of the sphere.
+        center: Vec3,
+        /// The radius of the sphere.
+        radius: f32,
+    },
+    /// An axis aligned box, from `min` to `max`.
+    Cuboid {
+        /// The corner with the smallest coordinates.
+        min: Vec3,
+        /// The corner with the largest coordinates.
+        max: Vec3,
+    },
+}
+
+impl FocusBoundsShape {
+    /// The center and radius of the smallest sphere that encloses the shape.
+    pub fn bounding_sphere(&self) -> (Vec3, f32) {
+        match *self {
+            FocusBoundsShape::Sphere { center, radius } => (center, radius),
+            FocusBoundsShape::Cuboid { min, max } => (min.midpoint(max), min.distance(max) / 2.0),
+        }
+    }
+
+    /// The point inside the shape that's closest to `point`, or `point` itself if it's inside.
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        match *self {
+            FocusBoundsShape::Sphere { center, radius } => {
+                center + (point - center).clamp_length_max(radius)
+            }
+            FocusBoundsShape::Cuboid { min, max } => point.clamp(min, max),
+        }
+    }
+}
+
+/// Add this component alongside `PanOrbitCamera` to keep the camera's eye on a path, e.g. for a
+/// guided tour through a building. Whenever the camera would move off the rail, `target_focus` is
+/// shifted so that the eye lands on the nearest point of the rail instead, keeping the camera's
+/// angle and `target_radius`. This means orbiting looks around from the rail, and panning or
+/// zooming slides along it. The targets are kept on the rail, so while the camera is smoothly
+/// moving towards them, the eye can stray from the rail slightly if `pan_smoothness`,
+/// `orbit_smoothness`, and `zoom_smoothness` differ. This is applied after `FocusBounds` and
+/// `PanOrbitCamera::lock_focus_height`, so the rail takes priority over them. Like `FocusBounds`,
+/// it's ignored while `PanOrbitCamera::limits_enabled` is `false`.
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_panorbit_camera::{CameraRail, PanOrbitCamera};
+/// fn setup(mut commands: Commands) {
+///     // Walk down a corridor at eye height, then turn right
+///     commands.spawn((
+///         PanOrbitCamera::default(),
+///         CameraRail::new([
+///             Vec3::new(0.0, 1.6, 10.0),
+///             Vec3::new(0.0, 1.6, 0.0),
+///             Vec3::new(10.0, 1.6, 0.0),
+///         ]),
+///     ));
+/// }
+/// ```
+#[derive(Component, Reflect, Clone, Debug, PartialEq)]
+pub struct CameraRail {
+    /// The points of the rail, which is made of straight lines between consecutive points. Like
+    /// `PanOrbitCamera::focus`, these are relative to the parent entity, if there is one. A single
+    /// point pins the eye in place, and no points disables the rail.
+    pub points: Vec<Vec3>,
+}
+
+impl CameraRail {
+    /// Create a rail through `points`, in order.
+    pub fn new(points: impl IntoIterator<Item = Vec3>) -> Self {
+        Self {
+            points: points.into_iter().collect(),
+        }
+    }
+
+    /// The point on the rail that's closest to `point`, or `point` itself if the rail has no
+    /// points.
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        match self.points.as_slice() {
+            [] => point,
+            [only] => *only,
+            points => points
+                .windows(2)
+                .map(|segment| {
+                    let (start, end) = (segment[0], segment[1]);
+                    let along = end - start;
+                    let length_squared = along.length_squared();
+                    let t = match length_squared > 0.0 {
+                        true => ((point - start).dot(along) / length_squared).clamp(0.0, 1.0),
+                        false => 0.0,
+                    };
+                    start + along * t
+                })
+                .min_by(|a, b| {
+                    a.distance_squared(point)
+                        .total_cmp(&b.distance_squared(point))
+                })
+                .unwrap_or(point),
+        }
+    }
+}
+
+/// A temporary smoothness used by `PanOrbitCamera` during a transition. See
+/// `PanOrbitCamera::transition_with`.
+#[derive(Reflect, Copy, Clone, Debug, PartialEq)]
+pub struct SmoothnessOverride {
+    /// The smoothness used for orbiting, panning, and zooming during the transition.
+    pub smoothness: f32,
+    /// The maximum remaining time of the transition, in seconds.
+    pub remaining: f32,
+}
+
+/// How `PanOrbitCamera::dynamic_fov` widens the field of view with the camera's speed. The speed
+/// is how fast the camera is orbiting, in radians per second, plus how fast it's zooming, in
+/// multiples of the radius per second.
+#[derive(Reflect, Copy, Clone, Debug, PartialEq)]
+pub struct DynamicFovConfig {
+    /// The vertical field of view, in radians, when the camera isn't moving.
+    pub base_fov: f32,
+    /// The vertical field of view, in radians, when the camera is moving at `full_speed` or
+    /// faster.
+    pub max_fov: f32,
+    /// The speed at which the field of view reaches `max_fov`. It widens linearly up to this.
+    pub full_speed: f32,
+    /// How smoothly the field of view responds to changes in speed, from `0.0` (instantly) to
+    /// `1.0`, like `PanOrbitCamera::orbit_smoothness`.
+    pub smoothness: f32,
+}
+
+impl Default for DynamicFovConfig {
+    fn default() -> Self {
+        Self {
+            base_fov: PI / 4.0,
+            max_fov: PI / 3.0,
+            full_speed: PI,
+            smoothness: 0.5,
+        }
+    }
+}
+
+/// Tracks which `PanOrbitCamera` is active (should handle input events), along with the window
+/// and viewport dimensions, which are used for scaling mouse motion.
+/// `PanOrbitCameraPlugin` manages this resource automatically, in order to support multiple
+/// viewports/windows. However, if this doesn't work for you, you can take over and manage it
+/// yourself, e.g. when you want to control a camera that is rendering to a texture. If you only
+/// want to choose which camera is active, use `ExplicitActiveCamera` instead.
+#[derive(Resource, Default, Debug, PartialEq)]
+pub struct ActiveCameraData {
+    /// ID of the entity with `PanOrbitCamera` that will handle user input. In other words, this
+    /// is the camera that will move when you orbit/pan/zoom.
+    pub entity: Option<Entity>,
+    /// The viewport size. This is only used to scale the panning mouse motion. I recommend setting
+    /// this to the actual render target dimensions (e.g. the image or viewport), and changing
+    /// `PanOrbitCamera::pan_sensitivity` to adjust the sensitivity if required.
+    pub viewport_size: Option<Vec2>,
+    /// The size of the window. This is only used to scale the orbit mouse motion. I recommend
+    /// setting this to actual dimensions of the window that you want to control the camera from,
+    /// and changing `PanOrbitCamera::orbit_sensitivity` to adjust the sensitivity if required.
+    pub window_size: Option<Vec2>,
+    /// The position of the cursor in the active camera's viewport, in logical pixels from its top
+    /// left corner, or `None` if the cursor isn't in the camera's window. This is only used by
+    /// `ZoomPivot::Cursor` and `DoubleTapAction::FocusCursor`. Unlike the sizes, it's updated
+    /// every frame, as the cursor moves.
+    pub cursor_position: Option<Vec2>,
+    /// Indicates to `PanOrbitCameraPlugin` that it should not update/overwrite this resource.
+    /// If you are manually updating this resource you should set this to `true`.
+    /// Note that setting this to `true` will effectively break multiple viewport/window support
+    /// unless you manually reimplement it.
+    pub manual: bool,
+}
+
+impl ActiveCameraData {
+    /// `cursor_position` in normalized device coordinates, i.e. from -1 to 1 across the viewport
+    /// with y up
+    fn cursor_ndc(&self) -> Option<Vec2> {
+        let (cursor_position, viewport_size) = (self.cursor_position?, self.viewport_size?);
+        Some((cursor_position / viewport_size * 2.0 - 1.0) * Vec2::new(1.0, -1.0))
+    }
+}
+
+/// Insert this resource to choose the active `PanOrbitCamera` yourself, instead of letting
+/// `PanOrbitCameraPlugin` pick the camera under the cursor. Unlike setting
+/// `ActiveCameraData::manual`, the plugin still keeps the viewport and window sizes in
+/// `ActiveCameraData` up to date for the chosen camera, so input is scaled correctly.
+/// Remove the resource to go back to automatic detection.
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_panorbit_camera::{ExplicitActiveCamera, PanOrbitCamera};
+/// fn setup(mut commands: Commands) {
+///     let camera = commands.spawn(PanOrbitCamera::default()).id();
+///     commands.insert_resource(ExplicitActiveCamera::new(camera));
+/// }
+/// ```
+#[derive(Resource, Default, Debug, Copy, Clone, PartialEq)]
+pub struct ExplicitActiveCamera {
+    /// The camera that will handle user input. If `None`, no camera will.
+    pub entity: Option<Entity>,
+}
+
+impl ExplicitActiveCamera {
+    /// Create an `ExplicitActiveCamera` with `entity` as the active camera.
+    pub fn new(entity: Entity) -> Self {
+        Self {
+            entity: Some(entity),
+        }
+    }
+
+    /// Make `entity` the active camera.
+    pub fn set(&mut self, entity: Entity) {
+        self.entity = Some(entity);
+    }
+
+    /// Make no camera active, so none of them handle user input.
+    pub fn clear(&mut self) {
+        self.entity = None;
+    }
+}
+
+/// Insert this resource to have `PanOrbitCameraPlugin` fill in `ActiveCameraData::viewport_size`,
+/// `ActiveCameraData::window_size`, and `ActiveCameraData::cursor_position` from the active camera
+/// even when `ActiveCameraData::manual` is `true`. You still choose the camera by setting
+/// `ActiveCameraData::entity`, but don't have to keep the sizes up to date, so input is scaled
+/// correctly. Sizes that can't be determined, e.g. the window size of a camera that renders to a
+/// texture, are left as you set them.
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_panorbit_camera::{ActiveCameraData, AutoFillViewportSizes, PanOrbitCamera};
+/// fn setup(mut commands: Commands, mut active_cam: ResMut<ActiveCameraData>) {
+///     let camera = commands.spawn(PanOrbitCamera::default()).id();
+///     active_cam.entity = Some(camera);
+///     active_cam.manual = true;
+///     commands.insert_resource(AutoFillViewportSizes);
+/// }
+/// ```
+#[derive(Resource, Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub struct AutoFillViewportSizes;
+
+/// A `SystemParam` for checking which `PanOrbitCamera` is active, i.e. which camera is handling
+/// user input.
+///
+/// This reflects `ActiveCameraData`, which is updated in `PostUpdate` as part of
+/// `PanOrbitCameraSystemSet`. That means systems running in `Update` will see which camera was
+/// active as of the previous frame. To see this frame's value, run your system in `PostUpdate`,
+/// after `PanOrbitCameraSystemSet`.
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_panorbit_camera::{ActivePanOrbitCamera, PanOrbitCamera};
+/// fn print_active(active: ActivePanOrbitCamera, cameras: Query<Entity, With<PanOrbitCamera>>) {
+///     for entity in cameras.iter() {
+///         if active.is_camera_active(entity) {
+///             info!("{entity} is active");
+///         }
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct ActivePanOrbitCamera<'w> {
+    active_cam: Res<'w, ActiveCameraData>,
+}
+
+impl ActivePanOrbitCamera<'_> {
+    /// The entity of the active camera, if any.
+    pub fn entity(&self) -> Option<Entity> {
+        self.active_cam.entity
+    }
+
+    /// Whether the camera with the given entity is the active camera.
+    pub fn is_camera_active(&self, entity: Entity) -> bool {
+        self.active_cam.entity == Some(entity)
+    }
+}
+
+/// A `SystemParam` for finding which `PanOrbitCamera` owns a position in a window, using the same
+/// rules that `PanOrbitCameraPlugin` uses to choose the active camera. Use this to route your own
+/// input to the right camera.
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy::window::PrimaryWindow;
+/// # use bevy_panorbit_camera::PanOrbitCameraViewports;
+/// fn print_hovered(
+///     viewports: PanOrbitCameraViewports,
+///     windows: Query<(Entity, &Window), With<PrimaryWindow>>,
+/// ) {
+///     let Ok((window_entity, window)) = windows.get_single() else {
+///         return;
+///     };
+///     if let Some(position) = window.cursor_position() {
+///         if let Some(camera) = viewports.camera_at_position(window_entity, position) {
+///             info!("Cursor is over {camera}");
+///         }
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct PanOrbitCameraViewports<'w, 's> {
+    primary_windows: Query<'w, 's, Entity, With<PrimaryWindow>>,
+    windows: Query<'w, 's, &'static Window>,
+    orbit_cameras: Query<'w, 's, (Entity, &'static Camera, &'static PanOrbitCamera)>,
+}
+
+impl PanOrbitCameraViewports<'_, '_> {
+    /// The camera that owns `position`, given in logical pixels in `window`, if any. This is the
+    /// camera whose viewport contains the position, taking `PanOrbitCamera::input_window` into
+    /// account. Where viewports overlap, the camera with the highest `Camera::order` wins.
+    pub fn camera_at_position(&self, window: Entity, position: Vec2) -> Option<Entity> {
+        let mut found = None;
+        let mut max_cam_order = 0;
+        for (entity, camera, pan_orbit) in self.orbit_cameras.iter() {
+            let Some((window_entity, _, renders_to_window)) =
+                input_window(camera, pan_orbit, &self.primary_windows, &self.windows)
+            else {
+                continue;
+            };
+            if window_entity == window
+                && viewport_contains(camera, renders_to_window, position)
+                && camera.order >= max_cam_order
+            {
+                found = Some(entity);
+                max_cam_order = camera.order;
+            }
+        }
+        found
+    }
+}
+
+/// Gather data about the active viewport, i.e. the viewport the user is interacting with.
+/// Enables multiple viewports/windows.
+#[allow(clippy::too_many_arguments)]
+fn active_viewport_data(
+    mut active_cam: ResMut<ActiveCameraData>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    key_input: Res<ButtonInput<KeyCode>>,
+    scroll_events: EventReader<MouseWheel>,
+    touches: Res<Touches>,
+    primary_windows: Query<Entity, With<PrimaryWindow>>,
+    windows: Query<&Window>,
+    orbit_cameras: Query<(Entity, &Camera, &PanOrbitCamera)>,
+    #[cfg(feature = "bevy_egui")] egui_wants_focus: Res<EguiWantsFocus>,
+) {
+    // Keep the active camera for the rest of a drag, even if the cursor moves into an overlapping
+    // viewport, so scrolling or pressing another button mid-drag doesn't switch cameras
+    let dragging = active_cam
+        .entity
+        .and_then(|entity| orbit_cameras.get(entity).ok())
+        .is_some_and(|(_, _, pan_orbit)| {
+            let orbit_held = input::orbit_pressed(pan_orbit, &mouse_input, &key_input)
+                && !input::orbit_just_pressed(pan_orbit, &mouse_input, &key_input);
+            let pan_held = input::pan_pressed(pan_orbit, &mouse_input, &key_input)
+                && !input::pan_just_pressed(pan_orbit, &mouse_input, &key_input);
+            orbit_held || pan_held
+        });
+    if dragging {
+        return;
+    }
+
+    let mut new_resource = ActiveCameraData::default();
+    let mut max_cam_order = 0;
+
+    let mut has_input = false;
+    for (entity, camera, pan_orbit) in orbit_cameras.iter() {
+        let input_just_activated = input::orbit_just_pressed(pan_orbit, &mouse_input, &key_input)
+            || input::pan_just_pressed(pan_orbit, &mouse_input, &key_input)
+            || !scroll_events.is_empty()
+            || (touches.iter_just_pressed().count() > 0
+                && touches.iter_just_pressed().count() == touches.iter().count());
+
+        if input_just_activated {
+            has_input = true;
+            #[allow(unused_mut, unused_assignments)]
+            let mut should_get_input = true;
+            #[cfg(feature = "bevy_egui")]
+            {
+                should_get_input = !egui_wants_focus.camera_should_ignore();
+            }
+            if should_get_input {
+                let Some((_, window, renders_to_window)) =
+                    input_window(camera, pan_orbit, &primary_windows, &windows)
+                else {
+                    continue;
+                };
+
+                // Is the cursor/touch in this window?
+                // Note: there's a bug in winit that causes `window.cursor_position()` to return
+                // a `Some` value even if the cursor is not in this window, in very specific cases.
+                // See: https://github.com/Plonq/bevy_panorbit_camera/issues/22
+                if let Some(input_position) = window.cursor_position().or(touches
+                    .iter_just_pressed()
+                    .collect::<Vec<_>>()
+                    .first()
+                    .map(|touch| touch.position()))
+                {
+                    // Only set if camera order is higher. This may overwrite a previous value
+                    // in the case the viewport is overlapping another viewport.
+                    if viewport_contains(camera, renders_to_window, input_position)
+                        && camera.order >= max_cam_order
+                    {
+                        new_resource = ActiveCameraData {
+                            entity: Some(entity),
+                            viewport_size: camera.logical_viewport_size(),
+                            window_size: Some(Vec2::new(window.width(), window.height())),
+                            cursor_position: active_cam.cursor_position,
+                            manual: false,
+                        };
+                        max_cam_order = camera.order;
+                    }
+                }
+            }
+        }
+    }
+
+    if has_input {
+        active_cam.set_if_neq(new_resource);
+    }
+}
+
+/// Keeps `ActiveCameraData` in sync with `ExplicitActiveCamera`, filling in the viewport and
+/// window sizes of the chosen camera
+fn explicit_active_camera_data(
+    mut active_cam: ResMut<ActiveCameraData>,
+    explicit: Res<ExplicitActiveCamera>,
+    primary_windows: Query<Entity, With<PrimaryWindow>>,
+    windows: Query<&Window>,
+    orbit_cameras: Query<&Camera, With<PanOrbitCamera>>,
+) {
+    let mut new_resource = ActiveCameraData::default();
+    if let Some(entity) = explicit.entity {
+        if let Ok(camera) = orbit_cameras.get(entity) {
+            let window = match camera.target {
+                RenderTarget::Window(win_ref) => {
+                    target_window(win_ref, &primary_windows, &windows).map(|(_, window)| window)
+                }
+                _ => None,
+            };
+            new_resource = ActiveCameraData {
+                entity: Some(entity),
+                viewport_size: camera.logical_viewport_size(),
+                window_size: window.map(|window| Vec2::new(window.width(), window.height())),
+                cursor_position: active_cam.cursor_position,
+                manual: false,
+            };
+        }
+    }
+    active_cam.set_if_neq(new_resource);
+}
+
+/// Fills in the viewport and window sizes for a manually chosen active camera. See
+/// `AutoFillViewportSizes`.
+fn auto_fill_viewport_sizes(
+    mut active_cam: ResMut<ActiveCameraData>,
+    primary_windows: Query<Entity, With<PrimaryWindow>>,
+    windows: Query<&Window>,
+    orbit_cameras: Query<&Camera, With<PanOrbitCamera>>,
+) {
+    let Some(entity) = active_cam.entity else {
+        return;
+    };
+    let Ok(camera) = orbit_cameras.get(entity) else {
+        return;
+    };
+    let window = match camera.target {
+        RenderTarget::Window(win_ref) => {
+            target_window(win_ref, &primary_windows, &windows).map(|(_, window)| window)
+        }
+        _ => None,
+    };
+    // Keep the user's values for anything that can't be worked out, e.g. the window size of a
+    // camera that renders to a texture
+    let viewport_size = camera.logical_viewport_size().or(active_cam.viewport_size);
+    let window_size = window
+        .map(|window| Vec2::new(window.width(), window.height()))
+        .or(active_cam.window_size);
+    if active_cam.viewport_size != viewport_size || active_cam.window_size != window_size {
+        active_cam.viewport_size = viewport_size;
+        active_cam.window_size = window_size;
+    }
+}
+
+/// Keep `ActiveCameraData::cursor_position` up to date for the active camera
+fn active_cursor_position(
+    mut active_cam: ResMut<ActiveCameraData>,
+    primary_windows: Query<Entity, With<PrimaryWindow>>,
+    windows: Query<&Window>,
+    orbit_cameras: Query<(&Camera, &PanOrbitCamera)>,
+) {
+    let cursor_position = active_cam.entity.and_then(|entity| {
+        let (camera, pan_orbit) = orbit_cameras.get(entity).ok()?;
+        let (_, window, renders_to_window) =
+            input_window(camera, pan_orbit, &primary_windows, &windows)?;
+        let cursor_position = window.cursor_position()?;
+        // If the camera renders somewhere else, the whole input window acts as its viewport
+        let viewport_min = match renders_to_window {
+            true => camera.logical_viewport_rect()?.min,
+            false => Vec2::ZERO,
+        };
+        Some(cursor_position - viewport_min)
+    });
+    if active_cam.cursor_position != cursor_position {
+        active_cam.cursor_position = cursor_position;
+    }
+}
+
+/// Finds the window that a camera takes input from, which is the window it renders to unless
+/// `PanOrbitCamera::input_window` is set. Also returns the window's entity, and whether the camera
+/// renders to it.
+fn input_window<'a>(
+    camera: &Camera,
+    pan_orbit: &PanOrbitCamera,
+    primary_windows: &Query<Entity, With<PrimaryWindow>>,
+    windows: &'a Query<&Window>,
+) -> Option<(Entity, &'a Window, bool)> {
+    let render_window = match camera.target {
+        RenderTarget::Window(win_ref) => target_window(win_ref, primary_windows, windows),
+        _ => None,
+    };
+    match pan_orbit.input_window {
+        Some(input_window) => {
+            let window = windows.get(input_window).ok()?;
+            let renders_to_window =
+                render_window.is_some_and(|(window_entity, _)| window_entity == input_window);
+            Some((input_window, window, renders_to_window))
+        }
+        // Window does not exist - maybe it was closed and the camera not cleaned up, or the
+        // camera doesn't render to a window
+        None => render_window.map(|(window_entity, window)| (window_entity, window, true)),
+    }
+}
+
+/// Whether `position`, in the camera's input window, is within the camera's viewport. If the
+/// camera renders somewhere else, the whole input window counts.
+fn viewport_contains(camera: &Camera, renders_to_window: bool, position: Vec2) -> bool {
+    if !renders_to_window {
+        return true;
+    }
+    camera
+        .logical_viewport_rect()
+        .is_some_and(|Rect { min, max }| {
+            position.x > min.x && position.x < max.x && position.y > min.y && position.y < max.y
+        })
+}
+
+/// Finds the window that a camera renders to, along with its entity
+fn target_window<'a>(
+    win_ref: WindowRef,
+    primary_windows: &Query<Entity, With<PrimaryWindow>>,
+    windows: &'a Query<&Window>,
+) -> Option<(Entity, &'a Window)> {
+    let window_entity = match win_ref {
+        WindowRef::Primary => {
+            // There should only be one primary window, but there can briefly be none or several,
+            // e.g. while windows are being created or closed
+            let mut primary_windows = primary_windows.iter();
+            let primary_window = primary_windows.next();
+            if primary_window.is_none() {
+                debug_once!("No primary window found, skipping cameras that render to it");
+            } else if primary_windows.next().is_some() {
+                debug_once!("Multiple primary windows found, using the first one");
+            }
+            primary_window?
+        }
+        WindowRef::Entity(entity) => entity,
+    };
+    windows
+        .get(window_entity)
+        .ok()
+        .map(|window| (window_entity, window))
+}
+
+/// Logs a warning for newly added cameras with an invalid configuration
+fn warn_invalid_config(orbit_cameras: Query<(Entity, &PanOrbitCamera), Added<PanOrbitCamera>>) {
+    for (entity, pan_orbit) in orbit_cameras.iter() {
+        if let Err(err) = pan_orbit.validate() {
+            warn!("PanOrbitCamera on entity {entity} is misconfigured: {err}");
+        }
+    }
+}
+
+/// Inserts a default `Projection` for cameras without one, e.g. because it was removed, as
+/// `pan_orbit_camera` skips them, so they would otherwise silently never move
+fn insert_missing_projection(
+    mut commands: Commands,
+    orbit_cameras: Query<Entity, (With<PanOrbitCamera>, Without<Projection>)>,
+) {
+    for entity in orbit_cameras.iter() {
+        warn!(
+            "PanOrbitCamera on entity {entity} has no Projection, which it needs to move, so a \
+             default perspective projection was inserted"
+        );
+        commands.entity(entity).insert(Projection::default());
+    }
+}
+
+/// Sent when a `PanOrbitCamera` moves, i.e. its yaw, pitch, radius, or focus changes, including
+/// while it's smoothly moving towards its target values. Use
+/// `PanOrbitCamera::change_event_angle_threshold` and `change_event_distance_threshold` to send
+/// fewer events.
+/// Events are sent in `PostUpdate`, as part of `PanOrbitCameraSystemSet`.
+#[derive(Event, Copy, Clone, Debug, PartialEq)]
+pub struct PanOrbitCameraChanged {
+    /// The entity with the `PanOrbitCamera` that moved.
+    pub entity: Entity,
+    /// The camera's new state.
+    pub state: PanOrbitCameraState,
+}
+
+/// The entity a camera is following, and its progress gliding to it if it changed recently
+struct FollowState {
+    target: Entity,
+    handoff: Option<FollowHandoff>,
+}
+
+/// A glide of the focus from `start` to a newly followed entity
+struct FollowHandoff {
+    start: Vec3,
+    elapsed: f32,
+}
+
+/// A camera's target values, for telling whether they changed since the previous frame
+#[derive(PartialEq)]
+struct Targets {
+    orbit: OrbitTarget,
+    focus: Vec3,
+    radius: f32,
+}
+
+/// Where a camera is orbiting to, as part of its `Targets`
+#[derive(PartialEq)]
+enum OrbitTarget {
+    YawPitch(f32, f32),
+    /// In globe mode, `target_yaw` and `target_pitch` follow the eased rotation, so only the
+    /// target rotation tells whether the orbit target changed
+    Globe(Quat),
+}
+
+/// The state of `PanOrbitCamera::orbit_ratchet`
+struct OrbitRatchet {
+    /// The yaw and pitch the orbit input would have moved to without ratcheting
+    input: Vec2,
+    /// The ratcheted yaw and pitch, i.e. `target_yaw` and `target_pitch`
+    target: Vec2,
+}
+
+/// A timed ease of the focus from `from` to `to`, for `PanSmoothing::Timed`
+struct PanEase {
+    from: Vec3,
+    to: Vec3,
+    elapsed: f32,
+}
+
+/// Moves the focus of cameras with a `follow_target` to their target
+fn follow_targets(
+    mut orbit_cameras: Query<(Entity, &mut PanOrbitCamera)>,
+    targets: Query<&GlobalTransform>,
+    time: Option<Res<Time>>,
+    mut follow_states: Local<EntityHashMap<FollowState>>,
+) {
+    let frame_dt = time.map_or(FALLBACK_DT, |time| time.delta_secs());
+    for (entity, mut pan_orbit) in orbit_cameras.iter_mut() {
+        let Some(target) = pan_orbit.follow_target else {
+            follow_states.remove(&entity);
+            continue;
+        };
+        let Ok(target_transform) = targets.get(target) else {
+            if pan_orbit.follow_target_lost == FollowTargetLost::ClearTarget {
+                debug!("Follow target {target} of camera {entity} is gone, clearing it");
+                pan_orbit.follow_target = None;
+            }
+            continue;
+        };
+        let target_position = target_transform.translation();
+        let duration = pan_orbit.follow_transition_duration;
+        let state = follow_states.entry(entity).or_insert(FollowState {
+            target,
+            handoff: None,
+        });
+        if state.target != target {
+            state.target = target;
+            state.handoff = (duration > 0.0).then_some(FollowHandoff {
+                start: pan_orbit.focus,
+                elapsed: 0.0,
+            });
+        }
+
+        if let Some(handoff) = &mut state.handoff {
+            handoff.elapsed += frame_dt;
+            let t = (handoff.elapsed / duration).min(1.0);
+            let eased = t * t * (3.0 - 2.0 * t);
+            let focus = handoff.start.lerp(target_position, eased);
+            // Set both so that pan smoothing doesn't apply on top of the handoff
+            pan_orbit.focus = focus;
+            pan_orbit.target_focus = focus;
+            pan_orbit.force_update = true;
+            if t >= 1.0 {
+                state.handoff = None;
+            }
+        } else {
+            pan_orbit.target_focus = target_position;
+        }
+    }
+    follow_states.retain(|entity, _| orbit_cameras.contains(*entity));
+}
+
+/// Sends `PanOrbitCameraChanged` events for cameras that have moved far enough since their last
+/// event, or have come to rest somewhere else
+fn send_change_events(
+    orbit_cameras: Query<(Entity, &PanOrbitCamera)>,
+    mut last_sent: Local<EntityHashMap<PanOrbitCameraState>>,
+    mut events: EventWriter<PanOrbitCameraChanged>,
+) {
+    last_sent.retain(|entity, _| orbit_cameras.contains(*entity));
+    for (entity, pan_orbit) in orbit_cameras.iter() {
+        let (Some(yaw), Some(pitch), Some(radius)) =
+            (pan_orbit.yaw, pan_orbit.pitch, pan_orbit.radius)
+        else {
+            continue;
+        };
+        let state = PanOrbitCameraState {
+            focus: pan_orbit.focus,
+            yaw,
+            pitch,
+            radius,
+        };
+        let settled = yaw == pan_orbit.target_yaw
+            && pitch == pan_orbit.target_pitch
+            && radius == pan_orbit.target_radius
+            && pan_orbit.focus == pan_orbit.target_focus;
+        let changed = last_sent.get(&entity).is_none_or(|last| {
+            let turned = (state.yaw - last.yaw)
+                .abs()
+                .max((state.pitch - last.pitch).abs());
+            let moved = (state.focus - last.focus)
+                .abs()
+                .max_element()
+                .max((state.radius - last.radius).abs());
+            turned > pan_orbit.change_event_angle_threshold
+                || moved > pan_orbit.change_event_distance_threshold
+                // Don't leave listeners with a state from just before the camera stopped
+                || (settled && state != *last)
+        });
+        if changed {
+            last_sent.insert(entity, state);
+            events.send(PanOrbitCameraChanged { entity, state });
+        }
+    }
+}
+
+/// Zooms cameras with `PanOrbitCamera::refit_on_resize` set to fit their bounds, whenever their
+/// viewport size changes
+fn refit_on_resize(
+    mut orbit_cameras: Query<(Entity, &mut PanOrbitCamera, &Camera, &Projection)>,
+    mut viewport_sizes: Local<EntityHashMap<Vec2>>,
+) {
+    viewport_sizes.retain(|entity, _| orbit_cameras.contains(*entity));
+    for (entity, mut pan_orbit, camera, projection) in orbit_cameras.iter_mut() {
+        let (Some(bounds), Some(size)) =
+            (pan_orbit.refit_on_resize, camera.logical_viewport_size())
+        else {
+            continue;
+        };
+        let previous_size = viewport_sizes.insert(entity, size);
+        if previous_size.is_none_or(|previous_size| previous_size == size) {
+            continue;
+        }
+        let bounds_radius = Vec3::from(bounds.center).distance(pan_orbit.target_focus)
+            + bounds.half_extents.length();
+        if let Some(radius) =
+            util::radius_to_fit(bounds_radius, projection, pan_orbit.frame_padding)
+        {
+            pan_orbit.target_radius = radius;
+            pan_orbit.force_update = true;
+        }
+    }
+}
+
+/// The speed below which zoom momentum stops, in pixel-based scroll amount per second. That's
+/// `MouseKeyTracker::scroll_pixel` (0.005 per pixel scrolled) after `zoom_sensitivity` is applied.
+const ZOOM_MOMENTUM_MIN_VELOCITY: f32 = 0.01;
+
+/// The time step, in seconds, assumed for each frame when there's no `Time` resource
+const FALLBACK_DT: f32 = 1.0 / 60.0;
+
+/// How much of the view is kept clear at each edge by `PanOrbitCamera::keep_focus_onscreen`, in
+/// normalized device coordinates, i.e. a tenth of the view's width or height
+const KEEP_FOCUS_ONSCREEN_MARGIN: f32 = 0.2;
+
+/// Iterations of the search for how far the focus can pan with
+/// `PanOrbitCamera::keep_focus_onscreen`
+const KEEP_FOCUS_ONSCREEN_ITERATIONS: usize = 16;
+
+/// Main system for processing input and converting to transformations
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn pan_orbit_camera(
+    active_cam: Res<ActiveCameraData>,
+    mouse_key_tracker: Res<MouseKeyTracker>,
+    touch_tracker: Res<TouchTracker>,
+    key_input: Res<ButtonInput<KeyCode>>,
+    mut orbit_cameras: Query<(
+        Entity,
+        &mut PanOrbitCamera,
+        &mut Transform,
+        &mut Projection,
+        Option<&mut ZoomLevels>,
+        Option<&FocusBounds>,
+        Option<&CameraRail>,
+    )>,
+    time: Option<Res<Time>>,
+    mut six_dof_input: Option<ResMut<SixDofInput>>,
+    mut camera_input: Option<ResMut<CameraInput>>,
+    cursor_pivot: Option<Res<CursorPivot>>,
+    disable_smoothing: Option<Res<DisableSmoothing>>,
+    mut zoom_velocities: Local<EntityHashMap<f32>>,
+    mut pan_hold_times: Local<EntityHashMap<f32>>,
+    mut pan_eases: Local<EntityHashMap<PanEase>>,
+    mut last_projections: Local<EntityHashMap<(bool, f32)>>,
+    (mut target_fovs, mut orbit_ratchets, mut previous_targets): (
+        Local<EntityHashMap<f32>>,
+        Local<EntityHashMap<OrbitRatchet>>,
+        Local<EntityHashMap<Targets>>,
+    ),
+) {
+    let frame_dt = time.map_or(FALLBACK_DT, |time| time.delta_secs());
+    let disable_smoothing = disable_smoothing.is_some_and(|disable| disable.0);
+    let six_dof = six_dof_input.as_deref().map(|input| {
+        (
+            input.translation * input.translation_sensitivity,
+            input.rotation * input.rotation_sensitivity,
+        )
+    });
+    if let Some(input) = six_dof_input.as_deref_mut() {
+        input.translation = Vec3::ZERO;
+        input.rotation = Vec3::ZERO;
+    }
+    let camera_input = camera_input.as_deref_mut().map(|input| {
+        let current = *input;
+        *input = CameraInput {
+            camera: input.camera,
+            orbit_joystick: input.orbit_joystick,
+            zoom_axis: input.zoom_axis,
+            ..default()
+        };
+        current
+    });
+    last_projections.retain(|entity, _| orbit_cameras.contains(*entity));
+    previous_targets.retain(|entity, _| orbit_cameras.contains(*entity));
+    orbit_ratchets.retain(|entity, _| orbit_cameras.contains(*entity));
+    zoom_velocities.retain(|entity, _| orbit_cameras.contains(*entity));
+    pan_hold_times.retain(|entity, _| orbit_cameras.contains(*entity));
+    pan_eases.retain(|entity, _| orbit_cameras.contains(*entity));
+    target_fovs.retain(|entity, _| orbit_cameras.contains(*entity));
+
+    for (
+        entity,
+        mut pan_orbit,
+        mut transform,
+        mut projection,
+        zoom_levels,
+        focus_bounds,
+        camera_rail,
+    ) in orbit_cameras.iter_mut()
+    {
+        // Closures that apply limits to the yaw, pitch, and zoom values
+        let limits_enabled = pan_orbit.limits_enabled;
+        let apply_zoom_limits = {
+            let bounds_limit = focus_bounds.and_then(|focus_bounds| {
+                focus_bounds
+                    .zoom_limit_factor
+                    .map(|factor| focus_bounds.bounding_radius() * factor)
+            });
+            let zoom_upper_limit = match (pan_orbit.zoom_upper_limit, bounds_limit) {
+                (Some(limit), Some(bounds_limit)) => Some(limit.min(bounds_limit)),
+                (limit, bounds_limit) => limit.or(bounds_limit),
+            };
+            let zoom_lower_limit = pan_orbit.zoom_lower_limit;
+            move |zoom: f32| match limits_enabled {
+                true => zoom.clamp_optional(Some(zoom_lower_limit), zoom_upper_limit),
+                false => zoom,
+            }
+        };
+
+        let apply_yaw_limits = {
+            let yaw_upper_limit = pan_orbit.yaw_upper_limit;
+            let yaw_lower_limit = pan_orbit.yaw_lower_limit;
+            move |yaw: f32| match limits_enabled {
+                true => yaw.clamp_optional(yaw_lower_limit, yaw_upper_limit),
+                false => yaw,
+            }
+        };
+
+        // Also takes the previous pitch, to know which way the pitch is moving through the keep-out
+        let apply_pitch_limits = {
+            let pitch_upper_limit = pan_orbit.pitch_upper_limit;
+            let pitch_lower_limit = pan_orbit.pitch_lower_limit;
+            let pitch_keepout = pan_orbit.pitch_keepout;
+            move |from: f32, pitch: f32| match limits_enabled {
+                true => pitch_keepout
+                    .map_or(pitch, |keepout| {
+                        util::skip_pitch_keepout(from, pitch, keepout)
+                    })
+                    .clamp_optional(pitch_lower_limit, pitch_upper_limit),
+                false => pitch,
+            }
+        };
+
+        if !pan_orbit.initialized {
+            // Calculate yaw, pitch, and radius from the camera's position. If user sets all
+            // these explicitly, this calculation is wasted, but that's okay since it will only run
+            // once on init.
+            // Yaw and pitch are relative to the axis, so work in the axis' coordinate system
+            let local_translation = util::axis_rotation(pan_orbit.axis).inverse()
+                * (transform.translation - pan_orbit.focus);
+            let (yaw, pitch, radius) =
+                util::calculate_from_translation_and_focus(local_translation, Vec3::ZERO);
+            let &mut mut yaw = pan_orbit.yaw.get_or_insert(yaw);
+            let &mut mut pitch = pan_orbit.pitch.get_or_insert(pitch);
+            let &mut mut radius = pan_orbit.radius.get_or_insert(radius);
+
+            // Apply limits. Now the starting angles are known, relative limits can be made
+            // absolute, and the starting angles are within them so they're left as they are.
+            if let Some(relative) = pan_orbit.yaw_limit_relative {
+                pan_orbit.yaw_upper_limit = Some(yaw + relative);
+                pan_orbit.yaw_lower_limit = Some(yaw - relative);
+            } else {
+                yaw = apply_yaw_limits(yaw);
+            }
+            if let Some(relative) = pan_orbit.pitch_limit_relative {
+                pan_orbit.pitch_upper_limit = Some(pitch + relative);
+                pan_orbit.pitch_lower_limit = Some(pitch - relative);
+            } else {
+                pitch = apply_pitch_limits(pitch, pitch);
+            }
+            radius = apply_zoom_limits(radius);
+
+            // Set initial values
+            pan_orbit.yaw = Some(yaw);
+            pan_orbit.pitch = Some(pitch);
+            pan_orbit.radius = Some(radius);
+            pan_orbit.target_yaw = yaw;
+            pan_orbit.target_pitch = pitch;
+            pan_orbit.target_radius = radius;
+            pan_orbit.target_focus = pan_orbit.focus;
+            pan_orbit.target_axis = pan_orbit.axis;
+            if pan_orbit.home_state.is_none() {
+                pan_orbit.home_state = Some(PanOrbitCameraState {
+                    focus: pan_orbit.focus,
+                    yaw,
+                    pitch,
+                    radius,
+                });
+            }
+            // Start somewhere else and let the usual smoothing glide to the configured view
+            if let Some(intro_from) = pan_orbit.intro_from {
+                yaw = intro_from.yaw;
+                pitch = intro_from.pitch;
+                radius = intro_from.radius;
+                pan_orbit.yaw = Some(yaw);
+                pan_orbit.pitch = Some(pitch);
+                pan_orbit.radius = Some(radius);
+                pan_orbit.focus = intro_from.focus;
+            }
+
+            util::update_orbit_transform(
+                yaw,
+                pitch,
+                radius,
+                pan_orbit.focus,
+                pan_orbit.axis,
+                pan_orbit.pivot_forward_offset,
+                &mut transform,
+                &mut projection,
+            );
+            if let Some(point) = pan_orbit.look_at_fixed {
+                util::look_at_point(&mut transform, point);
+            }
+
+            pan_orbit.initialized = true;
+        }
+
+        // Convert the radius if the projection was switched since last frame. Only the kind of
+        // projection and how much it shows are remembered, rather than a copy of it
+        let is_perspective = matches!(*projection, Projection::Perspective(_));
+        let view_height = util::view_height_per_radius(&projection);
+        let previous_projection = last_projections.insert(entity, (is_perspective, view_height));
+        if pan_orbit.projection_switch == ProjectionSwitch::PreserveApparentSize {
+            if let Some(factor) = previous_projection
+                .filter(|&(was_perspective, _)| was_perspective != is_perspective)
+                .map(|(_, previous_height)| previous_height / view_height)
+                .filter(|factor| factor.is_finite() && *factor > 0.0)
+            {
+                pan_orbit.radius = pan_orbit.radius.map(|radius| radius * factor);
+                pan_orbit.target_radius *= factor;
+                pan_orbit.force_update = true;
+            }
+        }
+
+        // 1 - Get Input
+
+        let mut orbit = Vec2::ZERO;
+        let mut pan = Vec2::ZERO;
+        let mut scroll_line = 0.0;
+        let mut scroll_pixel = 0.0;
+        let mut orbit_button_changed = false;
+        let mut six_dof_orbit = Vec2::ZERO;
+        let mut six_dof_pan = Vec2::ZERO;
+        let mut six_dof_roll = 0.0;
+        // Whether the mouse wheel zoomed, as only then is the cursor relevant to the zoom
+        let mut mouse_scrolled = false;
+
+        // The reason we only skip getting input if the camera is inactive/disabled is because
+        // it might still be moving (lerping towards target values) when the user is not
+        // actively controlling it.
+        if pan_orbit.enabled && active_cam.entity == Some(entity) {
+            let zoom_direction = match pan_orbit.reversed_zoom {
+                true => -1.0,
+                false => 1.0,
+            };
+
+            // On hybrid devices, optionally ignore one source while the other is in use
+            let touch_active = pan_orbit.touch_enabled
+                && !matches!(touch_tracker.get_touch_gestures(), TouchGestures::None);
+            let mouse_active = mouse_key_tracker.orbit != Vec2::ZERO
+                || mouse_key_tracker.pan != Vec2::ZERO
+                || mouse_key_tracker.scroll_line != 0.0
+                || mouse_key_tracker.scroll_pixel != 0.0;
+            let use_mouse =
+                !(pan_orbit.input_priority == InputPriority::TouchFirst && touch_active);
+            let use_touch = pan_orbit.touch_enabled
+                && !(pan_orbit.input_priority == InputPriority::MouseFirst && mouse_active);
+
+            if use_mouse {
+                orbit = mouse_key_tracker.orbit * pan_orbit.orbit_sensitivity;
+                pan = mouse_key_tracker.pan * pan_orbit.pan_sensitivity;
+                scroll_line =
+                    mouse_key_tracker.scroll_line * zoom_direction * pan_orbit.zoom_sensitivity;
+                scroll_pixel =
+                    mouse_key_tracker.scroll_pixel * zoom_direction * pan_orbit.zoom_sensitivity;
+                mouse_scrolled = scroll_line != 0.0 || scroll_pixel != 0.0;
+            }
+            orbit_button_changed = mouse_key_tracker.orbit_button_changed;
+            if mouse_key_tracker.double_tapped {
+                match pan_orbit.double_tap.map(|double_tap| double_tap.action) {
+                    Some(DoubleTapAction::ResetView) => pan_orbit.reset_view(),
+                    Some(DoubleTapAction::RecenterFocus) => pan_orbit.recenter_focus(),
+                    Some(DoubleTapAction::FocusCursor) => {
+                        if let Some(ndc) = active_cam.cursor_ndc() {
+                            let radius = pan_orbit.radius.unwrap_or(pan_orbit.target_radius);
+                            let offset = util::focus_plane_offset(&projection, ndc, radius);
+                            pan_orbit.target_focus =
+                                pan_orbit.focus + pan_orbit.rotation() * offset.extend(0.0);
+                        }
+                    }
+                    None => {}
+                }
+            }
+
+            // Zoom continuously while a zoom key is held, at a frame rate independent speed
+            let key_zoom_in = pan_orbit
+                .key_zoom_in
+                .is_some_and(|key| key_input.pressed(key));
+            let key_zoom_out = pan_orbit
+                .key_zoom_out
+                .is_some_and(|key| key_input.pressed(key));
+            let key_zoom = match (key_zoom_in, key_zoom_out) {
+                (true, false) => 1.0,
+                (false, true) => -1.0,
+                _ => 0.0,
+            };
+            scroll_line +=
+                key_zoom * pan_orbit.key_zoom_speed * frame_dt * pan_orbit.zoom_sensitivity;
+
+            if use_touch {
+                let touch_controls = touch_tracker.controls().unwrap_or(pan_orbit.touch_controls);
+                let pinch_zoom = |two_finger_gestures: TwoFingerGestures| {
+                    if touch_tracker.pinch_time() < pan_orbit.touch_pinch_delay {
+                        0.0
+                    } else {
+                        two_finger_gestures.pinch * 0.015
+                    }
+                };
+                let (touch_orbit, touch_pan, touch_zoom_pixel) = match touch_controls {
+                    TouchControls::OneFingerOrbit => match touch_tracker.get_touch_gestures() {
+                        TouchGestures::None => (Vec2::ZERO, Vec2::ZERO, 0.0),
+                        TouchGestures::OneFinger(one_finger_gestures) => {
+                            (one_finger_gestures.motion, Vec2::ZERO, 0.0)
+                        }
+                        TouchGestures::TwoFinger(two_finger_gestures) => (
+                            Vec2::ZERO,
+                            two_finger_gestures.motion,
+                            pinch_zoom(two_finger_gestures),
+                        ),
+                    },
+                    TouchControls::TwoFingerOrbit => match touch_tracker.get_touch_gestures() {
+                        TouchGestures::None => (Vec2::ZERO, Vec2::ZERO, 0.0),
+                        TouchGestures::OneFinger(one_finger_gestures) => {
+                            (Vec2::ZERO, one_finger_gestures.motion, 0.0)
+                        }
+                        TouchGestures::TwoFinger(two_finger_gestures) => (
+                            two_finger_gestures.motion,
+                            Vec2::ZERO,
+                            pinch_zoom(two_finger_gestures),
+                        ),
+                    },
+                };
+
+                orbit += touch_orbit
+                    * pan_orbit
+                        .touch_orbit_sensitivity
+                        .unwrap_or(pan_orbit.orbit_sensitivity);
+                pan += touch_pan
+                    * pan_orbit
+                        .touch_pan_sensitivity
+                        .unwrap_or(pan_orbit.pan_sensitivity);
+                scroll_pixel += touch_zoom_pixel * zoom_direction * pan_orbit.zoom_sensitivity;
+            }
+
+            if let Some((translation, rotation)) = six_dof {
+                // Convert to the units used by `orbit_by_ndc` and `pan_by_ndc`
+                six_dof_orbit =
+                    Vec2::new(-rotation.y / TAU, rotation.x / PI) * pan_orbit.orbit_sensitivity;
+                six_dof_roll = rotation.z * pan_orbit.orbit_sensitivity;
+                six_dof_pan = Vec2::new(-translation.x, translation.y) * pan_orbit.pan_sensitivity;
+                scroll_line -= translation.z * zoom_direction * pan_orbit.zoom_sensitivity;
+            }
+        }
+
+        if let Some(input) = camera_input
+            .filter(|input| pan_orbit.enabled && input.camera.or(active_cam.entity) == Some(entity))
+        {
+            let zoom_direction = match pan_orbit.reversed_zoom {
+                true => -1.0,
+                false => 1.0,
+            };
+            orbit += input.orbit * pan_orbit.orbit_sensitivity;
+            pan += input.pan * pan_orbit.pan_sensitivity;
+            scroll_line += input.zoom * zoom_direction * pan_orbit.zoom_sensitivity;
+
+            // The joystick axes are velocities, so scale them by the frame time, and convert to
+            // the units used by `orbit_by_ndc`
+            let joystick_orbit = input.orbit_joystick
+                * pan_orbit.joystick_orbit_speed
+                * pan_orbit.orbit_sensitivity
+                * frame_dt;
+            six_dof_orbit += joystick_orbit / Vec2::new(TAU, PI);
+            scroll_line += input.zoom_axis
+                * pan_orbit.joystick_zoom_speed
+                * frame_dt
+                * zoom_direction
+                * pan_orbit.zoom_sensitivity;
+        }
+
+        // Keep pixel-based zooming going after the input stops, e.g. after a trackpad flick
+        if pan_orbit.zoom_momentum > 0.0 && !disable_smoothing {
+            let velocity = zoom_velocities.entry(entity).or_default();
+            if scroll_pixel != 0.0 {
+                if frame_dt > 0.0 {
+                    *velocity = scroll_pixel / frame_dt;
+                }
+            } else if scroll_line != 0.0 {
+                *velocity = 0.0;
+            } else if *velocity != 0.0 {
+                *velocity *= 1.0 - util::lerp_factor(pan_orbit.zoom_momentum, frame_dt);
+                if velocity.abs() < ZOOM_MOMENTUM_MIN_VELOCITY {
+                    *velocity = 0.0;
+                }
+                scroll_pixel = *velocity * frame_dt;
+            }
+        } else {
+            zoom_velocities.remove(&entity);
+        }
+
+        // Ramp up continuous panning the longer it's held
+        if pan_orbit.pan_acceleration > 0.0 && six_dof_pan != Vec2::ZERO {
+            let held = pan_hold_times.entry(entity).or_default();
+            *held += frame_dt;
+            six_dof_pan *= 1.0 + pan_orbit.pan_acceleration * held.powi(2);
+        } else {
+            pan_hold_times.remove(&entity);
+        }
+
+        // 2 - Process input into target yaw/pitch, or focus, radius
+
+        let target_pitch_before = pan_orbit.target_pitch;
+        let target_focus_before = pan_orbit.target_focus;
+
+        if pan_orbit.globe_mode {
+            // Start from the current yaw/pitch when globe mode is first enabled
+            if let (None, Some(yaw), Some(pitch)) =
+                (pan_orbit.globe_rotation, pan_orbit.yaw, pan_orbit.pitch)
+            {
+                pan_orbit.globe_rotation = Some(util::rotation_from_yaw_pitch(yaw, pitch));
+                pan_orbit.target_globe_rotation = Some(util::rotation_from_yaw_pitch(
+                    pan_orbit.target_yaw,
+                    pan_orbit.target_pitch,
+                ));
+            }
+        } else if pan_orbit.globe_rotation.is_some() {
+            pan_orbit.globe_rotation = None;
+            pan_orbit.target_globe_rotation = None;
+        }
+
+        if orbit_button_changed {
+            // Only check for upside down when orbiting started or ended this frame,
+            // so we don't reverse the yaw direction while the user is still dragging
+            let wrapped_pitch = (pan_orbit.target_pitch % TAU).abs();
+            pan_orbit.is_upside_down = wrapped_pitch > TAU / 4.0 && wrapped_pitch < 3.0 * TAU / 4.0;
+        }
+
+        // Move the focus to the point under the cursor, keeping the camera in place
+        if let (
+            true,
+            Some(point),
+            Some(yaw),
+            Some(pitch),
+            Some(radius),
+            Projection::Perspective(_),
+        ) = (
+            pan_orbit.orbit_around_cursor && orbit.length_squared() > 0.0,
+            cursor_pivot
+                .as_ref()
+                .and_then(|cursor_pivot| cursor_pivot.point),
+            pan_orbit.yaw,
+            pan_orbit.pitch,
+            pan_orbit.radius,
+            &*projection,
+        ) {
+            let eye = pan_orbit.eye();
+            let offset = eye - point - pan_orbit.pivot_offset();
+            let direction = util::axis_rotation(pan_orbit.axis).inverse() * offset;
+            if (point - eye).dot(pan_orbit.focus - eye) > 0.0 && direction.length_squared() > 0.0 {
+                let (new_yaw, new_pitch) = util::yaw_pitch_from_direction(direction);
+                let new_yaw = util::wrap_angle_near(new_yaw, yaw);
+                let new_radius = offset.length();
+                // Shift the targets by the same amount, so any smoothing in progress continues
+                let focus_shift = point - pan_orbit.focus;
+                pan_orbit.target_focus += focus_shift;
+                pan_orbit.focus = point;
+                pan_orbit.target_radius += new_radius - radius;
+                pan_orbit.radius = Some(new_radius);
+                pan_orbit.target_yaw += new_yaw - yaw;
+                pan_orbit.yaw = Some(new_yaw);
+                pan_orbit.target_pitch += new_pitch - pitch;
+                pan_orbit.pitch = Some(new_pitch);
+                if pan_orbit.globe_rotation.is_some() {
+                    pan_orbit.globe_rotation =
+                        Some(util::rotation_from_yaw_pitch(new_yaw, new_pitch));
+                    pan_orbit.target_globe_rotation = Some(util::rotation_from_yaw_pitch(
+                        pan_orbit.target_yaw,
+                        pan_orbit.target_pitch,
+                    ));
+                }
+            }
+        }
+
+        let mut has_moved = false;
+        let orbit_before = Vec2::new(pan_orbit.target_yaw, pan_orbit.target_pitch);
         if orbit.length_squared() > 0.0 {
             // Use window size for rotation otherwise the sensitivity
             // is far too high for small viewports
-            if let Some(win_size) = active_cam.window_size {
-                let delta_x = {
-                    let delta = orbit.x / win_size.x * PI * 2.0;
-                    if pan_orbit.is_upside_down {
-                        -delta
+            let win_size = active_cam.window_size.or_else(|| {
+                warn_once!(
+                    "ActiveCameraData::window_size is not set, falling back to \
+                    PanOrbitCamera::fallback_viewport_size for orbit"
+                );
+                pan_orbit.fallback_viewport_size
+            });
+            if let Some(win_size) = win_size {
+                if let (true, Some(radius)) =
+                    (pan_orbit.sensitivity_scales_with_zoom, pan_orbit.radius)
+                {
+                    orbit *= util::zoom_sensitivity_scale(
+                        radius,
+                        pan_orbit.sensitivity_zoom_reference,
+                        pan_orbit.sensitivity_zoom_exponent,
+                    );
+                }
+                let win_size = match pan_orbit.orbit_uniform_scale {
+                    // Horizontal orbit is a full turn per unit, so scale x by twice the height
+                    true => Vec2::new(win_size.y * 2.0, win_size.y),
+                    false => win_size,
+                };
+                pan_orbit.orbit_by_ndc(orbit / win_size);
+                has_moved = true;
+            }
+        }
+        if pan.length_squared() > 0.0 {
+            // Make panning distance independent of resolution and FOV,
+            let vp_size = active_cam.viewport_size.or_else(|| {
+                warn_once!(
+                    "ActiveCameraData::viewport_size is not set, falling back to \
+                    PanOrbitCamera::fallback_viewport_size for pan"
+                );
+                pan_orbit.fallback_viewport_size
+            });
+            if let Some(vp_size) = vp_size {
+                let pan_direction = match pan_orbit.pan_grab_mode {
+                    true => 1.0,
+                    false => -1.0,
+                };
+                pan_orbit.pan_by_ndc(pan * pan_direction / vp_size, &projection);
+                has_moved = true;
+            }
+        }
+        if six_dof_orbit.length_squared() > 0.0 {
+            pan_orbit.orbit_by_ndc(six_dof_orbit);
+            has_moved = true;
+        }
+        if let (Some(target_rotation), true) =
+            (pan_orbit.target_globe_rotation, six_dof_roll != 0.0)
+        {
+            // Only globe mode can roll, as yaw and pitch alone always keep the horizon level
+            pan_orbit.target_globe_rotation =
+                Some(target_rotation * Quat::from_rotation_z(six_dof_roll));
+            has_moved = true;
+        }
+        let orbit_after = Vec2::new(pan_orbit.target_yaw, pan_orbit.target_pitch);
+        if let (Some(step), false, true) = (
+            pan_orbit.orbit_ratchet,
+            pan_orbit.globe_mode,
+            orbit_after != orbit_before,
+        ) {
+            let ratchet = orbit_ratchets.entry(entity).or_insert(OrbitRatchet {
+                input: orbit_before,
+                target: orbit_before,
+            });
+            // Start from the current target if it has been changed some other way, e.g. by limits
+            if ratchet.target != orbit_before {
+                ratchet.input = orbit_before;
+                ratchet.target = orbit_before;
+            }
+            let input = ratchet.input + orbit_after - orbit_before;
+            ratchet.target = Vec2::new(
+                util::ratchet(ratchet.input.x, input.x, step, ratchet.target.x),
+                util::ratchet(ratchet.input.y, input.y, step, ratchet.target.y),
+            );
+            ratchet.input = input;
+            pan_orbit.target_yaw = ratchet.target.x;
+            pan_orbit.target_pitch = ratchet.target.y;
+        }
+        if six_dof_pan.length_squared() > 0.0 {
+            pan_orbit.pan_by_ndc(six_dof_pan, &projection);
+            has_moved = true;
+        }
+        if !matches!(
+            (pan_orbit.zoom_mode, &*projection),
+            (ZoomMode::Fov { .. }, Projection::Perspective(_))
+        ) {
+            // Forget any zoom in progress if the zoom mode or projection has changed
+            target_fovs.remove(&entity);
+        }
+        if let (ZoomMode::Fov { min_fov, max_fov }, Projection::Perspective(perspective), true) = (
+            pan_orbit.zoom_mode,
+            &*projection,
+            (scroll_line + scroll_pixel).abs() > 0.0,
+        ) {
+            // Change the field of view instead of the radius, so the camera stays where it is
+            let target_fov = target_fovs.entry(entity).or_insert(perspective.fov);
+            let delta = -(scroll_line + scroll_pixel) * *target_fov * 0.2;
+            *target_fov = (*target_fov + delta).max(min_fov).min(max_fov);
+        } else if (scroll_line + scroll_pixel).abs() > 0.0 {
+            let radius_before = pan_orbit.radius;
+            let target_radius_before = pan_orbit.target_radius;
+            // The current radius before zoom limits are applied, so eye zoom can ignore them
+            let mut unclamped_radius = pan_orbit.radius;
+            if let Some(mut zoom_levels) = zoom_levels {
+                // Step through the levels one line at a time, accumulating pixel-based scrolling
+                // until it adds up to a whole line
+                zoom_levels.pending_steps += scroll_line + scroll_pixel;
+                let steps = zoom_levels.pending_steps.trunc();
+                zoom_levels.pending_steps -= steps;
+                if steps != 0.0 {
+                    pan_orbit.target_radius = util::step_zoom_level(
+                        &zoom_levels.levels,
+                        pan_orbit.target_radius,
+                        steps as i32,
+                    );
+                }
+            } else if let ZoomScaling::Logarithmic { factor } = pan_orbit.zoom_scaling {
+                // Scale the target value geometrically, one step per line
+                let line_scale = factor.powf(scroll_line);
+                let pixel_scale = factor.powf(scroll_pixel);
+                pan_orbit.target_radius *= line_scale * pixel_scale;
+
+                // If it is pixel-based scrolling, apply it directly to the current value
+                unclamped_radius = pan_orbit.radius.map(|value| value * pixel_scale);
+                pan_orbit.radius = unclamped_radius.map(apply_zoom_limits);
+            } else {
+                // Calculate the impact of scrolling on the reference value
+                let step = match pan_orbit.zoom_scaling {
+                    ZoomScaling::Linear { step } => step,
+                    _ => pan_orbit.target_radius * 0.2,
+                };
+                let line_delta = -scroll_line * step;
+                let pixel_delta = -scroll_pixel * step;
+
+                // Update the target value
+                pan_orbit.target_radius += line_delta + pixel_delta;
+
+                // If it is pixel-based scrolling, add it directly to the current value
+                unclamped_radius = pan_orbit.radius.map(|value| value + pixel_delta);
+                pan_orbit.radius = unclamped_radius.map(apply_zoom_limits);
+            }
+
+            if pan_orbit.zoom_pivot == ZoomPivot::Eye
+                && matches!(*projection, Projection::Perspective(_))
+            {
+                // Move the focus by however much the radius would have changed instead
+                let forward = pan_orbit.rotation() * Vec3::NEG_Z;
+                let target_delta = pan_orbit.target_radius - target_radius_before;
+                pan_orbit.target_focus -= forward * target_delta;
+                if let (Some(radius), Some(before)) = (unclamped_radius, radius_before) {
+                    pan_orbit.focus -= forward * (radius - before);
+                }
+                pan_orbit.radius = radius_before;
+                pan_orbit.target_radius = target_radius_before;
+            }
+            if let (ZoomPivot::Cursor, true, Some(ndc)) = (
+                pan_orbit.zoom_pivot,
+                mouse_scrolled,
+                active_cam.cursor_ndc(),
+            ) {
+                // Shrink or grow the cursor's offset from the focus along with the radius, so the
+                // point under the cursor stays put. Limits are applied first, so that the focus
+                // doesn't keep moving once the radius has stopped changing.
+                let rotation = pan_orbit.rotation();
+                let target_radius = apply_zoom_limits(pan_orbit.target_radius);
+                let target_offset =
+                    util::focus_plane_offset(&projection, ndc, target_radius_before);
+                pan_orbit.target_focus += rotation
+                    * target_offset.extend(0.0)
+                    * (1.0 - target_radius / target_radius_before);
+                // Pixel-based scrolling also changes the current radius directly
+                if let (Some(radius), Some(before)) = (pan_orbit.radius, radius_before) {
+                    let offset = util::focus_plane_offset(&projection, ndc, before);
+                    pan_orbit.focus += rotation * offset.extend(0.0) * (1.0 - radius / before);
+                }
+            }
+
+            has_moved = true;
+        }
+
+        // 3 - Apply constraints
+
+        if !pan_orbit.globe_mode {
+            pan_orbit.target_yaw = apply_yaw_limits(pan_orbit.target_yaw);
+            pan_orbit.target_pitch =
+                apply_pitch_limits(target_pitch_before, pan_orbit.target_pitch);
+            if let (Some((cone_axis, half_angle)), true) = (pan_orbit.orbit_cone, limits_enabled) {
+                // The view direction is from the camera towards the focus
+                let axis_rotation = util::axis_rotation(pan_orbit.axis);
+                let view_dir = -(axis_rotation
+                    * util::rotation_from_yaw_pitch(pan_orbit.target_yaw, pan_orbit.target_pitch)
+                    * Vec3::Z);
+                let clamped = util::clamp_to_cone(view_dir, cone_axis, half_angle);
+                if clamped != view_dir {
+                    let (yaw, pitch) =
+                        util::yaw_pitch_from_direction(axis_rotation.inverse() * -clamped);
+                    pan_orbit.target_yaw = util::wrap_angle_near(yaw, pan_orbit.target_yaw);
+                    pan_orbit.target_pitch = pitch;
+                }
+            }
+
+            if !pan_orbit.allow_upside_down {
+                pan_orbit.target_pitch = pan_orbit.target_pitch.clamp(-PI / 2.0, PI / 2.0);
+            }
+        } else if let (Some(max_roll), Some(target_rotation), true) = (
+            pan_orbit.max_roll,
+            pan_orbit.target_globe_rotation,
+            limits_enabled,
+        ) {
+            pan_orbit.target_globe_rotation = Some(util::clamp_roll(target_rotation, max_roll));
+        }
+        pan_orbit.target_radius = apply_zoom_limits(pan_orbit.target_radius);
+        if let (Some(focus_bounds), true) = (focus_bounds, limits_enabled) {
+            pan_orbit.target_focus = focus_bounds.closest_point(pan_orbit.target_focus);
+        }
+        if let Some(height) = pan_orbit.lock_focus_height {
+            let up = pan_orbit.axis[1];
+            let target_focus = pan_orbit.target_focus;
+            pan_orbit.target_focus = target_focus + up * (height - target_focus.dot(up));
+        }
+        if let (Some(camera_rail), true) = (camera_rail, limits_enabled) {
+            // Move the focus with the eye, so the camera keeps its angle and distance
+            let eye = pan_orbit.target_transform(&projection).0.translation;
+            pan_orbit.target_focus += camera_rail.closest_point(eye) - eye;
+        }
+        if let (true, Some(_), true) = (
+            pan_orbit.keep_focus_onscreen,
+            pan_orbit.look_at_fixed,
+            limits_enabled,
+        ) {
+            if !pan_orbit.is_target_focus_onscreen(&projection) {
+                // Find how far the focus can move from where it was before this frame while
+                // staying on-screen, provided it was on-screen to begin with
+                let target_focus = pan_orbit.target_focus;
+                let (mut onscreen, mut offscreen) = (0.0, 1.0);
+                pan_orbit.target_focus = target_focus_before;
+                if pan_orbit.is_target_focus_onscreen(&projection) {
+                    for _ in 0..KEEP_FOCUS_ONSCREEN_ITERATIONS {
+                        let t = (onscreen + offscreen) / 2.0;
+                        pan_orbit.target_focus = target_focus_before.lerp(target_focus, t);
+                        if pan_orbit.is_target_focus_onscreen(&projection) {
+                            onscreen = t;
+                        } else {
+                            offscreen = t;
+                        }
+                    }
+                    pan_orbit.target_focus = target_focus_before.lerp(target_focus, onscreen);
+                } else {
+                    pan_orbit.target_focus = target_focus;
+                }
+            }
+        }
+
+        // 4 - Update the camera's transform based on current values
+
+        // Large time steps, e.g. when the window regains focus after being throttled, would
+        // otherwise make the camera jump straight to its target
+        let dt = pan_orbit
+            .max_smoothing_dt
+            .map_or(frame_dt, |max_dt| frame_dt.min(max_dt));
+        let targets = Targets {
+            orbit: match pan_orbit.target_globe_rotation {
+                Some(rotation) => OrbitTarget::Globe(rotation),
+                None => OrbitTarget::YawPitch(pan_orbit.target_yaw, pan_orbit.target_pitch),
+            },
+            focus: pan_orbit.target_focus,
+            radius: pan_orbit.target_radius,
+        };
+        let (orbit_smoothness, pan_smoothness, zoom_smoothness) = match pan_orbit.transition {
+            _ if disable_smoothing => (0.0, 0.0, 0.0),
+            Some(transition) => (
+                transition.smoothness,
+                transition.smoothness,
+                transition.smoothness,
+            ),
+            None => {
+                // Use the "in" smoothness while the target is changing, and "out" once it stops
+                let previous = previous_targets.get(&entity);
+                let choose = |changed: bool,
+                              smoothness: f32,
+                              smoothness_in: Option<f32>,
+                              smoothness_out: Option<f32>| {
+                    match changed {
+                        true => smoothness_in,
+                        false => smoothness_out,
+                    }
+                    .unwrap_or(smoothness)
+                };
+                (
+                    choose(
+                        previous.is_none_or(|previous| previous.orbit != targets.orbit),
+                        pan_orbit.orbit_smoothness,
+                        pan_orbit.orbit_smoothness_in,
+                        pan_orbit.orbit_smoothness_out,
+                    ),
+                    choose(
+                        previous.is_none_or(|previous| previous.focus != targets.focus),
+                        pan_orbit.pan_smoothness,
+                        pan_orbit.pan_smoothness_in,
+                        pan_orbit.pan_smoothness_out,
+                    ),
+                    choose(
+                        previous.is_none_or(|previous| previous.radius != targets.radius),
+                        pan_orbit.zoom_smoothness,
+                        pan_orbit.zoom_smoothness_in,
+                        pan_orbit.zoom_smoothness_out,
+                    ),
+                )
+            }
+        };
+        previous_targets.insert(entity, targets);
+
+        if pan_orbit.axis != pan_orbit.target_axis {
+            let axis_rotation = util::axis_rotation(pan_orbit.axis);
+            let target_axis_rotation = util::axis_rotation(pan_orbit.target_axis);
+            let new_axis_rotation = util::slerp_and_snap_quat(
+                axis_rotation,
+                target_axis_rotation,
+                orbit_smoothness,
+                dt,
+            );
+            pan_orbit.reorient_axis(axis_rotation, new_axis_rotation);
+            if new_axis_rotation == target_axis_rotation {
+                // Avoid floating point error preventing the axis from ever reaching the target
+                pan_orbit.axis = pan_orbit.target_axis;
+            }
+            has_moved = true;
+        }
+
+        // How fast the camera is orbiting and zooming, for `dynamic_fov`
+        let mut speed = 0.0;
+
+        if let (Some(yaw), Some(pitch), Some(radius)) =
+            (pan_orbit.yaw, pan_orbit.pitch, pan_orbit.radius)
+        {
+            if has_moved
+                // For smoothed values, we must check whether current value is different from target
+                // value. If we only checked whether the values were non-zero this frame, then
+                // the camera would instantly stop moving as soon as you stopped moving it, instead
+                // of smoothly stopping
+                || pan_orbit.target_yaw != yaw
+                || pan_orbit.target_pitch != pitch
+                || pan_orbit.target_radius != radius
+                || pan_orbit.target_focus != pan_orbit.focus
+                || pan_orbit.target_globe_rotation != pan_orbit.globe_rotation
+                || pan_orbit.force_update
+            {
+                // Interpolate towards the target values
+                let distance_threshold = util::EPSILON * pan_orbit.world_scale;
+                let new_radius = util::lerp_and_snap_f32(
+                    radius,
+                    pan_orbit.target_radius,
+                    zoom_smoothness,
+                    dt,
+                    distance_threshold,
+                );
+                let mut new_focus = match pan_orbit.pan_smoothing {
+                    PanSmoothing::Timed { duration }
+                        if pan_orbit.transition.is_none() && !disable_smoothing =>
+                    {
+                        let ease = pan_eases.entry(entity).or_insert(PanEase {
+                            from: pan_orbit.focus,
+                            to: pan_orbit.focus,
+                            elapsed: 0.0,
+                        });
+                        if ease.to != pan_orbit.target_focus {
+                            *ease = PanEase {
+                                from: pan_orbit.focus,
+                                to: pan_orbit.target_focus,
+                                elapsed: 0.0,
+                            };
+                        }
+                        ease.elapsed += dt;
+                        let t = match duration > 0.0 {
+                            true => (ease.elapsed / duration).min(1.0),
+                            false => 1.0,
+                        };
+                        match t < 1.0 {
+                            true => ease.from.lerp(ease.to, 1.0 - (1.0 - t).powi(3)),
+                            false => ease.to,
+                        }
+                    }
+                    _ => {
+                        pan_eases.remove(&entity);
+                        util::lerp_and_snap_vec3(
+                            pan_orbit.focus,
+                            pan_orbit.target_focus,
+                            pan_smoothness,
+                            dt,
+                            distance_threshold,
+                        )
+                    }
+                };
+                if let (Some(max_pan_speed), false) = (pan_orbit.max_pan_speed, disable_smoothing) {
+                    new_focus = pan_orbit.focus
+                        + (new_focus - pan_orbit.focus).clamp_length_max(max_pan_speed * dt);
+                }
+
+                let eased_yaw = util::lerp_and_snap_f32(
+                    yaw,
+                    pan_orbit.target_yaw,
+                    orbit_smoothness,
+                    dt,
+                    util::EPSILON,
+                );
+                let mut eased_pitch = util::lerp_and_snap_f32(
+                    pitch,
+                    pan_orbit.target_pitch,
+                    orbit_smoothness,
+                    dt,
+                    util::EPSILON,
+                );
+                if let (Some(keepout), true) = (pan_orbit.pitch_keepout, limits_enabled) {
+                    // Jump across the band when easing reaches it, rather than passing through
+                    eased_pitch = util::skip_pitch_keepout(pitch, eased_pitch, keepout);
+                }
+
+                let rotation_before = transform.rotation;
+                let (new_yaw, new_pitch) = if let (Some(rotation), Some(target_rotation)) =
+                    (pan_orbit.globe_rotation, pan_orbit.target_globe_rotation)
+                {
+                    let new_rotation =
+                        util::slerp_and_snap_quat(rotation, target_rotation, orbit_smoothness, dt);
+
+                    util::update_orbit_transform_from_rotation(
+                        util::axis_rotation(pan_orbit.axis) * new_rotation,
+                        new_radius,
+                        new_focus,
+                        pan_orbit.pivot_offset(),
+                        &mut transform,
+                        &mut projection,
+                    );
+
+                    pan_orbit.globe_rotation = Some(new_rotation);
+                    if pan_orbit.accumulate_yaw_pitch {
+                        // Yaw/pitch just add up the orbit input, so ease them like the rotation
+                        (eased_yaw, eased_pitch)
                     } else {
-                        delta
+                        // Keep yaw/pitch in sync so they're still meaningful in globe mode
+                        let (new_yaw, new_pitch) = util::yaw_pitch_from_rotation(new_rotation);
+                        pan_orbit.target_yaw = new_yaw;
+                        pan_orbit.target_pitch = new_pitch;
+                        (new_yaw, new_pitch)
                     }
+                } else {
+                    util::update_orbit_transform(
+                        eased_yaw,
+                        eased_pitch,
+                        new_radius,
+                        new_focus,
+                        pan_orbit.axis,
+                        pan_orbit.pivot_forward_offset,
+                        &mut transform,
+                        &mut projection,
+                    );
+                    (eased_yaw, eased_pitch)
+                };
+                if let Some(point) = pan_orbit.look_at_fixed {
+                    util::look_at_point(&mut transform, point);
+                }
+
+                if frame_dt > 0.0 {
+                    let angular = rotation_before.angle_between(transform.rotation);
+                    let radial = (new_radius - radius).abs() / radius.max(util::EPSILON);
+                    speed = (angular + radial) / frame_dt;
+                }
+
+                // Update the current values
+                pan_orbit.yaw = Some(new_yaw);
+                pan_orbit.pitch = Some(new_pitch);
+                pan_orbit.radius = Some(new_radius);
+                pan_orbit.focus = new_focus;
+                pan_orbit.force_update = false;
+            }
+        }
+
+        if let (ZoomMode::Fov { .. }, Projection::Perspective(perspective)) =
+            (pan_orbit.zoom_mode, &*projection)
+        {
+            // Ease the field of view towards the target set when zooming
+            if let Some(&target_fov) = target_fovs.get(&entity) {
+                let fov = util::lerp_and_snap_f32(
+                    perspective.fov,
+                    target_fov,
+                    zoom_smoothness,
+                    dt,
+                    util::EPSILON,
+                );
+                if let Projection::Perspective(ref mut perspective) = *projection {
+                    perspective.fov = fov;
+                }
+                if fov == target_fov {
+                    target_fovs.remove(&entity);
+                }
+            }
+        } else if let (Some(dynamic_fov), Projection::Perspective(perspective)) =
+            (pan_orbit.dynamic_fov, &*projection)
+        {
+            // Widen the field of view with the speed, easing it so it doesn't flicker
+            let t = (speed / dynamic_fov.full_speed.max(f32::EPSILON)).min(1.0);
+            let target_fov = dynamic_fov.base_fov.lerp(dynamic_fov.max_fov, t);
+            let smoothness = match disable_smoothing {
+                true => 0.0,
+                false => dynamic_fov.smoothness,
+            };
+            let fov =
+                util::lerp_and_snap_f32(perspective.fov, target_fov, smoothness, dt, util::EPSILON);
+            if fov != perspective.fov {
+                if let Projection::Perspective(ref mut perspective) = *projection {
+                    perspective.fov = fov;
+                }
+            }
+        }
+
+        // Revert to the usual smoothness once a transition has finished
+        if let Some(mut transition) = pan_orbit.transition {
+            transition.remaining -= frame_dt;
+            pan_orbit.transition = if transition.remaining <= 0.0 || pan_orbit.is_at_target() {
+                None
+            } else {
+                Some(transition)
+            };
+        }
+    }
+}
+
+/// The apps and helpers shared by the tests in this file
+#[cfg(test)]
+mod test_app {
+    use super::*;
+    use bevy::asset::AssetEvent;
+    use bevy::input::touch::TouchInput;
+    use bevy::render::camera::{camera_system, ManualTextureViews};
+    use bevy::window::{WindowCreated, WindowResized, WindowScaleFactorChanged};
+    use std::time::Duration;
+
+    /// An empty app with anything `PanOrbitCameraPlugin` needs from the optional integrations that
+    /// their own plugins would normally provide
+    pub(crate) fn new_app() -> App {
+        #[cfg_attr(not(feature = "bevy_egui"), allow(unused_mut))]
+        let mut app = App::new();
+        // Usually added by `EguiPlugin`, which needs the full render setup
+        #[cfg(feature = "bevy_egui")]
+        app.init_resource::<bevy_egui::EguiUserTextures>();
+        app
+    }
+
+    /// An app that runs just `systems` in `Update`, without the rest of the plugin, along with the
+    /// resources and events that the plugin's systems read
+    pub(crate) fn setup_app<M>(systems: impl IntoSystemConfigs<M>) -> App {
+        let mut app = new_app();
+        app.init_resource::<Time>()
+            .init_resource::<ActiveCameraData>()
+            .init_resource::<ExplicitActiveCamera>()
+            .init_resource::<MouseKeyTracker>()
+            .init_resource::<TouchTracker>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<Touches>()
+            .add_event::<MouseWheel>()
+            .add_event::<TouchInput>()
+            .add_event::<PanOrbitCameraChanged>()
+            .add_systems(Update, systems);
+        #[cfg(feature = "bevy_egui")]
+        app.init_resource::<EguiWantsFocus>();
+        app
+    }
+
+    /// Compute the cameras' viewports in `PreUpdate`, as the render plugin would
+    pub(crate) fn compute_viewports(app: &mut App) {
+        app.init_resource::<Assets<Image>>()
+            .init_resource::<ManualTextureViews>()
+            .add_event::<WindowCreated>()
+            .add_event::<WindowResized>()
+            .add_event::<WindowScaleFactorChanged>()
+            .add_event::<AssetEvent<Image>>()
+            .add_systems(PreUpdate, camera_system::<Projection>);
+    }
+
+    /// Advance time by `dt` seconds and run the app once
+    pub(crate) fn update(app: &mut App, dt: f32) {
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(dt));
+        app.update();
+    }
+
+    pub(crate) fn camera(app: &App, entity: Entity) -> &PanOrbitCamera {
+        app.world().get::<PanOrbitCamera>(entity).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod plugin_tests {
+    use super::test_app::new_app;
+    use super::*;
+    use bevy::input::mouse::MouseMotion;
+    use bevy::input::InputPlugin;
+
+    fn snap_eye_height(mut cameras: Query<&mut Transform, With<PanOrbitCamera>>) {
+        for mut transform in cameras.iter_mut() {
+            transform.translation.y = transform.translation.y.round();
+        }
+    }
+
+    #[test]
+    fn post_process_set_runs_after_camera_update() {
+        let mut app = new_app();
+        app.add_plugins((MinimalPlugins, InputPlugin, PanOrbitCameraPlugin))
+            .add_systems(
+                PostUpdate,
+                snap_eye_height.in_set(PanOrbitCameraPostProcessSet),
+            );
+        let entity = app
+            .world_mut()
+            .spawn((
+                Transform::from_xyz(0.0, 1.3, 5.0),
+                PanOrbitCamera::default(),
+            ))
+            .id();
+        app.update();
+        let transform = app.world().get::<Transform>(entity).unwrap();
+        assert_eq!(transform.translation.y, 1.0);
+        // Doesn't feed back into the camera's values
+        let pan_orbit = app.world().get::<PanOrbitCamera>(entity).unwrap();
+        assert!(pan_orbit.pitch.unwrap() > 0.0);
+        assert_ne!(pan_orbit.radius.unwrap(), 5.0);
+    }
+
+    #[test]
+    fn mouse_orbit_works_without_time() {
+        let mut app = new_app();
+        app.add_plugins((InputPlugin, PanOrbitCameraPlugin));
+        assert!(app.world().get_resource::<Time>().is_none());
+        let entity = app
+            .world_mut()
+            .spawn((
+                Transform::from_xyz(0.0, 0.0, 5.0),
+                PanOrbitCamera::default(),
+            ))
+            .id();
+        app.insert_resource(ActiveCameraData {
+            entity: Some(entity),
+            manual: true,
+            ..default()
+        });
+        app.update();
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        app.world_mut().send_event(MouseMotion {
+            delta: Vec2::new(100.0, 0.0),
+        });
+        app.update();
+        let pan_orbit = app.world().get::<PanOrbitCamera>(entity).unwrap();
+        assert!(pan_orbit.target_yaw < 0.0);
+        assert!(pan_orbit.yaw.unwrap() < 0.0);
+    }
+
+    #[test]
+    fn missing_projection_is_inserted() {
+        let mut app = new_app();
+        app.add_plugins((MinimalPlugins, InputPlugin, PanOrbitCameraPlugin));
+        let entity = app
+            .world_mut()
+            .spawn((
+                Transform::from_xyz(0.0, 0.0, 5.0),
+                PanOrbitCamera::default(),
+            ))
+            .remove::<Projection>()
+            .id();
+        app.update();
+        assert!(matches!(
+            app.world().get::<Projection>(entity),
+            Some(Projection::Perspective(_))
+        ));
+        // The camera is updated in the same frame
+        let pan_orbit = app.world().get::<PanOrbitCamera>(entity).unwrap();
+        assert!(pan_orbit.initialized);
+    }
+
+    #[cfg(feature = "debug")]
+    #[test]
+    fn debug_info_summarises_cameras() {
+        let mut app = new_app();
+        app.add_plugins((MinimalPlugins, InputPlugin, PanOrbitCameraPlugin));
+        let active = app
+            .world_mut()
+            .spawn((
+                Transform::from_xyz(0.0, 0.0, 5.0),
+                PanOrbitCamera::default(),
+            ))
+            .id();
+        app.world_mut().spawn(PanOrbitCamera {
+            enabled: false,
+            ..default()
+        });
+        app.insert_resource(ExplicitActiveCamera::new(active));
+        app.update();
+        let info = app.world().resource::<PanOrbitCameraDebugInfo>();
+        assert_eq!(info.camera_count, 2);
+        assert_eq!(info.enabled_count, 1);
+        assert_eq!(info.active_camera, Some(active));
+        let state = info.active_state.unwrap();
+        assert_eq!(state.focus, Vec3::ZERO);
+        assert!((state.radius - 5.0).abs() < 0.0001);
+    }
+}
+
+#[cfg(test)]
+mod validate_tests {
+    use super::*;
+
+    #[test]
+    fn default_is_valid() {
+        assert_eq!(PanOrbitCamera::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn same_button_and_modifier_is_unreachable() {
+        let pan_orbit = PanOrbitCamera {
+            button_pan: MouseButton::Left,
+            ..default()
+        };
+        assert_eq!(pan_orbit.validate(), Err(ConfigError::UnreachablePan));
+        let pan_orbit = PanOrbitCamera {
+            button_pan: MouseButton::Left,
+            modifier_orbit: Some(KeyCode::ShiftLeft),
+            modifier_pan: Some(KeyCode::ShiftLeft),
+            ..default()
+        };
+        assert_eq!(pan_orbit.validate(), Err(ConfigError::UnreachablePan));
+        // A modifier disambiguates a shared button
+        let pan_orbit = PanOrbitCamera {
+            button_pan: MouseButton::Left,
+            modifier_pan: Some(KeyCode::ShiftLeft),
+            ..default()
+        };
+        assert_eq!(pan_orbit.validate(), Ok(()));
+    }
+
+    #[test]
+    fn same_zoom_keys_conflict() {
+        let pan_orbit = PanOrbitCamera {
+            key_zoom_in: Some(KeyCode::KeyZ),
+            key_zoom_out: Some(KeyCode::KeyZ),
+            ..default()
+        };
+        assert_eq!(pan_orbit.validate(), Err(ConfigError::ConflictingZoomKeys));
+    }
+
+    #[test]
+    fn inverted_limits() {
+        let pan_orbit = PanOrbitCamera {
+            yaw_lower_limit: Some(1.0),
+            yaw_upper_limit: Some(-1.0),
+            ..default()
+        };
+        assert_eq!(pan_orbit.validate(), Err(ConfigError::InvertedYawLimits));
+        let pan_orbit = PanOrbitCamera {
+            pitch_lower_limit: Some(1.0),
+            pitch_upper_limit: Some(-1.0),
+            ..default()
+        };
+        assert_eq!(pan_orbit.validate(), Err(ConfigError::InvertedPitchLimits));
+        let pan_orbit = PanOrbitCamera {
+            zoom_lower_limit: 10.0,
+            zoom_upper_limit: Some(5.0),
+            ..default()
+        };
+        assert_eq!(pan_orbit.validate(), Err(ConfigError::InvertedZoomLimits));
+    }
+
+    #[test]
+    fn zero_sensitivity_is_valid() {
+        let pan_orbit = PanOrbitCamera {
+            orbit_sensitivity: 0.0,
+            pan_sensitivity: 0.0,
+            zoom_sensitivity: 0.0,
+            ..default()
+        };
+        assert_eq!(pan_orbit.validate(), Ok(()));
+    }
+}
+
+#[cfg(test)]
+mod active_pan_orbit_camera_tests {
+    use super::test_app::setup_app;
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct IsActive(bool);
+
+    #[derive(Resource)]
+    struct CameraEntity(Entity);
+
+    fn check_active(
+        active: ActivePanOrbitCamera,
+        camera: Res<CameraEntity>,
+        mut result: ResMut<IsActive>,
+    ) {
+        result.0 = active.is_camera_active(camera.0);
+    }
+
+    #[test]
+    fn reflects_active_camera_data() {
+        let mut app = setup_app(check_active);
+        app.init_resource::<IsActive>();
+        let entity = app.world_mut().spawn_empty().id();
+        app.insert_resource(CameraEntity(entity));
+
+        app.update();
+        assert!(!app.world().resource::<IsActive>().0);
+
+        app.world_mut().resource_mut::<ActiveCameraData>().entity = Some(entity);
+        app.update();
+        assert!(app.world().resource::<IsActive>().0);
+    }
+}
+
+#[cfg(test)]
+mod active_viewport_data_tests {
+    use super::test_app::{compute_viewports, setup_app};
+    use super::*;
+    use bevy::input::mouse::MouseScrollUnit;
+    use bevy::render::camera::Viewport;
+
+    fn press_orbit_button(app: &mut App) {
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+    }
+
+    #[test]
+    fn no_primary_window_is_a_no_op() {
+        let mut app = setup_app(active_viewport_data);
+        app.world_mut().spawn(PanOrbitCamera::default());
+        press_orbit_button(&mut app);
+        app.update();
+        assert_eq!(app.world().resource::<ActiveCameraData>().entity, None);
+    }
+
+    #[test]
+    fn input_window_restricts_activation() {
+        let mut app = setup_app(active_viewport_data);
+        let mut primary_window = Window::default();
+        primary_window.set_cursor_position(Some(Vec2::new(10.0, 10.0)));
+        let primary_window = app.world_mut().spawn((primary_window, PrimaryWindow)).id();
+        let other_window = app.world_mut().spawn(Window::default()).id();
+        let entity = app
+            .world_mut()
+            .spawn(PanOrbitCamera {
+                input_window: Some(other_window),
+                ..default()
+            })
+            .id();
+
+        // The cursor is in the window the camera renders to, but not the input window
+        press_orbit_button(&mut app);
+        app.update();
+        assert_eq!(app.world().resource::<ActiveCameraData>().entity, None);
+
+        let move_cursor = |app: &mut App, window: Entity, position: Option<Vec2>| {
+            app.world_mut()
+                .get_mut::<Window>(window)
+                .unwrap()
+                .set_cursor_position(position);
+        };
+        move_cursor(&mut app, primary_window, None);
+        move_cursor(&mut app, other_window, Some(Vec2::new(10.0, 10.0)));
+        let mut mouse_input = app.world_mut().resource_mut::<ButtonInput<MouseButton>>();
+        mouse_input.reset_all();
+        mouse_input.press(MouseButton::Left);
+        app.update();
+        assert_eq!(
+            app.world().resource::<ActiveCameraData>().entity,
+            Some(entity)
+        );
+    }
+
+    #[test]
+    fn dragging_keeps_camera_active_over_overlapping_viewport() {
+        let mut app = setup_app(active_viewport_data);
+        compute_viewports(&mut app);
+        let window = app
+            .world_mut()
+            .spawn((
+                Window {
+                    resolution: (800.0, 600.0).into(),
+                    ..default()
+                },
+                PrimaryWindow,
+            ))
+            .id();
+        let main = app.world_mut().spawn(PanOrbitCamera::default()).id();
+        let minimap = app
+            .world_mut()
+            .spawn((
+                PanOrbitCamera::default(),
+                Camera {
+                    order: 1,
+                    viewport: Some(Viewport {
+                        physical_position: UVec2::ZERO,
+                        physical_size: UVec2::splat(200),
+                        ..default()
+                    }),
+                    ..default()
+                },
+            ))
+            .id();
+        app.update();
+        let move_cursor = |app: &mut App, position: Vec2| {
+            app.world_mut()
+                .get_mut::<Window>(window)
+                .unwrap()
+                .set_cursor_position(Some(position));
+        };
+        let scroll = |app: &mut App| {
+            app.world_mut().send_event(MouseWheel {
+                unit: MouseScrollUnit::Line,
+                x: 0.0,
+                y: 1.0,
+                window,
+            });
+        };
+
+        // Start dragging in the main viewport
+        move_cursor(&mut app, Vec2::new(500.0, 400.0));
+        press_orbit_button(&mut app);
+        app.update();
+        assert_eq!(
+            app.world().resource::<ActiveCameraData>().entity,
+            Some(main)
+        );
+
+        // Drag into the minimap, and scroll, which would otherwise activate it
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .clear();
+        move_cursor(&mut app, Vec2::new(50.0, 50.0));
+        scroll(&mut app);
+        app.update();
+        assert_eq!(
+            app.world().resource::<ActiveCameraData>().entity,
+            Some(main)
+        );
+
+        // Once the drag ends, the minimap can be activated
+        let mut mouse_input = app.world_mut().resource_mut::<ButtonInput<MouseButton>>();
+        mouse_input.release(MouseButton::Left);
+        mouse_input.clear();
+        scroll(&mut app);
+        app.update();
+        assert_eq!(
+            app.world().resource::<ActiveCameraData>().entity,
+            Some(minimap)
+        );
+    }
+
+    #[test]
+    fn cursor_position_is_relative_to_active_viewport() {
+        let mut app = setup_app((active_viewport_data, active_cursor_position).chain());
+        compute_viewports(&mut app);
+        let window = app
+            .world_mut()
+            .spawn((
+                Window {
+                    resolution: (800.0, 600.0).into(),
+                    ..default()
+                },
+                PrimaryWindow,
+            ))
+            .id();
+        let entity = app
+            .world_mut()
+            .spawn((
+                PanOrbitCamera::default(),
+                Camera {
+                    viewport: Some(Viewport {
+                        physical_position: UVec2::new(100, 50),
+                        physical_size: UVec2::splat(200),
+                        ..default()
+                    }),
+                    ..default()
+                },
+            ))
+            .id();
+        app.update();
+        app.world_mut()
+            .get_mut::<Window>(window)
+            .unwrap()
+            .set_cursor_position(Some(Vec2::new(150.0, 100.0)));
+        press_orbit_button(&mut app);
+        app.update();
+        let active_cam = app.world().resource::<ActiveCameraData>();
+        assert_eq!(active_cam.entity, Some(entity));
+        assert_eq!(active_cam.cursor_position, Some(Vec2::new(50.0, 50.0)));
+
+        // Kept up to date without any other input
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .clear();
+        app.world_mut()
+            .get_mut::<Window>(window)
+            .unwrap()
+            .set_cursor_position(Some(Vec2::new(110.0, 60.0)));
+        app.update();
+        let active_cam = app.world().resource::<ActiveCameraData>();
+        assert_eq!(active_cam.cursor_position, Some(Vec2::new(10.0, 10.0)));
+    }
+
+    #[test]
+    fn multiple_primary_windows_uses_the_first() {
+        let mut app = setup_app(active_viewport_data);
+        compute_viewports(&mut app);
+        let mut first = Window {
+            resolution: (800.0, 600.0).into(),
+            ..default()
+        };
+        first.set_cursor_position(Some(Vec2::new(10.0, 10.0)));
+        app.world_mut().spawn((first, PrimaryWindow));
+        app.world_mut().spawn((
+            Window {
+                resolution: (400.0, 300.0).into(),
+                ..default()
+            },
+            PrimaryWindow,
+        ));
+        let entity = app.world_mut().spawn(PanOrbitCamera::default()).id();
+        app.update();
+        press_orbit_button(&mut app);
+        app.update();
+        let active_cam = app.world().resource::<ActiveCameraData>();
+        assert_eq!(active_cam.entity, Some(entity));
+        assert_eq!(active_cam.window_size, Some(Vec2::new(800.0, 600.0)));
+    }
+}
+
+#[cfg(test)]
+mod touch_tracker_tests {
+    use super::test_app::{compute_viewports, setup_app};
+    use super::*;
+    use bevy::input::touch::{touch_screen_input_system, TouchInput, TouchPhase};
+    use bevy::render::camera::Viewport;
+
+    fn touch(app: &mut App, window: Entity, phase: TouchPhase, id: u64, position: Vec2) {
+        app.world_mut().send_event(TouchInput {
+            phase,
+            position,
+            window,
+            force: None,
+            id,
+        });
+    }
+
+    fn spawn_window(app: &mut App) -> Entity {
+        app.world_mut()
+            .spawn(Window {
+                resolution: (800.0, 600.0).into(),
+                ..default()
+            })
+            .id()
+    }
+
+    #[test]
+    fn touches_outside_active_viewport_are_ignored() {
+        let mut app = setup_app((touch_screen_input_system, touch_tracker).chain());
+        compute_viewports(&mut app);
+        let window = spawn_window(&mut app);
+        app.world_mut().entity_mut(window).insert(PrimaryWindow);
+        let minimap = app
+            .world_mut()
+            .spawn((
+                PanOrbitCamera::default(),
+                Camera {
+                    viewport: Some(Viewport {
+                        physical_position: UVec2::ZERO,
+                        physical_size: UVec2::splat(200),
+                        ..default()
+                    }),
+                    ..default()
+                },
+            ))
+            .id();
+        app.insert_resource(ActiveCameraData {
+            entity: Some(minimap),
+            ..default()
+        });
+        app.update();
+
+        // One finger in the minimap, and one outside it
+        touch(
+            &mut app,
+            window,
+            TouchPhase::Started,
+            0,
+            Vec2::new(50.0, 50.0),
+        );
+        touch(
+            &mut app,
+            window,
+            TouchPhase::Started,
+            1,
+            Vec2::new(500.0, 400.0),
+        );
+        app.update();
+        touch(
+            &mut app,
+            window,
+            TouchPhase::Moved,
+            0,
+            Vec2::new(60.0, 50.0),
+        );
+        touch(
+            &mut app,
+            window,
+            TouchPhase::Moved,
+            1,
+            Vec2::new(510.0, 400.0),
+        );
+        app.update();
+
+        match app.world().resource::<TouchTracker>().get_touch_gestures() {
+            TouchGestures::OneFinger(gestures) => {
+                assert_eq!(gestures.motion, Vec2::new(10.0, 0.0))
+            }
+            gestures => panic!("expected one finger, got {gestures:?}"),
+        }
+    }
+
+    #[test]
+    fn touches_in_other_windows_are_ignored() {
+        let mut app = setup_app((touch_screen_input_system, touch_tracker).chain());
+        compute_viewports(&mut app);
+        let primary_window = spawn_window(&mut app);
+        app.world_mut()
+            .entity_mut(primary_window)
+            .insert(PrimaryWindow);
+        let other_window = spawn_window(&mut app);
+        let entity = app.world_mut().spawn(PanOrbitCamera::default()).id();
+        app.insert_resource(ActiveCameraData {
+            entity: Some(entity),
+            ..default()
+        });
+        app.update();
+
+        // One finger in each window, at the same position
+        let position = Vec2::new(100.0, 100.0);
+        touch(&mut app, primary_window, TouchPhase::Started, 0, position);
+        touch(&mut app, other_window, TouchPhase::Started, 1, position);
+        app.update();
+        touch(
+            &mut app,
+            primary_window,
+            TouchPhase::Moved,
+            0,
+            position + Vec2::X * 10.0,
+        );
+        touch(
+            &mut app,
+            other_window,
+            TouchPhase::Moved,
+            1,
+            position + Vec2::Y * 10.0,
+        );
+        app.update();
+
+        match app.world().resource::<TouchTracker>().get_touch_gestures() {
+            TouchGestures::OneFinger(gestures) => {
+                assert_eq!(gestures.motion, Vec2::new(10.0, 0.0))
+            }
+            gestures => panic!("expected one finger, got {gestures:?}"),
+        }
+
+        // Once lifted, the same id can be reused in the other window
+        touch(&mut app, primary_window, TouchPhase::Ended, 0, position);
+        touch(&mut app, other_window, TouchPhase::Ended, 1, position);
+        app.update();
+        touch(&mut app, other_window, TouchPhase::Started, 0, position);
+        app.update();
+        touch(
+            &mut app,
+            other_window,
+            TouchPhase::Moved,
+            0,
+            position + Vec2::X * 10.0,
+        );
+        app.update();
+        assert!(matches!(
+            app.world().resource::<TouchTracker>().get_touch_gestures(),
+            TouchGestures::None
+        ));
+    }
+
+    #[test]
+    fn pinch_time_starts_at_first_two_finger_gesture() {
+        let mut app = setup_app((touch_screen_input_system, touch_tracker).chain());
+        app.world_mut().remove_resource::<Time>();
+        let touch = |app: &mut App, phase, id, x| {
+            app.world_mut().send_event(TouchInput {
+                phase,
+                position: Vec2::new(x, 0.0),
+                window: Entity::PLACEHOLDER,
+                force: None,
+                id,
+            });
+        };
+        touch(&mut app, TouchPhase::Started, 0, 0.0);
+        app.update();
+        touch(&mut app, TouchPhase::Started, 1, 100.0);
+        app.update();
+        let mut pinch_times = Vec::new();
+        for x in [110.0, 120.0, 130.0] {
+            touch(&mut app, TouchPhase::Moved, 1, x);
+            app.update();
+            let tracker = app.world().resource::<TouchTracker>();
+            assert!(matches!(
+                tracker.get_touch_gestures(),
+                TouchGestures::TwoFinger(_)
+            ));
+            pinch_times.push(tracker.pinch_time());
+        }
+        // Without a `Time` resource, each frame lasts a sixtieth of a second
+        assert_eq!(pinch_times, [0.0, FALLBACK_DT, 2.0 * FALLBACK_DT]);
+
+        // Lifting a finger starts again
+        touch(&mut app, TouchPhase::Ended, 1, 130.0);
+        app.update();
+        touch(&mut app, TouchPhase::Started, 1, 100.0);
+        app.update();
+        touch(&mut app, TouchPhase::Moved, 1, 110.0);
+        app.update();
+        assert_eq!(app.world().resource::<TouchTracker>().pinch_time(), 0.0);
+    }
+}
+
+#[cfg(test)]
+mod pan_orbit_camera_viewports_tests {
+    use super::test_app::{compute_viewports, new_app};
+    use super::*;
+    use bevy::ecs::system::SystemState;
+    use bevy::render::camera::Viewport;
+
+    #[test]
+    fn overlapping_viewports_resolve_by_order() {
+        let mut app = new_app();
+        compute_viewports(&mut app);
+        let window = app
+            .world_mut()
+            .spawn((
+                Window {
+                    resolution: (800.0, 600.0).into(),
+                    ..default()
+                },
+                PrimaryWindow,
+            ))
+            .id();
+        let other_window = app.world_mut().spawn(Window::default()).id();
+        let main = app.world_mut().spawn(PanOrbitCamera::default()).id();
+        let minimap = app
+            .world_mut()
+            .spawn((
+                PanOrbitCamera::default(),
+                Camera {
+                    order: 1,
+                    viewport: Some(Viewport {
+                        physical_position: UVec2::ZERO,
+                        physical_size: UVec2::splat(200),
+                        ..default()
+                    }),
+                    ..default()
+                },
+            ))
+            .id();
+        app.update();
+
+        let mut state = SystemState::<PanOrbitCameraViewports>::new(app.world_mut());
+        let viewports = state.get(app.world());
+        assert_eq!(
+            viewports.camera_at_position(window, Vec2::new(50.0, 50.0)),
+            Some(minimap)
+        );
+        assert_eq!(
+            viewports.camera_at_position(window, Vec2::new(500.0, 400.0)),
+            Some(main)
+        );
+        assert_eq!(
+            viewports.camera_at_position(window, Vec2::new(900.0, 50.0)),
+            None
+        );
+        assert_eq!(
+            viewports.camera_at_position(other_window, Vec2::new(50.0, 50.0)),
+            None
+        );
+    }
+}
+
+#[cfg(test)]
+mod auto_fill_viewport_sizes_tests {
+    use super::test_app::{compute_viewports, new_app, setup_app};
+    use super::*;
+    use bevy::input::InputPlugin;
+    use bevy::render::camera::Viewport;
+
+    #[test]
+    fn fills_sizes_for_manually_chosen_camera() {
+        let mut app = setup_app(auto_fill_viewport_sizes);
+        app.world_mut().spawn((
+            Window {
+                resolution: (800.0, 600.0).into(),
+                ..default()
+            },
+            PrimaryWindow,
+        ));
+        let entity = app.world_mut().spawn(PanOrbitCamera::default()).id();
+        app.insert_resource(ActiveCameraData {
+            entity: Some(entity),
+            viewport_size: Some(Vec2::new(100.0, 100.0)),
+            window_size: None,
+            cursor_position: None,
+            manual: true,
+        });
+        app.update();
+        let active_cam = app.world().resource::<ActiveCameraData>();
+        assert_eq!(active_cam.entity, Some(entity));
+        assert!(active_cam.manual);
+        assert_eq!(active_cam.window_size, Some(Vec2::new(800.0, 600.0)));
+        // The camera hasn't computed its viewport yet, so the user's value is kept
+        assert_eq!(active_cam.viewport_size, Some(Vec2::new(100.0, 100.0)));
+    }
+
+    #[test]
+    fn plugin_fills_sizes_from_camera_viewport() {
+        let mut app = new_app();
+        app.add_plugins((MinimalPlugins, InputPlugin, PanOrbitCameraPlugin));
+        compute_viewports(&mut app);
+        app.world_mut().spawn((
+            Window {
+                resolution: (800.0, 600.0).into(),
+                ..default()
+            },
+            PrimaryWindow,
+        ));
+        let entity = app
+            .world_mut()
+            .spawn((
+                PanOrbitCamera::default(),
+                Camera {
+                    viewport: Some(Viewport {
+                        physical_position: UVec2::ZERO,
+                        physical_size: UVec2::new(200, 100),
+                        ..default()
+                    }),
+                    ..default()
+                },
+            ))
+            .id();
+        app.insert_resource(ActiveCameraData {
+            entity: Some(entity),
+            manual: true,
+            ..default()
+        });
+        app.insert_resource(AutoFillViewportSizes);
+        app.update();
+        let camera = app.world().get::<Camera>(entity).unwrap();
+        assert_eq!(
+            camera.logical_viewport_size(),
+            Some(Vec2::new(200.0, 100.0))
+        );
+        let active_cam = app.world().resource::<ActiveCameraData>();
+        assert!(active_cam.manual);
+        assert_eq!(active_cam.viewport_size, camera.logical_viewport_size());
+        assert_eq!(active_cam.window_size, Some(Vec2::new(800.0, 600.0)));
+    }
+}
+
+#[cfg(test)]
+mod explicit_active_camera_data_tests {
+    use super::test_app::setup_app;
+    use super::*;
+
+    #[test]
+    fn uses_explicit_camera_and_its_window_size() {
+        let mut app = setup_app(explicit_active_camera_data);
+        app.world_mut().spawn((
+            Window {
+                resolution: (800.0, 600.0).into(),
+                ..default()
+            },
+            PrimaryWindow,
+        ));
+        let entity = app.world_mut().spawn(PanOrbitCamera::default()).id();
+        app.update();
+        assert_eq!(app.world().resource::<ActiveCameraData>().entity, None);
+
+        app.world_mut()
+            .resource_mut::<ExplicitActiveCamera>()
+            .set(entity);
+        app.update();
+        let active_cam = app.world().resource::<ActiveCameraData>();
+        assert_eq!(active_cam.entity, Some(entity));
+        assert_eq!(active_cam.window_size, Some(Vec2::new(800.0, 600.0)));
+
+        app.world_mut()
+            .resource_mut::<ExplicitActiveCamera>()
+            .clear();
+        app.update();
+        assert_eq!(app.world().resource::<ActiveCameraData>().entity, None);
+    }
+
+    #[test]
+    fn ignores_entity_without_pan_orbit_camera() {
+        let mut app = setup_app(explicit_active_camera_data);
+        let entity = app.world_mut().spawn_empty().id();
+        app.insert_resource(ExplicitActiveCamera::new(entity));
+        app.update();
+        assert_eq!(app.world().resource::<ActiveCameraData>().entity, None);
+    }
+}
+
+#[cfg(test)]
+mod focus_bounds_tests {
+    use super::*;
+
+    fn cuboid(min: [f32; 3], max: [f32; 3]) -> FocusBoundsShape {
+        FocusBoundsShape::Cuboid {
+            min: Vec3::from(min),
+            max: Vec3::from(max),
+        }
+    }
+
+    #[test]
+    fn single_shape_clamps() {
+        let bounds = FocusBounds::new(FocusBoundsShape::Sphere {
+            center: Vec3::X,
+            radius: 2.0,
+        });
+        assert_eq!(bounds.closest_point(Vec3::ZERO), Vec3::ZERO);
+        assert_eq!(
+            bounds.closest_point(Vec3::new(5.0, 0.0, 0.0)),
+            Vec3::new(3.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn bounding_radius() {
+        let sphere = |x: f32, radius: f32| FocusBoundsShape::Sphere {
+            center: Vec3::new(x, 0.0, 0.0),
+            radius,
+        };
+        assert_eq!(FocusBounds::new(sphere(5.0, 2.0)).bounding_radius(), 2.0);
+        assert_eq!(
+            FocusBounds::new(cuboid([0.0, 0.0, 0.0], [2.0, 2.0, 2.0])).bounding_radius(),
+            3.0_f32.sqrt()
+        );
+        // Spheres from -3 to 5 on the x axis
+        let shapes = [sphere(-2.0, 1.0), sphere(3.0, 2.0)];
+        assert_eq!(FocusBounds::union(shapes).bounding_radius(), 4.0);
+        assert_eq!(FocusBounds::intersection(shapes).bounding_radius(), 1.0);
+        assert_eq!(FocusBounds::union([]).bounding_radius(), 0.0);
+    }
+
+    #[test]
+    fn union_of_cuboids_uses_nearest_shape() {
+        // An L shape
+        let bounds = FocusBounds::union([
+            cuboid([0.0, 0.0, 0.0], [10.0, 1.0, 2.0]),
+            cuboid([0.0, 0.0, 0.0], [2.0, 1.0, 10.0]),
+        ]);
+        // Inside either shape is unchanged
+        assert_eq!(
+            bounds.closest_point(Vec3::new(8.0, 0.5, 1.0)),
+            Vec3::new(8.0, 0.5, 1.0)
+        );
+        assert_eq!(
+            bounds.closest_point(Vec3::new(1.0, 0.5, 8.0)),
+            Vec3::new(1.0, 0.5, 8.0)
+        );
+        // In the corner of the L, moves to the nearer arm
+        assert_eq!(
+            bounds.closest_point(Vec3::new(8.0, 0.5, 3.0)),
+            Vec3::new(8.0, 0.5, 2.0)
+        );
+        assert_eq!(
+            bounds.closest_point(Vec3::new(3.0, 0.5, 8.0)),
+            Vec3::new(2.0, 0.5, 8.0)
+        );
+    }
+
+    #[test]
+    fn intersection_of_sphere_and_cuboid_stays_in_both() {
+        let sphere = FocusBoundsShape::Sphere {
+            center: Vec3::ZERO,
+            radius: 2.0,
+        };
+        let bounds =
+            FocusBounds::intersection([sphere, cuboid([0.0, -5.0, -5.0], [5.0, 5.0, 5.0])]);
+        // Inside both is unchanged
+        assert_eq!(
+            bounds.closest_point(Vec3::new(1.0, 0.0, 0.0)),
+            Vec3::new(1.0, 0.0, 0.0)
+        );
+        // Inside the sphere only
+        assert_eq!(
+            bounds.closest_point(Vec3::new(-1.0, 1.0, 0.0)),
+            Vec3::new(0.0, 1.0, 0.0)
+        );
+        // Outside both
+        for point in [Vec3::new(-3.0, 3.0, 0.0), Vec3::new(4.0, 4.0, 4.0)] {
+            let closest = bounds.closest_point(point);
+            assert!(closest.x >= 0.0);
+            assert!(closest.length() <= 2.0 + 0.0001);
+        }
+    }
+}
+
+#[cfg(test)]
+mod camera_rail_tests {
+    use super::*;
+
+    #[test]
+    fn closest_point_on_polyline() {
+        let rail = CameraRail::new([
+            Vec3::new(0.0, 0.0, 10.0),
+            Vec3::ZERO,
+            Vec3::new(10.0, 0.0, 0.0),
+        ]);
+        // Alongside each segment
+        assert_eq!(
+            rail.closest_point(Vec3::new(-2.0, 1.0, 5.0)),
+            Vec3::new(0.0, 0.0, 5.0)
+        );
+        assert_eq!(
+            rail.closest_point(Vec3::new(5.0, 0.0, 2.0)),
+            Vec3::new(5.0, 0.0, 0.0)
+        );
+        // Past the ends, and outside the corner
+        assert_eq!(
+            rail.closest_point(Vec3::new(0.0, 0.0, 20.0)),
+            Vec3::new(0.0, 0.0, 10.0)
+        );
+        assert_eq!(rail.closest_point(Vec3::new(-1.0, 0.0, -1.0)), Vec3::ZERO);
+    }
+
+    #[test]
+    fn degenerate_rails() {
+        let point = Vec3::new(1.0, 2.0, 3.0);
+        assert_eq!(CameraRail::new([]).closest_point(point), point);
+        assert_eq!(CameraRail::new([Vec3::X]).closest_point(point), Vec3::X);
+        assert_eq!(
+            CameraRail::new([Vec3::X, Vec3::X]).closest_point(point),
+            Vec3::X
+        );
+    }
+}
+
+#[cfg(test)]
+mod follow_targets_tests {
+    use super::test_app::{camera, setup_app, update};
+    use super::*;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn follows_target() {
+        let mut app = setup_app(follow_targets);
+        let target = app
+            .world_mut()
+            .spawn(GlobalTransform::from_xyz(1.0, 2.0, 3.0))
+            .id();
+        let entity = app
+            .world_mut()
+            .spawn(PanOrbitCamera {
+                follow_target: Some(target),
+                ..default()
+            })
+            .id();
+        update(&mut app, 0.1);
+        assert_eq!(camera(&app, entity).target_focus, Vec3::new(1.0, 2.0, 3.0));
+        *app.world_mut().get_mut::<GlobalTransform>(target).unwrap() =
+            GlobalTransform::from_xyz(4.0, 5.0, 6.0);
+        update(&mut app, 0.1);
+        assert_eq!(camera(&app, entity).target_focus, Vec3::new(4.0, 5.0, 6.0));
+    }
+
+    #[test]
+    fn despawned_target_follows_policy() {
+        for policy in [FollowTargetLost::KeepFocus, FollowTargetLost::ClearTarget] {
+            let mut app = setup_app(follow_targets);
+            let target = app
+                .world_mut()
+                .spawn(GlobalTransform::from_xyz(1.0, 2.0, 3.0))
+                .id();
+            let entity = app
+                .world_mut()
+                .spawn(PanOrbitCamera {
+                    follow_target: Some(target),
+                    follow_target_lost: policy,
+                    ..default()
+                })
+                .id();
+            update(&mut app, 0.1);
+            app.world_mut().despawn(target);
+            update(&mut app, 0.1);
+            update(&mut app, 0.1);
+            let pan_orbit = app.world().get::<PanOrbitCamera>(entity).unwrap();
+            assert_eq!(pan_orbit.target_focus, Vec3::new(1.0, 2.0, 3.0));
+            let expected_target = match policy {
+                FollowTargetLost::KeepFocus => Some(target),
+                FollowTargetLost::ClearTarget => None,
+            };
+            assert_eq!(pan_orbit.follow_target, expected_target);
+        }
+    }
+
+    #[test]
+    fn switching_targets_glides_for_the_transition_duration() {
+        let mut app = setup_app(follow_targets);
+        let first = app.world_mut().spawn(GlobalTransform::IDENTITY).id();
+        let second = app
+            .world_mut()
+            .spawn(GlobalTransform::from_xyz(10.0, 0.0, 0.0))
+            .id();
+        let entity = app
+            .world_mut()
+            .spawn(PanOrbitCamera {
+                follow_target: Some(first),
+                follow_transition_duration: 1.0,
+                ..default()
+            })
+            .id();
+        update(&mut app, 0.1);
+        assert_eq!(camera(&app, entity).target_focus, Vec3::ZERO);
+
+        app.world_mut()
+            .get_mut::<PanOrbitCamera>(entity)
+            .unwrap()
+            .follow_target = Some(second);
+        update(&mut app, 0.5);
+        // Halfway through the handoff, and not jumping straight to the new target
+        assert!(approx_eq!(
+            f32,
+            camera(&app, entity).target_focus.x,
+            5.0,
+            epsilon = 0.0001
+        ));
+        update(&mut app, 0.25);
+        assert!(camera(&app, entity).target_focus.x < 10.0);
+        // Arrives exactly when the duration is up, unlike exponential smoothing
+        update(&mut app, 0.25);
+        assert_eq!(camera(&app, entity).target_focus, Vec3::new(10.0, 0.0, 0.0));
+        let pan_orbit = app.world().get::<PanOrbitCamera>(entity).unwrap();
+        assert_eq!(pan_orbit.focus, pan_orbit.target_focus);
+    }
+}
+
+#[cfg(test)]
+mod send_change_events_tests {
+    use super::test_app::setup_app;
+    use super::*;
+
+    fn event_count(app: &mut App) -> usize {
+        app.world_mut()
+            .resource_mut::<Events<PanOrbitCameraChanged>>()
+            .drain()
+            .count()
+    }
+
+    fn set_yaw(app: &mut App, entity: Entity, yaw: f32) {
+        app.world_mut()
+            .get_mut::<PanOrbitCamera>(entity)
+            .unwrap()
+            .yaw = Some(yaw);
+    }
+
+    #[test]
+    fn sends_event_only_when_moved_past_threshold() {
+        let mut app = setup_app(send_change_events);
+        let entity = app
+            .world_mut()
+            .spawn(PanOrbitCamera {
+                yaw: Some(0.0),
+                pitch: Some(0.0),
+                radius: Some(5.0),
+                change_event_angle_threshold: 0.01,
+                ..default()
+            })
+            .id();
+        app.update();
+        assert_eq!(event_count(&mut app), 1);
+
+        // Not moved
+        app.update();
+        assert_eq!(event_count(&mut app), 0);
+
+        // Small smoothing steps accumulate until they pass the threshold
+        set_yaw(&mut app, entity, 0.004);
+        app.update();
+        assert_eq!(event_count(&mut app), 0);
+        set_yaw(&mut app, entity, 0.008);
+        app.update();
+        assert_eq!(event_count(&mut app), 0);
+        set_yaw(&mut app, entity, 0.012);
+        app.update();
+        assert_eq!(event_count(&mut app), 1);
+    }
+
+    #[test]
+    fn sends_final_event_when_settled() {
+        let mut app = setup_app(send_change_events);
+        let entity = app
+            .world_mut()
+            .spawn(PanOrbitCamera {
+                yaw: Some(0.0),
+                pitch: Some(0.0),
+                radius: Some(5.0),
+                target_radius: 5.0,
+                target_yaw: 0.006,
+                change_event_angle_threshold: 0.01,
+                ..default()
+            })
+            .id();
+        app.update();
+        assert_eq!(event_count(&mut app), 1);
+        set_yaw(&mut app, entity, 0.004);
+        app.update();
+        assert_eq!(event_count(&mut app), 0);
+
+        // Reaching the target sends the final state, though it's within the threshold
+        set_yaw(&mut app, entity, 0.006);
+        app.update();
+        let events: Vec<_> = app
+            .world_mut()
+            .resource_mut::<Events<PanOrbitCameraChanged>>()
+            .drain()
+            .collect();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].state.yaw, 0.006);
+        app.update();
+        assert_eq!(event_count(&mut app), 0);
+    }
+
+    #[test]
+    fn angle_and_distance_thresholds_are_separate() {
+        let mut app = setup_app(send_change_events);
+        let entity = app
+            .world_mut()
+            .spawn(PanOrbitCamera {
+                yaw: Some(0.0),
+                pitch: Some(0.0),
+                radius: Some(5.0),
+                target_yaw: 1.0,
+                change_event_angle_threshold: 0.01,
+                change_event_distance_threshold: 0.5,
+                ..default()
+            })
+            .id();
+        app.update();
+        assert_eq!(event_count(&mut app), 1);
+
+        // Moving 0.1 is within the distance threshold, though larger than the angle threshold
+        app.world_mut()
+            .get_mut::<PanOrbitCamera>(entity)
+            .unwrap()
+            .radius = Some(5.1);
+        app.update();
+        assert_eq!(event_count(&mut app), 0);
+        set_yaw(&mut app, entity, 0.02);
+        app.update();
+        assert_eq!(event_count(&mut app), 1);
+    }
+
+    #[test]
+    fn no_event_before_initialization() {
+        let mut app = setup_app(send_change_events);
+        app.world_mut().spawn(PanOrbitCamera::default());
+        app.update();
+        assert_eq!(event_count(&mut app), 0);
+    }
+}
+
+#[cfg(test)]
+mod pan_orbit_camera_state_tests {
+    use super::*;
+    use float_cmp::approx_eq;
+
+    fn state(yaw: f32, radius: f32) -> PanOrbitCameraState {
+        PanOrbitCameraState {
+            focus: Vec3::ZERO,
+            yaw,
+            pitch: 0.0,
+            radius,
+        }
+    }
+
+    #[test]
+    fn lerp_takes_shortest_way_round() {
+        let blended = state(3.0, 1.0).lerp(&state(-3.0, 1.0), 0.5);
+        // Halfway between 3 and TAU - 3, rather than 0
+        assert!(approx_eq!(f32, blended.yaw, PI, epsilon = 0.0001));
+        let blended = state(-3.0, 1.0).lerp(&state(3.0, 1.0), 0.5);
+        assert!(approx_eq!(f32, blended.yaw, -PI, epsilon = 0.0001));
+        // Ends exactly where it should, modulo a full turn
+        let blended = state(3.0, 1.0).lerp(&state(-3.0, 1.0), 1.0);
+        assert!(approx_eq!(f32, blended.yaw, TAU - 3.0, epsilon = 0.0001));
+    }
+
+    #[test]
+    fn lerp_blends_everything_linearly() {
+        let from = PanOrbitCameraState {
+            focus: Vec3::ZERO,
+            yaw: 0.0,
+            pitch: 0.0,
+            radius: 1.0,
+        };
+        let to = PanOrbitCameraState {
+            focus: Vec3::new(2.0, 4.0, 6.0),
+            yaw: 1.0,
+            pitch: 0.5,
+            radius: 3.0,
+        };
+        let blended = from.lerp(&to, 0.5);
+        assert_eq!(blended.focus, Vec3::new(1.0, 2.0, 3.0));
+        assert!(approx_eq!(f32, blended.yaw, 0.5, epsilon = 0.0001));
+        assert_eq!(blended.pitch, 0.25);
+        assert_eq!(blended.radius, 2.0);
+    }
+
+    #[test]
+    fn lerp_log_radius_is_geometric() {
+        let from = state(0.0, 1.0);
+        let to = state(1.0, 100.0);
+        assert!(approx_eq!(
+            f32,
+            from.lerp_log_radius(&to, 0.5).radius,
+            10.0,
+            epsilon = 0.0001
+        ));
+        assert!(approx_eq!(
+            f32,
+            from.lerp_log_radius(&to, 0.25).radius,
+            10.0_f32.sqrt(),
+            epsilon = 0.0001
+        ));
+        // Everything else is the same as `lerp`
+        assert_eq!(from.lerp_log_radius(&to, 0.5).yaw, from.lerp(&to, 0.5).yaw);
+    }
+
+    #[test]
+    fn set_state_skips_smoothing() {
+        let mut pan_orbit = PanOrbitCamera::default();
+        pan_orbit.set_state(state(1.0, 2.0));
+        assert_eq!(pan_orbit.yaw, Some(1.0));
+        assert_eq!(pan_orbit.target_yaw, 1.0);
+        assert_eq!(pan_orbit.radius, Some(2.0));
+        assert_eq!(pan_orbit.target_radius, 2.0);
+        assert!(pan_orbit.force_update);
+    }
+}
+
+#[cfg(test)]
+mod pan_orbit_camera_tests {
+    use super::test_app::{camera, setup_app, update};
+    use super::*;
+    use float_cmp::approx_eq;
+
+    fn spawn_camera(app: &mut App, pan_orbit: PanOrbitCamera) -> Entity {
+        let entity = app.world_mut().spawn(pan_orbit).id();
+        app.world_mut().resource_mut::<ActiveCameraData>().entity = Some(entity);
+        entity
+    }
+
+    fn initial_camera() -> PanOrbitCamera {
+        PanOrbitCamera {
+            yaw: Some(0.0),
+            pitch: Some(0.0),
+            radius: Some(5.0),
+            ..default()
+        }
+    }
+
+    #[test]
+    fn orbit_uses_fallback_size_when_window_size_missing() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(&mut app, initial_camera());
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(100.0, 0.0);
+        update(&mut app, 0.01);
+        assert!(approx_eq!(
+            f32,
+            camera(&app, entity).target_yaw,
+            -100.0 / 1000.0 * TAU
+        ));
+    }
+
+    #[test]
+    fn settling_uses_out_smoothness() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                orbit_smoothness_in: Some(0.5),
+                orbit_smoothness_out: Some(0.9),
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.1);
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(100.0, 0.0);
+        update(&mut app, 0.1);
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::ZERO;
+        let pan_orbit = camera(&app, entity);
+        let target_yaw = pan_orbit.target_yaw;
+        // The target changed this frame, so the "in" smoothness was used
+        let expected = target_yaw * util::lerp_factor(0.5, 0.1);
+        assert!(approx_eq!(f32, pan_orbit.yaw.unwrap(), expected));
+
+        // The target stays put while the camera settles, so the "out" smoothness is used
+        for _ in 0..3 {
+            let yaw = camera(&app, entity).yaw.unwrap();
+            update(&mut app, 0.1);
+            let expected = yaw + (target_yaw - yaw) * util::lerp_factor(0.9, 0.1);
+            assert!(approx_eq!(f32, camera(&app, entity).yaw.unwrap(), expected));
+        }
+    }
+
+    #[test]
+    fn globe_mode_settling_uses_out_smoothness() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                globe_mode: true,
+                orbit_smoothness_in: Some(0.5),
+                orbit_smoothness_out: Some(0.9),
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.1);
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(100.0, 0.0);
+        update(&mut app, 0.1);
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::ZERO;
+        let target_yaw = -100.0 / 1000.0 * TAU;
+        // The target changed this frame, so the "in" smoothness was used
+        let expected = target_yaw * util::lerp_factor(0.5, 0.1);
+        assert!(approx_eq!(
+            f32,
+            camera(&app, entity).yaw.unwrap(),
+            expected,
+            epsilon = 0.0001
+        ));
+
+        // Target yaw follows the eased rotation, but the target rotation stays put while the
+        // camera settles, so the "out" smoothness is used
+        for _ in 0..3 {
+            let yaw = camera(&app, entity).yaw.unwrap();
+            update(&mut app, 0.1);
+            let expected = yaw + (target_yaw - yaw) * util::lerp_factor(0.9, 0.1);
+            assert!(approx_eq!(
+                f32,
+                camera(&app, entity).yaw.unwrap(),
+                expected,
+                epsilon = 0.0001
+            ));
+        }
+    }
+
+    #[test]
+    fn camera_orbit_style_moves_camera_against_drag() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(&mut app, initial_camera());
+        assert_eq!(camera(&app, entity).orbit_style, OrbitStyle::Camera);
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(100.0, 50.0);
+        update(&mut app, 0.01);
+        // Dragging right and down orbits the camera left and up, as it always has
+        let pan_orbit = camera(&app, entity);
+        assert!(approx_eq!(f32, pan_orbit.target_yaw, -100.0 / 1000.0 * TAU));
+        assert!(approx_eq!(f32, pan_orbit.target_pitch, 50.0 / 1000.0 * PI));
+    }
+
+    #[test]
+    fn object_orbit_style_moves_camera_with_drag() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                orbit_style: OrbitStyle::Object,
+                ..initial_camera()
+            },
+        );
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(100.0, 50.0);
+        update(&mut app, 0.01);
+        // The camera moves the other way, so the scene appears to follow the drag
+        let pan_orbit = camera(&app, entity);
+        assert!(approx_eq!(f32, pan_orbit.target_yaw, 100.0 / 1000.0 * TAU));
+        assert!(approx_eq!(f32, pan_orbit.target_pitch, -50.0 / 1000.0 * PI));
+    }
+
+    #[test]
+    fn six_dof_input_is_consumed_by_active_camera() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(&mut app, initial_camera());
+        app.insert_resource(SixDofInput {
+            translation: Vec3::new(0.0, 0.0, 1.0),
+            rotation: Vec3::new(0.1, 0.2, 0.0),
+            rotation_sensitivity: Vec3::new(1.0, 0.5, 1.0),
+            ..default()
+        });
+        update(&mut app, 0.01);
+        let pan_orbit = camera(&app, entity);
+        assert!(approx_eq!(f32, pan_orbit.target_yaw, 0.1));
+        assert!(approx_eq!(f32, pan_orbit.target_pitch, 0.1));
+        // One line of zooming out
+        assert!(approx_eq!(f32, pan_orbit.target_radius, 6.0));
+        let input = app.world().resource::<SixDofInput>();
+        assert_eq!(input.translation, Vec3::ZERO);
+        assert_eq!(input.rotation, Vec3::ZERO);
+    }
+
+    #[test]
+    fn pan_follows_screen_axes_with_look_at_fixed() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                look_at_fixed: Some(Vec3::new(5.0, 0.0, 0.0)),
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.01);
+        let right = app.world().get::<Transform>(entity).unwrap().right();
+        let mut pan_orbit = *camera(&app, entity);
+        pan_orbit.pan_by_ndc(Vec2::new(-0.1, 0.0), &Projection::default());
+        let pan_dir = pan_orbit.target_focus.normalize();
+        assert!(pan_dir.abs_diff_eq(*right, 0.0001));
+    }
+
+    #[test]
+    fn six_dof_input_uses_camera_settings() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                orbit_sensitivity: 2.0,
+                orbit_style: OrbitStyle::Object,
+                reversed_zoom: true,
+                ..initial_camera()
+            },
+        );
+        app.insert_resource(SixDofInput {
+            translation: Vec3::new(0.0, 0.0, 1.0),
+            rotation: Vec3::new(0.1, 0.2, 0.0),
+            ..default()
+        });
+        update(&mut app, 0.01);
+        let pan_orbit = camera(&app, entity);
+        assert!(approx_eq!(f32, pan_orbit.target_yaw, -0.4));
+        assert!(approx_eq!(f32, pan_orbit.target_pitch, -0.2));
+        // One line of zooming in, as the zoom is reversed
+        assert!(approx_eq!(f32, pan_orbit.target_radius, 4.0));
+    }
+
+    #[test]
+    fn six_dof_input_disabled_by_zero_sensitivity() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                orbit_sensitivity: 0.0,
+                pan_sensitivity: 0.0,
+                zoom_sensitivity: 0.0,
+                ..initial_camera()
+            },
+        );
+        app.insert_resource(SixDofInput {
+            translation: Vec3::ONE,
+            rotation: Vec3::ONE,
+            ..default()
+        });
+        update(&mut app, 0.01);
+        let pan_orbit = camera(&app, entity);
+        assert_eq!(pan_orbit.target_yaw, 0.0);
+        assert_eq!(pan_orbit.target_pitch, 0.0);
+        assert_eq!(pan_orbit.target_radius, 5.0);
+        assert_eq!(pan_orbit.target_focus, Vec3::ZERO);
+    }
+
+    #[test]
+    fn six_dof_rotation_z_rolls_in_globe_mode() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                globe_mode: true,
+                max_roll: Some(0.1),
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.01);
+        app.insert_resource(SixDofInput {
+            rotation: Vec3::new(0.0, 0.0, 0.5),
+            ..default()
+        });
+        update(&mut app, 0.01);
+        let target_rotation = camera(&app, entity).target_globe_rotation.unwrap();
+        assert!(target_rotation.abs_diff_eq(Quat::from_rotation_z(0.1), 0.0001));
+    }
+
+    #[test]
+    fn pan_acceleration_speeds_up_held_six_dof_pan() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                pan_acceleration: 1.0,
+                ..initial_camera()
+            },
+        );
+        app.init_resource::<SixDofInput>();
+        // Hold a pan key, which writes the same input every frame
+        let mut movements = Vec::new();
+        for _ in 0..5 {
+            let before = camera(&app, entity).target_focus;
+            app.world_mut().resource_mut::<SixDofInput>().translation = Vec3::new(0.01, 0.0, 0.0);
+            update(&mut app, 0.1);
+            movements.push(camera(&app, entity).target_focus.distance(before));
+        }
+        assert!(movements.windows(2).all(|pair| pair[1] > pair[0]));
+
+        // Releasing the key resets the acceleration
+        update(&mut app, 0.1);
+        let before = camera(&app, entity).target_focus;
+        app.world_mut().resource_mut::<SixDofInput>().translation = Vec3::new(0.01, 0.0, 0.0);
+        update(&mut app, 0.1);
+        let movement = camera(&app, entity).target_focus.distance(before);
+        assert!(approx_eq!(f32, movement, movements[0], epsilon = 0.0001));
+    }
+
+    #[test]
+    fn camera_input_adds_to_mouse_input() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(&mut app, initial_camera());
+        app.insert_resource(CameraInput {
+            orbit: Vec2::new(100.0, 0.0),
+            zoom: 1.0,
+            ..default()
+        });
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(100.0, 0.0);
+        update(&mut app, 0.01);
+        let pan_orbit = camera(&app, entity);
+        assert!(approx_eq!(f32, pan_orbit.target_yaw, -200.0 / 1000.0 * TAU));
+        // One line of zooming in
+        assert!(approx_eq!(f32, pan_orbit.target_radius, 4.0));
+        let input = app.world().resource::<CameraInput>();
+        assert_eq!(input.orbit, Vec2::ZERO);
+        assert_eq!(input.zoom, 0.0);
+    }
+
+    #[test]
+    fn joystick_orbits_and_zooms_continuously() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(&mut app, initial_camera());
+        let mut input = CameraInput::default();
+        input.set_orbit_joystick(Vec2::new(2.0, 0.0));
+        input.set_zoom_axis(0.5);
+        assert_eq!(input.orbit_joystick, Vec2::X);
+        app.insert_resource(input);
+        // The joystick is held, so keeps orbiting at the same speed whatever the frame rate
+        for _ in 0..10 {
+            update(&mut app, 0.05);
+        }
+        for _ in 0..5 {
+            update(&mut app, 0.1);
+        }
+        let pan_orbit = camera(&app, entity);
+        assert!(approx_eq!(f32, pan_orbit.target_yaw, -PI, epsilon = 0.0001));
+        // Two and a half lines per second for one second, applied a bit at a time
+        assert!(pan_orbit.target_radius > 2.9 && pan_orbit.target_radius < 3.1);
+        assert_eq!(
+            app.world().resource::<CameraInput>().orbit_joystick,
+            Vec2::X
+        );
+
+        // Releasing it stops the camera
+        let mut input = app.world_mut().resource_mut::<CameraInput>();
+        input.set_orbit_joystick(Vec2::ZERO);
+        input.set_zoom_axis(0.0);
+        let before = *camera(&app, entity);
+        update(&mut app, 0.1);
+        let pan_orbit = camera(&app, entity);
+        assert_eq!(pan_orbit.target_yaw, before.target_yaw);
+        assert_eq!(pan_orbit.target_radius, before.target_radius);
+    }
+
+    #[test]
+    fn dynamic_fov_widens_while_moving_and_returns_when_idle() {
+        let mut app = setup_app(pan_orbit_camera);
+        let config = DynamicFovConfig::default();
+        let entity = app
+            .world_mut()
+            .spawn((
+                PanOrbitCamera {
+                    dynamic_fov: Some(config),
+                    orbit_smoothness: 0.0,
+                    ..initial_camera()
+                },
+                Projection::Perspective(PerspectiveProjection {
+                    fov: config.base_fov,
+                    ..default()
+                }),
+            ))
+            .id();
+        app.world_mut().resource_mut::<ActiveCameraData>().entity = Some(entity);
+        let fov = |app: &App| match app.world().get::<Projection>(entity).unwrap() {
+            Projection::Perspective(perspective) => perspective.fov,
+            _ => unreachable!(),
+        };
+
+        for _ in 0..10 {
+            app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(50.0, 0.0);
+            update(&mut app, 0.02);
+        }
+        assert!(fov(&app) > config.base_fov);
+        assert!(fov(&app) <= config.max_fov);
+
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::ZERO;
+        for _ in 0..100 {
+            update(&mut app, 0.02);
+        }
+        assert_eq!(fov(&app), config.base_fov);
+    }
+
+    #[test]
+    fn fov_zoom_mode_changes_fov_instead_of_radius() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = app
+            .world_mut()
+            .spawn((
+                PanOrbitCamera {
+                    zoom_mode: ZoomMode::Fov {
+                        min_fov: 0.5,
+                        max_fov: PI / 4.0,
+                    },
+                    zoom_smoothness: 0.0,
+                    ..initial_camera()
+                },
+                Projection::Perspective(PerspectiveProjection {
+                    fov: PI / 4.0,
+                    ..default()
+                }),
+            ))
+            .id();
+        app.world_mut().resource_mut::<ActiveCameraData>().entity = Some(entity);
+        let fov = |app: &App| match app.world().get::<Projection>(entity).unwrap() {
+            Projection::Perspective(perspective) => perspective.fov,
+            _ => unreachable!(),
+        };
+        let scroll = |app: &mut App, lines: f32| {
+            app.world_mut()
+                .resource_mut::<MouseKeyTracker>()
+                .scroll_line = lines;
+            update(app, 0.01);
+        };
+
+        scroll(&mut app, 1.0);
+        assert!(approx_eq!(f32, fov(&app), PI / 4.0 * 0.8));
+        let pan_orbit = camera(&app, entity);
+        assert_eq!(pan_orbit.radius, Some(5.0));
+        assert_eq!(pan_orbit.target_radius, 5.0);
+
+        // Limited in both directions
+        for _ in 0..10 {
+            scroll(&mut app, 1.0);
+        }
+        assert_eq!(fov(&app), 0.5);
+        for _ in 0..10 {
+            scroll(&mut app, -1.0);
+        }
+        assert_eq!(fov(&app), PI / 4.0);
+        assert_eq!(camera(&app, entity).radius, Some(5.0));
+    }
+
+    #[test]
+    fn look_at_fixed_keeps_point_centered() {
+        let mut app = setup_app(pan_orbit_camera);
+        let point = Vec3::new(0.5, 0.2, 0.0);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                look_at_fixed: Some(point),
+                orbit_smoothness: 0.0,
+                pan_smoothness: 0.0,
+                ..initial_camera()
+            },
+        );
+        let assert_centered = |app: &App| {
+            let transform = app.world().get::<Transform>(entity).unwrap();
+            let direction = (point - transform.translation).normalize();
+            assert!(direction.abs_diff_eq(*transform.forward(), 0.0001));
+        };
+        update(&mut app, 0.01);
+        assert_centered(&app);
+
+        app.world_mut().resource_mut::<MouseKeyTracker>().pan = Vec2::new(100.0, 50.0);
+        update(&mut app, 0.01);
+        app.world_mut().resource_mut::<MouseKeyTracker>().pan = Vec2::ZERO;
+        // Panning moves the center of the orbit, but not the view
+        assert_ne!(camera(&app, entity).focus, Vec3::ZERO);
+        assert_centered(&app);
+
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(100.0, 50.0);
+        update(&mut app, 0.01);
+        assert_centered(&app);
+    }
+
+    #[test]
+    fn keep_focus_onscreen_stops_panning_at_edge_of_view() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                look_at_fixed: Some(Vec3::ZERO),
+                keep_focus_onscreen: true,
+                orbit_smoothness: 0.0,
+                pan_smoothness: 0.0,
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.01);
+        // Far enough to take the focus well off the side of the view, and behind the camera
+        for _ in 0..10 {
+            app.world_mut().resource_mut::<MouseKeyTracker>().pan = Vec2::new(300.0, 0.0);
+            update(&mut app, 0.01);
+        }
+        let pan_orbit = *camera(&app, entity);
+        assert!(pan_orbit.focus.x < -1.0);
+        let projection = app.world().get::<Projection>(entity).unwrap();
+        let position = pan_orbit
+            .focus_screen_position(Vec2::new(1000.0, 1000.0), projection)
+            .unwrap();
+        // The camera moved left while still looking at the origin, so the focus is held just inside
+        // the margin at the left edge
+        assert!(approx_eq!(f32, position.x, 100.0, epsilon = 1.0));
+        assert!(approx_eq!(f32, position.y, 500.0, epsilon = 0.01));
+
+        // Panning back into the view isn't limited
+        app.world_mut().resource_mut::<MouseKeyTracker>().pan = Vec2::new(-100.0, 0.0);
+        update(&mut app, 0.01);
+        assert!(camera(&app, entity).focus.x > pan_orbit.focus.x);
+    }
+
+    #[test]
+    fn smooths_with_fallback_dt_without_time() {
+        let mut app = setup_app(pan_orbit_camera);
+        app.world_mut().remove_resource::<Time>();
+        let entity = spawn_camera(&mut app, initial_camera());
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(100.0, 0.0);
+        app.update();
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::ZERO;
+        let target_yaw = camera(&app, entity).target_yaw;
+        assert!(target_yaw < 0.0);
+
+        // Eases towards the target over a few frames, rather than jumping or never moving
+        let yaw = camera(&app, entity).yaw.unwrap();
+        assert!(yaw < 0.0 && yaw > target_yaw);
+        for _ in 0..300 {
+            app.update();
+        }
+        assert_eq!(camera(&app, entity).yaw, Some(target_yaw));
+    }
+
+    #[test]
+    fn orbit_ratchet_only_moves_at_detents() {
+        let mut app = setup_app(pan_orbit_camera);
+        let step = PI / 12.0;
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                orbit_ratchet: Some(step),
+                orbit_smoothness: 0.0,
+                ..initial_camera()
+            },
+        );
+        // Drag slowly, a fraction of a step each frame
+        for frame in 1..=10 {
+            app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(10.0, 0.0);
+            update(&mut app, 0.01);
+            let input = frame as f32 * 10.0 / 1000.0 * TAU;
+            let expected = -(input / step).floor() * step;
+            let pan_orbit = camera(&app, entity);
+            assert!(approx_eq!(f32, pan_orbit.target_yaw, expected));
+            assert!(approx_eq!(f32, pan_orbit.yaw.unwrap(), expected));
+        }
+
+        // Dragging back moves to the last detent the input crosses
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(-5.0, 0.0);
+        update(&mut app, 0.01);
+        assert!(approx_eq!(
+            f32,
+            camera(&app, entity).target_yaw,
+            -2.0 * step
+        ));
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(-60.0, 0.0);
+        update(&mut app, 0.01);
+        assert!(approx_eq!(f32, camera(&app, entity).target_yaw, -step));
+    }
+
+    #[test]
+    fn camera_input_can_target_inactive_camera() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = app.world_mut().spawn(initial_camera()).id();
+        app.insert_resource(CameraInput {
+            orbit: Vec2::new(100.0, 0.0),
+            ..default()
+        });
+        update(&mut app, 0.01);
+        assert_eq!(camera(&app, entity).target_yaw, 0.0);
+
+        app.insert_resource(CameraInput {
+            orbit: Vec2::new(100.0, 0.0),
+            camera: Some(entity),
+            ..default()
+        });
+        update(&mut app, 0.01);
+        assert!(approx_eq!(
+            f32,
+            camera(&app, entity).target_yaw,
+            -100.0 / 1000.0 * TAU
+        ));
+        // The camera is kept, so it doesn't have to be set every frame
+        assert_eq!(app.world().resource::<CameraInput>().camera, Some(entity));
+    }
+
+    #[test]
+    fn focus_distance_matches_transform() {
+        for projection in [
+            Projection::Perspective(default()),
+            Projection::Orthographic(OrthographicProjection::default_3d()),
+        ] {
+            let mut app = setup_app(pan_orbit_camera);
+            let entity = app
+                .world_mut()
+                .spawn((
+                    PanOrbitCamera {
+                        focus: Vec3::new(1.0, 2.0, 3.0),
+                        ..initial_camera()
+                    },
+                    projection.clone(),
+                ))
+                .id();
+            update(&mut app, 0.01);
+            let pan_orbit = camera(&app, entity);
+            let translation = app.world().get::<Transform>(entity).unwrap().translation;
+            assert!(approx_eq!(
+                f32,
+                pan_orbit.focus_distance(&projection).unwrap(),
+                translation.distance(pan_orbit.focus),
+                epsilon = 0.001
+            ));
+        }
+    }
+
+    #[test]
+    fn radius_snaps_to_nearby_target() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                radius: Some(5.0),
+                ..PanOrbitCamera::with_world_scale(1.0)
+            },
+        );
+        update(&mut app, 0.01);
+        app.world_mut()
+            .get_mut::<PanOrbitCamera>(entity)
+            .unwrap()
+            .target_radius = 5.0005;
+        update(&mut app, 0.01);
+        // Within 0.001 of the target snaps at the default world scale of 1
+        assert_eq!(camera(&app, entity).radius, Some(5.0005));
+    }
+
+    #[test]
+    fn small_world_scale_keeps_smoothing_near_target() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                radius: Some(0.005),
+                ..PanOrbitCamera::with_world_scale(0.001)
+            },
+        );
+        update(&mut app, 0.01);
+        app.world_mut()
+            .get_mut::<PanOrbitCamera>(entity)
+            .unwrap()
+            .target_radius = 0.0055;
+        update(&mut app, 0.01);
+        // The same relative distance as 5.0 to 5.5, which is too far to snap
+        let radius = camera(&app, entity).radius.unwrap();
+        assert!(radius > 0.005 && radius < 0.0055);
+    }
+
+    #[test]
+    fn large_world_scale_snaps_from_further_away() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                radius: Some(5000.0),
+                ..PanOrbitCamera::with_world_scale(1000.0)
+            },
+        );
+        update(&mut app, 0.01);
+        app.world_mut()
+            .get_mut::<PanOrbitCamera>(entity)
+            .unwrap()
+            .target_radius = 5000.5;
+        update(&mut app, 0.01);
+        // Within 1.0 of the target snaps at a world scale of 1000
+        assert_eq!(camera(&app, entity).radius, Some(5000.5));
+    }
+
+    #[test]
+    fn disable_smoothing_reaches_target_every_frame() {
+        let mut app = setup_app(pan_orbit_camera);
+        app.insert_resource(DisableSmoothing(true));
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                max_pan_speed: Some(1.0),
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.0);
+        for dt in [0.0, 0.016, 0.5] {
+            app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(10.0, 20.0);
+            app.world_mut()
+                .resource_mut::<MouseKeyTracker>()
+                .scroll_line = 1.0;
+            app.world_mut()
+                .get_mut::<PanOrbitCamera>(entity)
+                .unwrap()
+                .target_focus += Vec3::splat(10.0);
+            update(&mut app, dt);
+            let pan_orbit = camera(&app, entity);
+            assert_eq!(pan_orbit.yaw, Some(pan_orbit.target_yaw));
+            assert_eq!(pan_orbit.pitch, Some(pan_orbit.target_pitch));
+            assert_eq!(pan_orbit.radius, Some(pan_orbit.target_radius));
+            assert_eq!(pan_orbit.focus, pan_orbit.target_focus);
+        }
+    }
+
+    #[test]
+    fn max_pan_speed_caps_focus_movement() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                max_pan_speed: Some(10.0),
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.1);
+        app.world_mut()
+            .get_mut::<PanOrbitCamera>(entity)
+            .unwrap()
+            .target_focus = Vec3::new(1000.0, 0.0, 0.0);
+        update(&mut app, 0.1);
+        assert!(approx_eq!(f32, camera(&app, entity).focus.x, 1.0));
+        update(&mut app, 0.1);
+        assert!(approx_eq!(f32, camera(&app, entity).focus.x, 2.0));
+    }
+
+    #[test]
+    fn set_view_takes_shortest_way_round() {
+        let mut pan_orbit = PanOrbitCamera {
+            target_yaw: TAU + 0.2,
+            ..default()
+        };
+        pan_orbit.set_view(CardinalView::Right);
+        assert!(approx_eq!(f32, pan_orbit.target_yaw, TAU + PI / 2.0));
+        pan_orbit.set_view(CardinalView::Left);
+        assert!(approx_eq!(f32, pan_orbit.target_yaw, TAU - PI / 2.0));
+    }
+
+    #[test]
+    fn set_view_is_relative_to_axis() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                axis: [Vec3::X, Vec3::Z, -Vec3::Y],
+                orbit_smoothness: 0.0,
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.01);
+        app.world_mut()
+            .get_mut::<PanOrbitCamera>(entity)
+            .unwrap()
+            .set_view(CardinalView::Top);
+        update(&mut app, 0.01);
+        let translation = app.world().get::<Transform>(entity).unwrap().translation;
+        assert!(translation.abs_diff_eq(Vec3::new(0.0, 0.0, 5.0), 0.0001));
+    }
+
+    #[test]
+    fn camera_rail_keeps_eye_on_rail() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                pan_smoothness: 0.0,
+                orbit_smoothness: 0.0,
+                zoom_smoothness: 0.0,
+                ..initial_camera()
+            },
+        );
+        app.world_mut().entity_mut(entity).insert(CameraRail::new([
+            Vec3::new(0.0, 0.0, 5.0),
+            Vec3::new(0.0, 0.0, -5.0),
+        ]));
+        update(&mut app, 0.01);
+        let eye = |app: &App| app.world().get::<Transform>(entity).unwrap().translation;
+        assert!(eye(&app).abs_diff_eq(Vec3::new(0.0, 0.0, 5.0), 0.0001));
+
+        // Panning sideways would move the eye off the rail, so it stays put
+        app.world_mut().resource_mut::<MouseKeyTracker>().pan = Vec2::new(100.0, 0.0);
+        update(&mut app, 0.01);
+        assert!(eye(&app).abs_diff_eq(Vec3::new(0.0, 0.0, 5.0), 0.0001));
+        app.world_mut().resource_mut::<MouseKeyTracker>().pan = Vec2::ZERO;
+
+        // Looking around keeps the eye on the rail, with the same angle and distance
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(100.0, 0.0);
+        update(&mut app, 0.01);
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::ZERO;
+        let eye_position = eye(&app);
+        assert!(eye_position.x.abs() < 0.0001 && eye_position.y.abs() < 0.0001);
+        let pan_orbit = camera(&app, entity);
+        assert_ne!(pan_orbit.yaw, Some(0.0));
+        assert_eq!(pan_orbit.radius, Some(5.0));
+        assert!(approx_eq!(
+            f32,
+            eye_position.distance(pan_orbit.focus),
+            5.0,
+            epsilon = 0.0001
+        ));
+
+        // Zooming slides along the rail
+        app.world_mut()
+            .resource_mut::<MouseKeyTracker>()
+            .scroll_line = 1.0;
+        update(&mut app, 0.01);
+        let pan_orbit = camera(&app, entity);
+        let eye_position = eye(&app);
+        assert!(eye_position.x.abs() < 0.0001 && eye_position.y.abs() < 0.0001);
+        assert_eq!(pan_orbit.radius, Some(4.0));
+        assert!(eye_position.z < 5.0);
+    }
+
+    #[test]
+    fn view_matrix_round_trips() {
+        let y_up_view = Transform::from_xyz(3.0, 4.0, -2.0)
+            .looking_at(Vec3::new(1.0, 0.5, 0.0), Vec3::Y)
+            .compute_matrix()
+            .inverse();
+        assert!(PanOrbitCamera::from_view_matrix(y_up_view, 2.0)
+            .view_matrix()
+            .abs_diff_eq(y_up_view, 0.0001));
+        // With a pivot offset, the camera orbits the pivot, but still looks at the focus
+        let mut pan_orbit = PanOrbitCamera {
+            pivot_forward_offset: 1.5,
+            ..default()
+        };
+        pan_orbit.set_from_view_matrix(y_up_view, 2.0);
+        let transform = Transform::from_matrix(pan_orbit.view_matrix().inverse());
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(3.0, 4.0, -2.0), 0.0001));
+        assert!(transform.forward().abs_diff_eq(
+            (Vec3::new(1.0, 0.5, 0.0) - transform.translation).normalize(),
+            0.0001
+        ));
+        assert!(approx_eq!(
+            f32,
+            pan_orbit.focus_distance(&Projection::default()).unwrap(),
+            2.0,
+            epsilon = 0.0001
+        ));
+
+        let transform =
+            Transform::from_xyz(3.0, 4.0, -2.0).looking_at(Vec3::new(1.0, 0.5, 0.0), Vec3::Z);
+        let view = transform.compute_matrix().inverse();
+        // Looking at the target with Z up has no roll relative to a Z up axis
+        let mut pan_orbit = PanOrbitCamera {
+            axis: [Vec3::X, Vec3::Z, Vec3::NEG_Y],
+            ..default()
+        };
+        pan_orbit.set_from_view_matrix(view, 2.0);
+        assert_eq!(pan_orbit.radius, Some(2.0));
+        assert!(pan_orbit
+            .focus
+            .abs_diff_eq(transform.translation + transform.forward() * 2.0, 0.0001));
+        assert!(pan_orbit.view_matrix().abs_diff_eq(view, 0.0001));
+
+        // The camera ends up with the same transform
+        let mut app = setup_app(pan_orbit_camera);
+        pan_orbit.orbit_smoothness = 0.0;
+        let entity = spawn_camera(&mut app, pan_orbit);
+        update(&mut app, 0.01);
+        let settled = app.world().get::<Transform>(entity).unwrap();
+        assert!(settled.compute_matrix().inverse().abs_diff_eq(view, 0.0001));
+    }
+
+    #[test]
+    fn view_matrix_keeps_roll_in_globe_mode() {
+        let transform =
+            Transform::from_xyz(0.0, 0.0, 5.0).with_rotation(Quat::from_rotation_z(0.3));
+        let view = transform.compute_matrix().inverse();
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                globe_mode: true,
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.01);
+        let mut pan_orbit = *camera(&app, entity);
+        pan_orbit.set_from_view_matrix(view, 5.0);
+        assert!(pan_orbit.view_matrix().abs_diff_eq(view, 0.0001));
+        // Without globe mode, the roll is discarded
+        let pan_orbit = PanOrbitCamera::from_view_matrix(view, 5.0);
+        assert_eq!(pan_orbit.yaw, Some(0.0));
+        assert_eq!(pan_orbit.pitch, Some(0.0));
+        assert!(pan_orbit.view_matrix().abs_diff_eq(
+            Transform::from_xyz(0.0, 0.0, 5.0)
+                .compute_matrix()
+                .inverse(),
+            0.0001
+        ));
+    }
+
+    #[test]
+    fn aim_at_keeps_radius() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(&mut app, initial_camera());
+        update(&mut app, 0.01);
+
+        // The point is as far from the camera as the focus was, so the camera doesn't move
+        let point = Vec3::new(5.0, 0.0, 5.0);
+        app.world_mut()
+            .get_mut::<PanOrbitCamera>(entity)
+            .unwrap()
+            .aim_at(point);
+        let pan_orbit = camera(&app, entity);
+        assert_eq!(pan_orbit.target_focus, point);
+        assert_eq!(pan_orbit.target_radius, 5.0);
+        assert!(approx_eq!(
+            f32,
+            pan_orbit.target_yaw,
+            -PI / 2.0,
+            epsilon = 0.0001
+        ));
+        assert!(approx_eq!(
+            f32,
+            pan_orbit.target_pitch,
+            0.0,
+            epsilon = 0.0001
+        ));
+        let (transform, _) = pan_orbit.target_transform(&Projection::default());
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(0.0, 0.0, 5.0), 0.0001));
+
+        // A closer point pushes the camera back, to keep the distance
+        let point = Vec3::new(0.0, 3.0, 0.0);
+        app.world_mut()
+            .get_mut::<PanOrbitCamera>(entity)
+            .unwrap()
+            .aim_at(point);
+        for _ in 0..100 {
+            update(&mut app, 0.1);
+        }
+        let pan_orbit = camera(&app, entity);
+        assert_eq!(pan_orbit.radius, Some(5.0));
+        let translation = app.world().get::<Transform>(entity).unwrap().translation;
+        assert!(approx_eq!(
+            f32,
+            translation.distance(point),
+            5.0,
+            epsilon = 0.001
+        ));
+        // Still on the line from the point through where the camera was
+        let dir = (translation - point).normalize();
+        assert!(dir.abs_diff_eq((Vec3::new(0.0, 0.0, 5.0) - point).normalize(), 0.001));
+    }
+
+    #[test]
+    fn view_from_looks_along_each_face() {
+        let views = [
+            CardinalView::Front,
+            CardinalView::Back,
+            CardinalView::Right,
+            CardinalView::Left,
+            CardinalView::Top,
+            CardinalView::Bottom,
+        ];
+        // Z up, to check that the direction is in the scene's space rather than relative to axis
+        for axis in [[Vec3::X, Vec3::Y, Vec3::Z], [Vec3::X, Vec3::Z, Vec3::NEG_Y]] {
+            for view in views {
+                let mut pan_orbit = PanOrbitCamera {
+                    axis,
+                    target_radius: 5.0,
+                    ..default()
                 };
-                let delta_y = orbit.y / win_size.y * PI;
-                pan_orbit.target_yaw -= delta_x;
-                pan_orbit.target_pitch += delta_y;
+                pan_orbit.view_from(view.direction());
+                let (transform, _) = pan_orbit.target_transform(&Projection::default());
+                assert!(
+                    transform
+                        .translation
+                        .abs_diff_eq(view.direction() * 5.0, 0.0001),
+                    "{view:?} with {axis:?}"
+                );
+                assert!(transform.forward().abs_diff_eq(-view.direction(), 0.0001));
+            }
+        }
+    }
+
+    #[test]
+    fn view_from_corner() {
+        let mut pan_orbit = PanOrbitCamera {
+            target_radius: 5.0,
+            ..default()
+        };
+        pan_orbit.view_from(Vec3::ONE);
+        let (transform, _) = pan_orbit.target_transform(&Projection::default());
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::ONE.normalize() * 5.0, 0.0001));
+    }
+
+    #[test]
+    fn set_focus_world_with_parent() {
+        let mut app = setup_app(pan_orbit_camera);
+        let parent = app
+            .world_mut()
+            .spawn(GlobalTransform::from(
+                Transform::from_xyz(10.0, 0.0, 0.0).with_scale(Vec3::splat(2.0)),
+            ))
+            .id();
+        let entity = spawn_camera(&mut app, initial_camera());
+        app.world_mut().entity_mut(parent).add_child(entity);
+        update(&mut app, 0.01);
+
+        let parent_global = *app.world().get::<GlobalTransform>(parent).unwrap();
+        app.world_mut()
+            .get_mut::<PanOrbitCamera>(entity)
+            .unwrap()
+            .set_focus_world(Vec3::new(12.0, 4.0, 0.0), &parent_global);
+        assert_eq!(camera(&app, entity).target_focus, Vec3::new(1.0, 2.0, 0.0));
+        assert_eq!(
+            parent_global.transform_point(camera(&app, entity).target_focus),
+            Vec3::new(12.0, 4.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn double_tap_resets_view() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                double_tap: Some(DoubleTapControls::default()),
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.01);
+        {
+            let mut pan_orbit = app.world_mut().get_mut::<PanOrbitCamera>(entity).unwrap();
+            pan_orbit.target_yaw = 1.0;
+            pan_orbit.target_radius = 10.0;
+            pan_orbit.target_focus = Vec3::ONE;
+        }
+        app.world_mut()
+            .resource_mut::<MouseKeyTracker>()
+            .double_tapped = true;
+        update(&mut app, 0.01);
+        let pan_orbit = camera(&app, entity);
+        assert_eq!(pan_orbit.target_yaw, 0.0);
+        assert_eq!(pan_orbit.target_radius, 5.0);
+        assert_eq!(pan_orbit.target_focus, Vec3::ZERO);
+    }
+
+    #[test]
+    fn reset_view_restores_orientation_in_globe_mode() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                globe_mode: true,
+                orbit_smoothness: 0.0,
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.01);
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(100.0, 50.0);
+        update(&mut app, 0.01);
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::ZERO;
+        let transform = app.world().get::<Transform>(entity).unwrap();
+        assert!(!transform.translation.abs_diff_eq(Vec3::Z * 5.0, 0.1));
+
+        app.world_mut()
+            .get_mut::<PanOrbitCamera>(entity)
+            .unwrap()
+            .reset_view();
+        update(&mut app, 0.01);
+        let transform = app.world().get::<Transform>(entity).unwrap();
+        assert!(transform.translation.abs_diff_eq(Vec3::Z * 5.0, 0.0001));
+        assert!(transform.rotation.abs_diff_eq(Quat::IDENTITY, 0.0001));
+    }
+
+    #[test]
+    fn double_tap_focus_cursor_moves_focus_to_cursor() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                double_tap: Some(DoubleTapControls {
+                    action: DoubleTapAction::FocusCursor,
+                    ..default()
+                }),
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.01);
+        // Halfway to the top right corner
+        app.insert_resource(ActiveCameraData {
+            entity: Some(entity),
+            viewport_size: Some(Vec2::new(100.0, 100.0)),
+            cursor_position: Some(Vec2::new(75.0, 25.0)),
+            ..default()
+        });
+        app.world_mut()
+            .resource_mut::<MouseKeyTracker>()
+            .double_tapped = true;
+        update(&mut app, 0.01);
+        let pan_orbit = camera(&app, entity);
+        // Half of the way to the edges of the view at the focus, without changing the radius
+        let expected = 0.5 * (PI / 8.0).tan() * 5.0;
+        assert!(approx_eq!(
+            f32,
+            pan_orbit.target_focus.x,
+            expected,
+            epsilon = 0.0001
+        ));
+        assert!(approx_eq!(
+            f32,
+            pan_orbit.target_focus.y,
+            expected,
+            epsilon = 0.0001
+        ));
+        assert!(approx_eq!(
+            f32,
+            pan_orbit.target_focus.z,
+            0.0,
+            epsilon = 0.0001
+        ));
+        assert_eq!(pan_orbit.target_radius, 5.0);
+    }
+
+    #[test]
+    fn orbit_scales_each_axis_by_window_size() {
+        let mut app = setup_app(pan_orbit_camera);
+        app.insert_resource(ActiveCameraData {
+            window_size: Some(Vec2::new(2100.0, 900.0)),
+            ..default()
+        });
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                orbit_uniform_scale: false,
+                ..initial_camera()
+            },
+        );
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(100.0, 100.0);
+        update(&mut app, 0.01);
+        // A full width turns the camera all the way round, and a full height from pole to pole
+        let pan_orbit = camera(&app, entity);
+        assert!(approx_eq!(f32, pan_orbit.target_yaw, -100.0 / 2100.0 * TAU));
+        assert!(approx_eq!(f32, pan_orbit.target_pitch, 100.0 / 900.0 * PI));
+    }
+
+    #[test]
+    fn orbit_uniform_scale_uses_height_for_both_axes() {
+        let mut app = setup_app(pan_orbit_camera);
+        app.insert_resource(ActiveCameraData {
+            window_size: Some(Vec2::new(2100.0, 900.0)),
+            ..default()
+        });
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                orbit_uniform_scale: true,
+                ..initial_camera()
+            },
+        );
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(100.0, 100.0);
+        update(&mut app, 0.01);
+        let pan_orbit = camera(&app, entity);
+        assert!(approx_eq!(f32, pan_orbit.target_yaw, -100.0 / 900.0 * PI));
+        assert!(approx_eq!(f32, pan_orbit.target_pitch, 100.0 / 900.0 * PI));
+    }
+
+    #[test]
+    fn pan_uses_fallback_size_when_viewport_size_missing() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(&mut app, initial_camera());
+        app.world_mut().resource_mut::<MouseKeyTracker>().pan = Vec2::new(100.0, 0.0);
+        update(&mut app, 0.01);
+        // A tenth of the 1000x1000 fallback size, so a tenth of the FOV times the radius for the
+        // default square perspective projection
+        let expected = Vec3::new(-100.0 / 1000.0 * PI / 4.0 * 5.0, 0.0, 0.0);
+        assert!(camera(&app, entity)
+            .target_focus
+            .abs_diff_eq(expected, 0.0001));
+    }
+
+    /// Pan right by a tenth of the viewport with an orthographic camera at each pitch, including
+    /// at and near the poles, returning the resulting focus for each
+    fn ortho_pan_right_at_each_pitch(pan_grab_mode: bool) -> Vec<Vec3> {
+        let mut app = setup_app(pan_orbit_camera);
+        app.insert_resource(ActiveCameraData {
+            viewport_size: Some(Vec2::new(1000.0, 1000.0)),
+            ..default()
+        });
+        let pitches = [
+            PI / 2.0,
+            PI / 2.0 - 0.1,
+            PI / 4.0,
+            -PI / 2.0,
+            -PI / 2.0 + 0.1,
+        ];
+        pitches
+            .into_iter()
+            .map(|pitch| {
+                let entity = spawn_camera(
+                    &mut app,
+                    PanOrbitCamera {
+                        pitch: Some(pitch),
+                        pan_grab_mode,
+                        ..initial_camera()
+                    },
+                );
+                app.world_mut()
+                    .entity_mut(entity)
+                    .insert(Projection::Orthographic(
+                        OrthographicProjection::default_3d(),
+                    ));
+                app.world_mut().resource_mut::<MouseKeyTracker>().pan = Vec2::new(100.0, 0.0);
+                update(&mut app, 0.01);
+                camera(&app, entity).target_focus
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ortho_grab_pan_moves_focus_against_drag_at_any_pitch() {
+        // Grabbing the scene and dragging right moves the camera left
+        for focus in ortho_pan_right_at_each_pitch(true) {
+            assert!(focus.abs_diff_eq(Vec3::new(-0.2, 0.0, 0.0), 0.0001));
+        }
+    }
 
-                has_moved = true;
+    #[test]
+    fn ortho_scroll_pan_moves_focus_with_drag_at_any_pitch() {
+        for focus in ortho_pan_right_at_each_pitch(false) {
+            assert!(focus.abs_diff_eq(Vec3::new(0.2, 0.0, 0.0), 0.0001));
+        }
+    }
+
+    #[test]
+    fn lock_focus_height_keeps_pan_horizontal() {
+        let mut app = setup_app(pan_orbit_camera);
+        app.insert_resource(ActiveCameraData {
+            viewport_size: Some(Vec2::new(1000.0, 1000.0)),
+            ..default()
+        });
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                yaw: Some(PI / 6.0),
+                pitch: Some(PI / 4.0),
+                lock_focus_height: Some(2.0),
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.01);
+        assert!(approx_eq!(f32, camera(&app, entity).target_focus.y, 2.0));
+        app.world_mut().resource_mut::<MouseKeyTracker>().pan = Vec2::new(100.0, 100.0);
+        update(&mut app, 0.01);
+        let target_focus = camera(&app, entity).target_focus;
+        assert!(approx_eq!(f32, target_focus.y, 2.0, epsilon = 0.0001));
+        assert_ne!(target_focus.xz(), Vec2::ZERO);
+    }
+
+    #[test]
+    fn pitch_keepout_skips_across_band() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                pitch_keepout: Some((0.3, 0.6)),
+                ..initial_camera()
+            },
+        );
+        let orbit = |app: &mut App, delta: Vec2| {
+            app.world_mut().resource_mut::<MouseKeyTracker>().orbit = delta;
+            update(app, 0.01);
+            app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::ZERO;
+            camera(app, entity).target_pitch
+        };
+        // Eases with the default smoothing, but never through the band
+        let settle = |app: &mut App| {
+            for _ in 0..100 {
+                let pitch = camera(app, entity).pitch.unwrap();
+                assert!(
+                    !(pitch > 0.3 && pitch < 0.6),
+                    "pitch {pitch} is inside the band"
+                );
+                update(app, 0.01);
             }
+            camera(app, entity).pitch.unwrap()
+        };
+        // Orbiting up by 0.1 * PI would stop inside the band, so it jumps to the far edge
+        assert_eq!(orbit(&mut app, Vec2::new(0.0, 100.0)), 0.6);
+        assert_eq!(settle(&mut app), 0.6);
+        // Then orbiting back down slightly jumps back across
+        assert_eq!(orbit(&mut app, Vec2::new(0.0, -20.0)), 0.3);
+        assert_eq!(settle(&mut app), 0.3);
+        // Orbiting clear of the band is unaffected
+        assert!(approx_eq!(
+            f32,
+            orbit(&mut app, Vec2::new(0.0, -100.0)),
+            0.3 - 0.1 * PI,
+            epsilon = 0.0001
+        ));
+    }
+
+    #[test]
+    fn focus_bounds_constrain_panning() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(&mut app, initial_camera());
+        app.world_mut()
+            .entity_mut(entity)
+            .insert(FocusBounds::new(FocusBoundsShape::Cuboid {
+                min: Vec3::splat(-1.0),
+                max: Vec3::splat(1.0),
+            }));
+        app.world_mut().resource_mut::<MouseKeyTracker>().pan = Vec2::new(-10000.0, 0.0);
+        update(&mut app, 0.01);
+        assert_eq!(camera(&app, entity).target_focus, Vec3::new(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn focus_bounds_limit_zoom() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                zoom_upper_limit: Some(100.0),
+                ..initial_camera()
+            },
+        );
+        app.world_mut().entity_mut(entity).insert(
+            FocusBounds::new(FocusBoundsShape::Sphere {
+                center: Vec3::ZERO,
+                radius: 2.0,
+            })
+            .with_zoom_limit_factor(3.0),
+        );
+        app.world_mut()
+            .resource_mut::<MouseKeyTracker>()
+            .scroll_line = -100.0;
+        update(&mut app, 0.01);
+        assert_eq!(camera(&app, entity).target_radius, 6.0);
+    }
+
+    #[test]
+    fn globe_mode_orbits_over_the_pole() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                globe_mode: true,
+                orbit_smoothness: 0.0,
+                ..initial_camera()
+            },
+        );
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(0.0, 750.0);
+        update(&mut app, 0.01);
+        // Dragged 3/4 of the way over the top, so the camera is now behind the focus
+        let transform = app.world().get::<Transform>(entity).unwrap();
+        assert!(transform.translation.z < 0.0);
+        assert!(approx_eq!(
+            f32,
+            camera(&app, entity).pitch.unwrap(),
+            PI / 4.0,
+            epsilon = 0.0001
+        ));
+    }
+
+    #[test]
+    fn globe_mode_flips_yaw_when_orbiting_over_the_pole() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                globe_mode: true,
+                orbit_smoothness: 0.0,
+                ..initial_camera()
+            },
+        );
+        for _ in 0..15 {
+            app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(0.0, 50.0);
+            update(&mut app, 0.01);
         }
-        if pan.length_squared() > 0.0 {
-            // Make panning distance independent of resolution and FOV,
-            if let Some(vp_size) = active_cam.viewport_size {
-                let mut multiplier = 1.0;
-                match *projection {
-                    Projection::Perspective(ref p) => {
-                        pan *= Vec2::new(p.fov * p.aspect_ratio, p.fov) / vp_size;
-                        // Make panning proportional to distance away from focus point
-                        if let Some(radius) = pan_orbit.radius {
-                            multiplier = radius;
-                        }
-                    }
-                    Projection::Orthographic(ref p) => {
-                        pan *= Vec2::new(p.area.width(), p.area.height()) / vp_size;
-                    }
-                }
-                // Translate by local axes
-                let right = transform.rotation * Vec3::X * -pan.x;
-                let up = transform.rotation * Vec3::Y * pan.y;
-                let translation = (right + up) * multiplier;
-                pan_orbit.target_focus += translation;
-                has_moved = true;
+        // Recalculating from the rotation turns the pitch around at the pole and flips the yaw
+        let camera = camera(&app, entity);
+        assert!(approx_eq!(
+            f32,
+            camera.yaw.unwrap().abs(),
+            PI,
+            epsilon = 0.0001
+        ));
+        assert!(approx_eq!(
+            f32,
+            camera.pitch.unwrap(),
+            PI / 4.0,
+            epsilon = 0.0001
+        ));
+    }
+
+    #[test]
+    fn accumulated_yaw_pitch_orbit_over_the_pole_smoothly() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                globe_mode: true,
+                accumulate_yaw_pitch: true,
+                orbit_smoothness: 0.0,
+                ..initial_camera()
+            },
+        );
+        // Keeps going in the same direction, with no discontinuity
+        let step = 50.0 / 1000.0 * PI;
+        for i in 1..=15 {
+            app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(0.0, 50.0);
+            update(&mut app, 0.01);
+            let camera = camera(&app, entity);
+            assert_eq!(camera.yaw, Some(0.0));
+            assert!(approx_eq!(
+                f32,
+                camera.pitch.unwrap(),
+                i as f32 * step,
+                epsilon = 0.0001
+            ));
+        }
+    }
+
+    #[test]
+    fn orbit_ignores_radius_by_default() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                radius: Some(10.0),
+                ..initial_camera()
+            },
+        );
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(100.0, 0.0);
+        update(&mut app, 0.01);
+        assert!(approx_eq!(
+            f32,
+            camera(&app, entity).target_yaw,
+            -100.0 / 1000.0 * TAU
+        ));
+    }
+
+    #[test]
+    fn orbit_scales_with_radius_when_enabled() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                radius: Some(10.0),
+                sensitivity_scales_with_zoom: true,
+                ..initial_camera()
+            },
+        );
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(100.0, 0.0);
+        update(&mut app, 0.01);
+        // Twice the reference radius, so orbiting is twice as fast
+        assert!(approx_eq!(
+            f32,
+            camera(&app, entity).target_yaw,
+            -100.0 / 1000.0 * TAU * 2.0
+        ));
+    }
+
+    #[test]
+    fn transition_override_reverts_after_arrival() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(&mut app, initial_camera());
+        update(&mut app, 0.01);
+        let state = PanOrbitCameraState {
+            focus: Vec3::X,
+            yaw: 1.0,
+            pitch: 0.5,
+            radius: 2.0,
+        };
+        app.world_mut()
+            .get_mut::<PanOrbitCamera>(entity)
+            .unwrap()
+            .transition_with(state, 0.0, 10.0);
+        update(&mut app, 0.01);
+        let pan_orbit = camera(&app, entity);
+        // Smoothness of 0 arrives immediately, which would not happen with the default smoothness
+        assert_eq!(pan_orbit.yaw, Some(1.0));
+        assert_eq!(pan_orbit.focus, Vec3::X);
+        assert_eq!(pan_orbit.transition, None);
+        assert_eq!(
+            pan_orbit.orbit_smoothness,
+            PanOrbitCamera::default().orbit_smoothness
+        );
+    }
+
+    #[test]
+    fn transition_override_reverts_after_duration() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(&mut app, initial_camera());
+        update(&mut app, 0.01);
+        let state = PanOrbitCameraState {
+            focus: Vec3::ZERO,
+            yaw: 3.0,
+            pitch: 0.0,
+            radius: 5.0,
+        };
+        app.world_mut()
+            .get_mut::<PanOrbitCamera>(entity)
+            .unwrap()
+            .transition_with(state, 0.99, 0.1);
+        update(&mut app, 0.05);
+        assert!(camera(&app, entity).transition.is_some());
+        update(&mut app, 0.06);
+        assert_ne!(camera(&app, entity).yaw, Some(3.0));
+        assert_eq!(camera(&app, entity).transition, None);
+    }
+
+    #[test]
+    fn transition_takes_shortest_way_round() {
+        let mut pan_orbit = PanOrbitCamera {
+            yaw: Some(3.0 * PI),
+            target_yaw: 3.0 * PI,
+            ..initial_camera()
+        };
+        let state = PanOrbitCameraState {
+            focus: Vec3::ZERO,
+            yaw: 0.5,
+            pitch: 0.0,
+            radius: 5.0,
+        };
+        pan_orbit.transition_with(state, 0.5, 1.0);
+        // The same view as a yaw of 0.5, but less than half a turn away
+        assert!(approx_eq!(f32, pan_orbit.target_yaw, TAU + 0.5));
+    }
+
+    #[test]
+    fn transition_turns_camera_in_globe_mode() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                globe_mode: true,
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.01);
+        let state = PanOrbitCameraState {
+            focus: Vec3::ZERO,
+            yaw: 1.0,
+            pitch: 0.5,
+            radius: 5.0,
+        };
+        app.world_mut()
+            .get_mut::<PanOrbitCamera>(entity)
+            .unwrap()
+            .transition_with(state, 0.0, 10.0);
+        update(&mut app, 0.01);
+        let pan_orbit = camera(&app, entity);
+        assert!(approx_eq!(
+            f32,
+            pan_orbit.yaw.unwrap(),
+            1.0,
+            epsilon = 0.0001
+        ));
+        assert!(approx_eq!(
+            f32,
+            pan_orbit.pitch.unwrap(),
+            0.5,
+            epsilon = 0.0001
+        ));
+        assert_eq!(pan_orbit.transition, None);
+        let transform = app.world().get::<Transform>(entity).unwrap();
+        let expected = util::rotation_from_yaw_pitch(1.0, 0.5) * Vec3::Z * 5.0;
+        assert!(transform.translation.abs_diff_eq(expected, 0.0001));
+    }
+
+    #[test]
+    fn yaw_keeps_height_outside_globe_mode() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                pitch: Some(PI / 4.0),
+                orbit_smoothness: 0.0,
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.01);
+        let up_before = app.world().get::<Transform>(entity).unwrap().up();
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(100.0, 0.0);
+        update(&mut app, 0.01);
+        // Yawing around world up keeps the camera at the same height, but tilts its view up
+        let transform = app.world().get::<Transform>(entity).unwrap();
+        assert!(approx_eq!(
+            f32,
+            transform.translation.y,
+            5.0 * (PI / 4.0).sin()
+        ));
+        assert!(up_before.angle_between(*transform.up()) > 0.01);
+    }
+
+    #[test]
+    fn globe_mode_yaws_around_view_up() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                pitch: Some(PI / 4.0),
+                globe_mode: true,
+                orbit_smoothness: 0.0,
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.01);
+        let up_before = app.world().get::<Transform>(entity).unwrap().up();
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(100.0, 0.0);
+        update(&mut app, 0.01);
+        // Yawing around the view up keeps it unchanged, but the camera changes height
+        let transform = app.world().get::<Transform>(entity).unwrap();
+        assert!(up_before.angle_between(*transform.up()) < 0.0001);
+        assert!(transform.translation.y < 5.0 * (PI / 4.0).sin() - 0.01);
+    }
+
+    #[test]
+    fn max_smoothing_dt_limits_large_time_steps() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                max_smoothing_dt: Some(0.1),
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.01);
+        app.world_mut()
+            .get_mut::<PanOrbitCamera>(entity)
+            .unwrap()
+            .target_yaw = 1.0;
+        update(&mut app, 10.0);
+        // Smoothed as if only 0.1 seconds passed, rather than arriving straight away
+        let expected = util::lerp_factor(PanOrbitCamera::default().orbit_smoothness, 0.1);
+        assert!(approx_eq!(f32, camera(&app, entity).yaw.unwrap(), expected));
+    }
+
+    #[test]
+    fn long_frames_arrive_without_max_smoothing_dt() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(&mut app, initial_camera());
+        update(&mut app, 0.01);
+        app.world_mut()
+            .get_mut::<PanOrbitCamera>(entity)
+            .unwrap()
+            .target_yaw = 1.0;
+        update(&mut app, 10.0);
+        assert_eq!(camera(&app, entity).yaw, Some(1.0));
+    }
+
+    #[test]
+    fn large_time_steps_never_overshoot() {
+        for dt in [0.05, 0.5, 5.0, 1000.0] {
+            let mut app = setup_app(pan_orbit_camera);
+            let entity = spawn_camera(&mut app, initial_camera());
+            update(&mut app, 0.01);
+            {
+                let mut pan_orbit = app.world_mut().get_mut::<PanOrbitCamera>(entity).unwrap();
+                pan_orbit.target_yaw = 1.0;
+                pan_orbit.target_pitch = 0.5;
+                pan_orbit.target_radius = 10.0;
+                pan_orbit.target_focus = Vec3::new(2.0, 0.0, 0.0);
+            }
+            let mut previous = *camera(&app, entity);
+            for _ in 0..5 {
+                update(&mut app, dt);
+                let pan_orbit = *camera(&app, entity);
+                // Each value moves towards its target, without passing it
+                assert!(pan_orbit.yaw >= previous.yaw && pan_orbit.yaw <= Some(1.0));
+                assert!(pan_orbit.pitch >= previous.pitch && pan_orbit.pitch <= Some(0.5));
+                assert!(pan_orbit.radius >= previous.radius && pan_orbit.radius <= Some(10.0));
+                assert!(pan_orbit.focus.x >= previous.focus.x && pan_orbit.focus.x <= 2.0);
+                previous = pan_orbit;
             }
         }
-        if (scroll_line + scroll_pixel).abs() > 0.0 {
-            // Calculate the impact of scrolling on the reference value
-            let line_delta = -scroll_line * (pan_orbit.target_radius) * 0.2;
-            let pixel_delta = -scroll_pixel * (pan_orbit.target_radius) * 0.2;
+    }
 
-            // Update the target value
-            pan_orbit.target_radius += line_delta + pixel_delta;
+    #[test]
+    fn mouse_input_matches_ndc_input() {
+        let mut app = setup_app(pan_orbit_camera);
+        let size = Vec2::new(3840.0, 2160.0);
+        app.insert_resource(ActiveCameraData {
+            window_size: Some(size),
+            viewport_size: Some(size),
+            ..default()
+        });
+        let entity = spawn_camera(&mut app, initial_camera());
+        let mut tracker = app.world_mut().resource_mut::<MouseKeyTracker>();
+        tracker.orbit = size * Vec2::new(0.1, 0.05);
+        tracker.pan = size * Vec2::new(0.2, 0.1);
+        update(&mut app, 0.01);
 
-            // If it is pixel-based scrolling, add it directly to the current value
-            pan_orbit.radius = pan_orbit
-                .radius
-                .map(|value| apply_zoom_limits(value + pixel_delta));
+        let mut expected = initial_camera();
+        expected.orbit_by_ndc(Vec2::new(0.1, 0.05));
+        expected.pan_by_ndc(Vec2::new(0.2, 0.1), &Projection::default());
+        let pan_orbit = camera(&app, entity);
+        assert!(approx_eq!(f32, pan_orbit.target_yaw, expected.target_yaw));
+        assert!(approx_eq!(
+            f32,
+            pan_orbit.target_pitch,
+            expected.target_pitch
+        ));
+        assert!(pan_orbit
+            .target_focus
+            .abs_diff_eq(expected.target_focus, 0.0001));
+    }
 
-            has_moved = true;
+    #[test]
+    fn ndc_orbit_matches_mouse_orbit_with_object_style() {
+        let object_camera = PanOrbitCamera {
+            orbit_style: OrbitStyle::Object,
+            ..initial_camera()
+        };
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(&mut app, object_camera);
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(100.0, 50.0);
+        update(&mut app, 0.01);
+
+        let mut expected = object_camera;
+        expected.orbit_by_ndc(Vec2::new(0.1, 0.05));
+        let pan_orbit = camera(&app, entity);
+        assert!(approx_eq!(f32, pan_orbit.target_yaw, expected.target_yaw));
+        assert!(approx_eq!(
+            f32,
+            pan_orbit.target_pitch,
+            expected.target_pitch
+        ));
+        // The opposite way to the default `OrbitStyle::Camera`
+        assert!(approx_eq!(f32, expected.target_yaw, 100.0 / 1000.0 * TAU));
+    }
+
+    #[test]
+    fn above_horizon_stops_camera_going_below() {
+        let mut app = setup_app(pan_orbit_camera);
+        let mut pan_orbit = PanOrbitCamera {
+            orbit_smoothness: 0.0,
+            ..initial_camera()
+        };
+        pan_orbit.set_pitch_range(PitchRange::above_horizon());
+        let entity = spawn_camera(&mut app, pan_orbit);
+        // Orbiting down is stopped at the horizon
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(0.0, -300.0);
+        update(&mut app, 0.01);
+        assert_eq!(camera(&app, entity).pitch, Some(0.0));
+        let translation = app.world().get::<Transform>(entity).unwrap().translation;
+        assert_eq!(translation.y, 0.0);
+
+        // Orbiting up works as usual
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(0.0, 300.0);
+        update(&mut app, 0.01);
+        assert!(approx_eq!(
+            f32,
+            camera(&app, entity).pitch.unwrap(),
+            0.3 * PI
+        ));
+        let translation = app.world().get::<Transform>(entity).unwrap().translation;
+        assert!(translation.y > 0.0);
+    }
+
+    #[test]
+    fn below_horizon_stops_camera_going_above() {
+        let mut app = setup_app(pan_orbit_camera);
+        let mut pan_orbit = PanOrbitCamera {
+            orbit_smoothness: 0.0,
+            ..initial_camera()
+        };
+        pan_orbit.set_pitch_range(PitchRange::below_horizon());
+        let entity = spawn_camera(&mut app, pan_orbit);
+        // Orbiting up is stopped at the horizon
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(0.0, 300.0);
+        update(&mut app, 0.01);
+        assert_eq!(camera(&app, entity).pitch, Some(0.0));
+        let translation = app.world().get::<Transform>(entity).unwrap().translation;
+        assert_eq!(translation.y, 0.0);
+
+        // Orbiting down works as usual
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(0.0, -300.0);
+        update(&mut app, 0.01);
+        assert!(approx_eq!(
+            f32,
+            camera(&app, entity).pitch.unwrap(),
+            -0.3 * PI
+        ));
+        let translation = app.world().get::<Transform>(entity).unwrap().translation;
+        assert!(translation.y < 0.0);
+    }
+
+    #[test]
+    fn holding_zoom_key_zooms_continuously() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                key_zoom_in: Some(KeyCode::Equal),
+                key_zoom_out: Some(KeyCode::Minus),
+                ..initial_camera()
+            },
+        );
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Equal);
+        update(&mut app, 0.1);
+        let first = camera(&app, entity).target_radius;
+        update(&mut app, 0.1);
+        let second = camera(&app, entity).target_radius;
+        assert!(first < 5.0 && second < first);
+
+        let mut key_input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        key_input.release(KeyCode::Equal);
+        key_input.press(KeyCode::Minus);
+        update(&mut app, 0.1);
+        assert!(camera(&app, entity).target_radius > second);
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .release_all();
+        let released = camera(&app, entity).target_radius;
+        update(&mut app, 0.1);
+        assert_eq!(camera(&app, entity).target_radius, released);
+    }
+
+    #[test]
+    fn z_up_axis_positions_camera_relative_to_z() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                axis: [Vec3::X, Vec3::Z, Vec3::NEG_Y],
+                pitch: Some(PI / 4.0),
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.01);
+        let transform = app.world().get::<Transform>(entity).unwrap();
+        assert!(approx_eq!(
+            f32,
+            transform.translation.z,
+            5.0 * (PI / 4.0).sin()
+        ));
+        assert!(transform.up().z > 0.0);
+    }
+
+    #[test]
+    fn target_axis_smoothly_reorients_up_without_moving_camera() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                yaw: Some(PI / 2.0),
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.01);
+        app.world_mut()
+            .get_mut::<PanOrbitCamera>(entity)
+            .unwrap()
+            .target_axis = [Vec3::X, Vec3::Z, Vec3::NEG_Y];
+
+        update(&mut app, 0.01);
+        let transform = app.world().get::<Transform>(entity).unwrap();
+        assert!(transform.translation.abs_diff_eq(Vec3::X * 5.0, 0.0001));
+        // Part way between Y and Z
+        assert!(transform.up().y > 0.01 && transform.up().z > 0.01);
+
+        for _ in 0..100 {
+            update(&mut app, 0.1);
         }
+        let transform = app.world().get::<Transform>(entity).unwrap();
+        assert!(transform.translation.abs_diff_eq(Vec3::X * 5.0, 0.0001));
+        assert!(transform.up().abs_diff_eq(Vec3::Z, 0.0001));
+        let pan_orbit = camera(&app, entity);
+        assert_eq!(pan_orbit.axis, pan_orbit.target_axis);
+        assert!(approx_eq!(
+            f32,
+            pan_orbit.yaw.unwrap(),
+            PI / 2.0,
+            epsilon = 0.0001
+        ));
+    }
 
-        // 3 - Apply constraints
+    #[test]
+    fn zoom_levels_snap_per_line() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(&mut app, initial_camera());
+        app.world_mut()
+            .entity_mut(entity)
+            .insert(ZoomLevels::new([10.0, 1.0, 2.0, 5.0, 20.0]));
+        let scroll = |app: &mut App, lines: f32| {
+            app.world_mut()
+                .resource_mut::<MouseKeyTracker>()
+                .scroll_line = lines;
+            update(app, 0.01);
+            camera(app, entity).target_radius
+        };
+        assert_eq!(scroll(&mut app, 1.0), 2.0);
+        assert_eq!(scroll(&mut app, 1.0), 1.0);
+        // Clamped at the smallest level
+        assert_eq!(scroll(&mut app, 1.0), 1.0);
+        assert_eq!(scroll(&mut app, -3.0), 10.0);
+        // Less than a whole line doesn't step until it adds up
+        assert_eq!(scroll(&mut app, -0.6), 10.0);
+        assert_eq!(scroll(&mut app, -0.6), 20.0);
+    }
 
-        pan_orbit.target_yaw = apply_yaw_limits(pan_orbit.target_yaw);
-        pan_orbit.target_pitch = apply_pitch_limits(pan_orbit.target_pitch);
-        pan_orbit.target_radius = apply_zoom_limits(pan_orbit.target_radius);
+    #[test]
+    fn logarithmic_zoom_scales_radius_per_line() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                zoom_scaling: ZoomScaling::Logarithmic { factor: 0.9 },
+                ..initial_camera()
+            },
+        );
+        for _ in 0..2 {
+            app.world_mut()
+                .resource_mut::<MouseKeyTracker>()
+                .scroll_line = 1.0;
+            update(&mut app, 0.01);
+        }
+        assert!(approx_eq!(
+            f32,
+            camera(&app, entity).target_radius,
+            5.0 * 0.9 * 0.9,
+            epsilon = 0.0001
+        ));
+        // Zooming back out undoes it exactly
+        for _ in 0..2 {
+            app.world_mut()
+                .resource_mut::<MouseKeyTracker>()
+                .scroll_line = -1.0;
+            update(&mut app, 0.01);
+        }
+        assert!(approx_eq!(
+            f32,
+            camera(&app, entity).target_radius,
+            5.0,
+            epsilon = 0.0001
+        ));
+    }
 
-        if !pan_orbit.allow_upside_down {
-            pan_orbit.target_pitch = pan_orbit.target_pitch.clamp(-PI / 2.0, PI / 2.0);
+    #[test]
+    fn logarithmic_zoom_gives_geometric_progression() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                zoom_scaling: ZoomScaling::Logarithmic { factor: 0.5 },
+                zoom_lower_limit: 0.001,
+                ..initial_camera()
+            },
+        );
+        let mut radii = vec![5.0];
+        for _ in 0..10 {
+            app.world_mut()
+                .resource_mut::<MouseKeyTracker>()
+                .scroll_line = 1.0;
+            update(&mut app, 0.01);
+            radii.push(camera(&app, entity).target_radius);
         }
+        for pair in radii.windows(2) {
+            assert!(approx_eq!(f32, pair[1] / pair[0], 0.5, epsilon = 0.0001));
+        }
+    }
 
-        // 4 - Update the camera's transform based on current values
+    #[test]
+    fn linear_zoom_changes_radius_by_fixed_amount() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                zoom_scaling: ZoomScaling::Linear { step: 0.5 },
+                ..initial_camera()
+            },
+        );
+        for expected in [4.5, 4.0, 3.5] {
+            app.world_mut()
+                .resource_mut::<MouseKeyTracker>()
+                .scroll_line = 1.0;
+            update(&mut app, 0.01);
+            assert!(approx_eq!(
+                f32,
+                camera(&app, entity).target_radius,
+                expected,
+                epsilon = 0.0001
+            ));
+        }
+    }
 
-        if let (Some(yaw), Some(pitch), Some(radius)) =
-            (pan_orbit.yaw, pan_orbit.pitch, pan_orbit.radius)
+    #[test]
+    fn zoom_pivot_focus_changes_radius() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(&mut app, initial_camera());
+        update(&mut app, 0.01);
+        app.world_mut()
+            .resource_mut::<MouseKeyTracker>()
+            .scroll_line = 1.0;
+        update(&mut app, 0.01);
+        let pan_orbit = camera(&app, entity);
+        assert!(pan_orbit.target_radius < 5.0);
+        assert_eq!(pan_orbit.target_focus, Vec3::ZERO);
+    }
+
+    #[test]
+    fn zoom_pivot_cursor_keeps_point_under_cursor() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                zoom_pivot: ZoomPivot::Cursor,
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.01);
+        // Halfway to the top right corner
+        app.insert_resource(ActiveCameraData {
+            entity: Some(entity),
+            viewport_size: Some(Vec2::new(100.0, 100.0)),
+            cursor_position: Some(Vec2::new(75.0, 25.0)),
+            ..default()
+        });
+        app.world_mut()
+            .resource_mut::<MouseKeyTracker>()
+            .scroll_line = 1.0;
+        update(&mut app, 0.01);
+
+        let pan_orbit = camera(&app, entity);
+        assert_eq!(pan_orbit.target_radius, 4.0);
+        // The point under the cursor is (0.5, 0.5) of the way to the edges of the view at the
+        // focus, which is tan(fov / 2) * radius from the centre. Zooming in by a fifth moves the
+        // focus a fifth of the way towards it.
+        let expected = 0.5 * (PI / 8.0).tan() * 5.0 * 0.2;
+        assert!(approx_eq!(
+            f32,
+            pan_orbit.target_focus.x,
+            expected,
+            epsilon = 0.0001
+        ));
+        assert!(approx_eq!(
+            f32,
+            pan_orbit.target_focus.y,
+            expected,
+            epsilon = 0.0001
+        ));
+        assert_eq!(pan_orbit.target_focus.z, 0.0);
+    }
+
+    #[test]
+    fn zoom_pivot_cursor_keeps_focus_at_zoom_limit() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                zoom_pivot: ZoomPivot::Cursor,
+                zoom_lower_limit: 5.0,
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.01);
+        app.insert_resource(ActiveCameraData {
+            entity: Some(entity),
+            viewport_size: Some(Vec2::new(100.0, 100.0)),
+            cursor_position: Some(Vec2::new(75.0, 25.0)),
+            ..default()
+        });
+        app.world_mut()
+            .resource_mut::<MouseKeyTracker>()
+            .scroll_line = 1.0;
+        update(&mut app, 0.01);
+
+        // Already at the limit, so neither the radius nor the focus move
+        let pan_orbit = camera(&app, entity);
+        assert_eq!(pan_orbit.target_radius, 5.0);
+        assert_eq!(pan_orbit.target_focus, Vec3::ZERO);
+    }
+
+    #[test]
+    fn zoom_pivot_cursor_only_applies_to_mouse_scroll() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                zoom_pivot: ZoomPivot::Cursor,
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.01);
+        app.insert_resource(ActiveCameraData {
+            entity: Some(entity),
+            viewport_size: Some(Vec2::new(100.0, 100.0)),
+            cursor_position: Some(Vec2::new(75.0, 25.0)),
+            ..default()
+        });
+        app.insert_resource(CameraInput {
+            zoom: 1.0,
+            ..default()
+        });
+        update(&mut app, 0.01);
+        let pan_orbit = camera(&app, entity);
+        assert_eq!(pan_orbit.target_radius, 4.0);
+        assert_eq!(pan_orbit.target_focus, Vec3::ZERO);
+    }
+
+    #[test]
+    fn zoom_pivot_eye_moves_focus() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                zoom_pivot: ZoomPivot::Eye,
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.01);
+        app.world_mut()
+            .resource_mut::<MouseKeyTracker>()
+            .scroll_line = 1.0;
+        update(&mut app, 0.01);
+        let pan_orbit = camera(&app, entity);
+        assert_eq!(pan_orbit.target_radius, 5.0);
+        // The camera looks along -Z, so zooming in moves the focus that way
+        assert!(approx_eq!(
+            f32,
+            pan_orbit.target_focus.z,
+            -1.0,
+            epsilon = 0.0001
+        ));
+        assert!(approx_eq!(
+            f32,
+            pan_orbit.target_focus.x,
+            0.0,
+            epsilon = 0.0001
+        ));
+        assert!(approx_eq!(
+            f32,
+            pan_orbit.target_focus.y,
+            0.0,
+            epsilon = 0.0001
+        ));
+    }
+
+    #[test]
+    fn zoom_pivot_eye_ignores_zoom_limits() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                zoom_pivot: ZoomPivot::Eye,
+                zoom_lower_limit: 5.0,
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.01);
+        app.world_mut()
+            .resource_mut::<MouseKeyTracker>()
+            .scroll_line = 1.0;
+        update(&mut app, 0.01);
+        app.world_mut()
+            .resource_mut::<MouseKeyTracker>()
+            .scroll_line = 0.0;
+        // Already at the lower limit, but the focus still moves forward by a full step
+        let pan_orbit = camera(&app, entity);
+        assert_eq!(pan_orbit.target_radius, 5.0);
+        assert!(approx_eq!(
+            f32,
+            pan_orbit.target_focus.z,
+            -1.0,
+            epsilon = 0.0001
+        ));
+
+        // Pixel-based scrolling moves the current focus directly, also ignoring the limit
+        let focus_before = camera(&app, entity).focus;
+        app.world_mut()
+            .resource_mut::<MouseKeyTracker>()
+            .scroll_pixel = 0.5;
+        update(&mut app, 0.01);
+        let pan_orbit = camera(&app, entity);
+        assert_eq!(pan_orbit.radius, Some(5.0));
+        assert!(pan_orbit.focus.z < focus_before.z - 0.4);
+    }
+
+    #[test]
+    fn limits_ignored_while_disabled() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                yaw_upper_limit: Some(0.5),
+                zoom_upper_limit: Some(10.0),
+                limits_enabled: false,
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.01);
         {
-            if has_moved
-                // For smoothed values, we must check whether current value is different from target
-                // value. If we only checked whether the values were non-zero this frame, then
-                // the camera would instantly stop moving as soon as you stopped moving it, instead
-                // of smoothly stopping
-                || pan_orbit.target_yaw != yaw
-                || pan_orbit.target_pitch != pitch
-                || pan_orbit.target_radius != radius
-                || pan_orbit.target_focus != pan_orbit.focus
-                || pan_orbit.force_update
+            let mut pan_orbit = app.world_mut().get_mut::<PanOrbitCamera>(entity).unwrap();
+            pan_orbit.target_yaw = 1.0;
+            pan_orbit.target_radius = 20.0;
+        }
+        update(&mut app, 0.01);
+        assert_eq!(camera(&app, entity).target_yaw, 1.0);
+        assert_eq!(camera(&app, entity).target_radius, 20.0);
+
+        app.world_mut()
+            .get_mut::<PanOrbitCamera>(entity)
+            .unwrap()
+            .limits_enabled = true;
+        update(&mut app, 0.01);
+        assert_eq!(camera(&app, entity).target_yaw, 0.5);
+        assert_eq!(camera(&app, entity).target_radius, 10.0);
+    }
+
+    #[test]
+    fn orbit_cone_clamps_view_direction() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                orbit_cone: Some((Vec3::NEG_Z, 0.3)),
+                orbit_smoothness: 0.0,
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.01);
+        let view_dir = |app: &App| {
+            let pan_orbit = camera(app, entity);
+            -(pan_orbit.rotation() * Vec3::Z)
+        };
+        // Orbit diagonally, well past the edge of the cone
+        for _ in 0..5 {
+            app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(100.0, 100.0);
+            update(&mut app, 0.01);
+        }
+        let angle = view_dir(&app).angle_between(Vec3::NEG_Z);
+        assert!(approx_eq!(f32, angle, 0.3, epsilon = 0.0001));
+        // On the circle rather than in a corner, so both yaw and pitch are inside the cone
+        let pan_orbit = camera(&app, entity);
+        assert!(pan_orbit.target_yaw.abs() < 0.3);
+        assert!(pan_orbit.target_pitch.abs() < 0.3);
+
+        // Orbiting back inside the cone is unaffected
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(-50.0, -50.0);
+        update(&mut app, 0.01);
+        assert!(view_dir(&app).angle_between(Vec3::NEG_Z) < 0.3);
+    }
+
+    #[test]
+    fn relative_limits_center_on_initial_angles() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                yaw_limit_relative: Some(0.5),
+                pitch_limit_relative: Some(0.2),
+                ..default()
+            },
+        );
+        // Yaw and pitch both PI / 4
+        app.world_mut()
+            .entity_mut(entity)
+            .insert(Transform::from_xyz(1.0, 2.0f32.sqrt(), 1.0));
+        update(&mut app, 0.01);
+        let pan_orbit = camera(&app, entity);
+        let (yaw, pitch) = (pan_orbit.yaw.unwrap(), pan_orbit.pitch.unwrap());
+        assert!(approx_eq!(f32, yaw, PI / 4.0, epsilon = 0.0001));
+        assert!(approx_eq!(f32, pitch, PI / 4.0, epsilon = 0.0001));
+        assert_eq!(pan_orbit.yaw_upper_limit, Some(yaw + 0.5));
+        assert_eq!(pan_orbit.yaw_lower_limit, Some(yaw - 0.5));
+        assert_eq!(pan_orbit.pitch_upper_limit, Some(pitch + 0.2));
+        assert_eq!(pan_orbit.pitch_lower_limit, Some(pitch - 0.2));
+
+        {
+            let mut pan_orbit = app.world_mut().get_mut::<PanOrbitCamera>(entity).unwrap();
+            pan_orbit.target_yaw = 0.0;
+            pan_orbit.target_pitch = 1.5;
+        }
+        update(&mut app, 0.01);
+        assert_eq!(camera(&app, entity).target_yaw, yaw - 0.5);
+        assert_eq!(camera(&app, entity).target_pitch, pitch + 0.2);
+    }
+
+    #[test]
+    fn pixel_zoom_stops_with_input_without_momentum() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(&mut app, initial_camera());
+        update(&mut app, 0.01);
+        app.world_mut()
+            .resource_mut::<MouseKeyTracker>()
+            .scroll_pixel = 0.1;
+        update(&mut app, 0.01);
+        let radius = camera(&app, entity).radius;
+        assert!(radius < Some(5.0));
+        app.world_mut()
+            .resource_mut::<MouseKeyTracker>()
+            .scroll_pixel = 0.0;
+        update(&mut app, 0.01);
+        assert_eq!(camera(&app, entity).radius, radius);
+    }
+
+    #[test]
+    fn zoom_momentum_coasts_after_pixel_zoom() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                zoom_momentum: 0.9,
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.01);
+        app.world_mut()
+            .resource_mut::<MouseKeyTracker>()
+            .scroll_pixel = 0.1;
+        update(&mut app, 0.01);
+        app.world_mut()
+            .resource_mut::<MouseKeyTracker>()
+            .scroll_pixel = 0.0;
+        // It keeps zooming in after the input stops, but slows down
+        let mut radius = camera(&app, entity).radius.unwrap();
+        let mut previous_delta = f32::INFINITY;
+        for _ in 0..3 {
+            update(&mut app, 0.01);
+            let new_radius = camera(&app, entity).radius.unwrap();
+            let delta = radius - new_radius;
+            assert!(delta > 0.0 && delta < previous_delta);
+            radius = new_radius;
+            previous_delta = delta;
+        }
+    }
+
+    #[test]
+    fn target_transform_matches_settled_transform() {
+        for projection in [
+            Projection::default(),
+            Projection::Orthographic(OrthographicProjection::default_3d()),
+        ] {
+            let mut app = setup_app(pan_orbit_camera);
+            let entity = app
+                .world_mut()
+                .spawn((initial_camera(), projection.clone()))
+                .id();
+            update(&mut app, 0.01);
             {
-                // Interpolate towards the target values
-                let new_yaw = util::lerp_and_snap_f32(
-                    yaw,
-                    pan_orbit.target_yaw,
-                    pan_orbit.orbit_smoothness,
-                    time.delta_secs(),
-                );
-                let new_pitch = util::lerp_and_snap_f32(
-                    pitch,
-                    pan_orbit.target_pitch,
-                    pan_orbit.orbit_smoothness,
-                    time.delta_secs(),
-                );
-                let new_radius = util::lerp_and_snap_f32(
-                    radius,
-                    pan_orbit.target_radius,
-                    pan_orbit.zoom_smoothness,
-                    time.delta_secs(),
-                );
-                let new_focus = util::lerp_and_snap_vec3(
-                    pan_orbit.focus,
-                    pan_orbit.target_focus,
-                    pan_orbit.pan_smoothness,
-                    time.delta_secs(),
-                );
+                let mut pan_orbit = app.world_mut().get_mut::<PanOrbitCamera>(entity).unwrap();
+                pan_orbit.target_yaw = 1.0;
+                pan_orbit.target_pitch = -0.5;
+                pan_orbit.target_radius = 3.0;
+                pan_orbit.target_focus = Vec3::new(1.0, 2.0, 3.0);
+            }
+            update(&mut app, 0.01);
+            let (target_transform, target_scale) =
+                camera(&app, entity).target_transform(&projection);
+            for _ in 0..200 {
+                update(&mut app, 0.1);
+            }
+            let transform = app.world().get::<Transform>(entity).unwrap();
+            assert!(transform
+                .translation
+                .abs_diff_eq(target_transform.translation, 0.0001));
+            assert!(transform
+                .rotation
+                .abs_diff_eq(target_transform.rotation, 0.0001));
+            let scale = match app.world().get::<Projection>(entity).unwrap() {
+                Projection::Orthographic(p) => Some(p.scale),
+                Projection::Perspective(_) => None,
+            };
+            assert_eq!(scale, target_scale);
+        }
+    }
 
-                util::update_orbit_transform(
-                    new_yaw,
-                    new_pitch,
-                    new_radius,
-                    new_focus,
-                    &mut transform,
-                    &mut projection,
-                );
+    #[test]
+    fn frame_bounds_fits_bounding_sphere() {
+        let mut pan_orbit = PanOrbitCamera {
+            frame_padding: 0.0,
+            ..initial_camera()
+        };
+        let projection = Projection::Perspective(PerspectiveProjection {
+            fov: PI / 2.0,
+            ..default()
+        });
+        // A bounding sphere of radius 1
+        let half_extent = 1.0 / 3.0_f32.sqrt();
+        let aabb = Aabb::from_min_max(
+            Vec3::new(1.0, 2.0, 3.0) - half_extent,
+            Vec3::new(1.0, 2.0, 3.0) + half_extent,
+        );
+        pan_orbit.frame_bounds(aabb, &projection, 2.0);
+        assert_eq!(pan_orbit.target_focus, Vec3::new(1.0, 2.0, 3.0));
+        assert!(approx_eq!(
+            f32,
+            pan_orbit.target_radius,
+            2.0_f32.sqrt(),
+            epsilon = 0.0001
+        ));
+        // Taller than it is wide, so the horizontal fov is narrower and the camera is further away
+        pan_orbit.frame_bounds(aabb, &projection, 0.5);
+        assert!(approx_eq!(
+            f32,
+            pan_orbit.target_radius,
+            5.0_f32.sqrt(),
+            epsilon = 0.0001
+        ));
+        assert_eq!(pan_orbit.target_yaw, 0.0);
+        assert_eq!(pan_orbit.target_pitch, 0.0);
+        assert!(pan_orbit.force_update);
+    }
 
-                // Update the current values
-                pan_orbit.yaw = Some(new_yaw);
-                pan_orbit.pitch = Some(new_pitch);
-                pan_orbit.radius = Some(new_radius);
-                pan_orbit.focus = new_focus;
-                pan_orbit.force_update = false;
+    #[test]
+    fn frame_bounds_handles_orthographic_and_empty_bounds() {
+        let mut pan_orbit = PanOrbitCamera {
+            frame_padding: 0.0,
+            ..initial_camera()
+        };
+        let projection = Projection::Orthographic(OrthographicProjection {
+            scale: 2.0,
+            area: Rect::new(-1.0, -2.0, 1.0, 2.0),
+            ..OrthographicProjection::default_3d()
+        });
+        let aabb = Aabb::from_min_max(Vec3::splat(-1.0), Vec3::splat(1.0));
+        pan_orbit.frame_bounds(aabb, &projection, 1.0);
+        assert!(approx_eq!(
+            f32,
+            pan_orbit.target_radius,
+            2.0 * 3.0_f32.sqrt(),
+            epsilon = 0.0001
+        ));
+
+        let empty = Aabb::from_min_max(Vec3::X, Vec3::X);
+        pan_orbit.frame_bounds(empty, &Projection::default(), 1.0);
+        assert_eq!(pan_orbit.target_focus, Vec3::X);
+        assert_eq!(pan_orbit.target_radius, pan_orbit.zoom_lower_limit);
+    }
+
+    #[test]
+    fn azimuth_and_elevation_map_to_yaw_and_pitch() {
+        let at = |yaw: f32, pitch: f32| PanOrbitCamera {
+            yaw: Some(yaw),
+            pitch: Some(pitch),
+            ..default()
+        };
+        let assert_angle = |actual: f32, expected: f32| {
+            assert!(
+                approx_eq!(f32, actual, expected, epsilon = 0.0001),
+                "{actual} != {expected}"
+            );
+        };
+        // Yaw 0 has the camera on +Z, a quarter turn clockwise from north (+X)
+        assert_angle(at(0.0, 0.0).azimuth(), PI / 2.0);
+        assert_angle(at(PI / 2.0, 0.0).azimuth(), 0.0);
+        assert_angle(at(-PI / 2.0, 0.0).azimuth(), PI);
+        assert_angle(at(PI, 0.0).azimuth(), PI * 1.5);
+        assert_angle(at(0.3, PI / 4.0).elevation(), PI / 4.0);
+        assert_angle(at(0.3, -0.2).elevation(), -0.2);
+        // Past the pole, the camera is above the focus on the other side
+        let upside_down = at(0.0, PI * 0.75);
+        assert_angle(upside_down.elevation(), PI / 4.0);
+        assert_angle(upside_down.azimuth(), PI * 1.5);
+
+        let mut pan_orbit = at(0.0, 0.0);
+        pan_orbit.set_azimuth(PI);
+        pan_orbit.set_elevation(0.5);
+        assert_angle(pan_orbit.target_yaw, -PI / 2.0);
+        assert_angle(pan_orbit.target_pitch, 0.5);
+    }
+
+    #[test]
+    fn azimuth_is_relative_to_axis() {
+        let mut pan_orbit = PanOrbitCamera {
+            axis: [Vec3::X, Vec3::Z, Vec3::NEG_Y],
+            yaw: Some(0.0),
+            pitch: Some(0.0),
+            ..default()
+        };
+        // With Z up, the camera starts on -Y, which is clockwise from +X looking down
+        assert!(approx_eq!(f32, pan_orbit.azimuth(), PI / 2.0));
+        let dir = pan_orbit.rotation() * Vec3::Z;
+        assert!(dir.abs_diff_eq(Vec3::NEG_Y, 0.0001));
+
+        // North is +X, and an elevation of a quarter turn is straight above, along +Z
+        pan_orbit.set_azimuth(0.0);
+        pan_orbit.yaw = Some(pan_orbit.target_yaw);
+        let dir = pan_orbit.rotation() * Vec3::Z;
+        assert!(dir.abs_diff_eq(Vec3::X, 0.0001));
+        pan_orbit.set_elevation(PI / 2.0);
+        pan_orbit.pitch = Some(pan_orbit.target_pitch);
+        let dir = pan_orbit.rotation() * Vec3::Z;
+        assert!(dir.abs_diff_eq(Vec3::Z, 0.0001));
+        assert!(approx_eq!(
+            f32,
+            pan_orbit.elevation(),
+            PI / 2.0,
+            epsilon = 0.0001
+        ));
+    }
+
+    #[test]
+    fn focus_screen_position_projects_focus() {
+        let viewport_size = Vec2::new(800.0, 600.0);
+        let projection = Projection::Perspective(PerspectiveProjection {
+            fov: PI / 4.0,
+            aspect_ratio: 800.0 / 600.0,
+            ..default()
+        });
+        let pan_orbit = PanOrbitCamera {
+            focus: Vec3::new(1.0, 2.0, 3.0),
+            yaw: Some(0.7),
+            pitch: Some(-0.3),
+            ..initial_camera()
+        };
+        let position = pan_orbit
+            .focus_screen_position(viewport_size, &projection)
+            .unwrap();
+        assert!(position.abs_diff_eq(Vec2::new(400.0, 300.0), 0.001));
+
+        // Looking one unit to the right of the focus, from five units away
+        let pan_orbit = PanOrbitCamera {
+            look_at_fixed: Some(Vec3::X),
+            ..initial_camera()
+        };
+        let position = pan_orbit
+            .focus_screen_position(viewport_size, &projection)
+            .unwrap();
+        let ndc_x = -0.2 / ((PI / 8.0).tan() * 800.0 / 600.0);
+        assert!(position.abs_diff_eq(Vec2::new((ndc_x + 1.0) * 400.0, 300.0), 0.001));
+
+        // Looking away from the focus
+        let pan_orbit = PanOrbitCamera {
+            look_at_fixed: Some(Vec3::Z * 10.0),
+            ..initial_camera()
+        };
+        assert_eq!(
+            pan_orbit.focus_screen_position(viewport_size, &projection),
+            None
+        );
+        assert_eq!(
+            PanOrbitCamera::default().focus_screen_position(viewport_size, &projection),
+            None
+        );
+    }
+
+    #[test]
+    fn max_roll_limits_horizon_tilt_in_globe_mode() {
+        let roll_after_orbiting = |max_roll: Option<f32>| {
+            let mut app = setup_app(pan_orbit_camera);
+            let entity = spawn_camera(
+                &mut app,
+                PanOrbitCamera {
+                    globe_mode: true,
+                    max_roll,
+                    orbit_smoothness: 0.0,
+                    ..initial_camera()
+                },
+            );
+            update(&mut app, 0.01);
+            // Orbiting up then sideways then down rolls the camera
+            for orbit in [Vec2::new(0.0, 300.0), Vec2::new(600.0, 0.0)] {
+                app.world_mut().resource_mut::<MouseKeyTracker>().orbit = orbit;
+                update(&mut app, 0.01);
+            }
+            let rotation = camera(&app, entity).globe_rotation.unwrap();
+            let forward = rotation * Vec3::NEG_Z;
+            let level_right = forward.cross(Vec3::Y).normalize();
+            let right = rotation * Vec3::X;
+            level_right
+                .cross(right)
+                .dot(forward)
+                .atan2(level_right.dot(right))
+        };
+        assert!(roll_after_orbiting(None).abs() > 0.2);
+        assert!(roll_after_orbiting(Some(0.1)).abs() <= 0.1 + 0.0001);
+    }
+
+    #[test]
+    fn intro_from_starts_at_intro_view_and_glides_to_target() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                intro_from: Some(PanOrbitCameraState {
+                    focus: Vec3::Y,
+                    yaw: 1.0,
+                    pitch: 0.5,
+                    radius: 20.0,
+                }),
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.0);
+        let cam = camera(&app, entity);
+        assert_eq!(cam.yaw, Some(1.0));
+        assert_eq!(cam.radius, Some(20.0));
+        assert_eq!(cam.focus, Vec3::Y);
+        assert_eq!(cam.target_yaw, 0.0);
+        assert_eq!(cam.target_radius, 5.0);
+        assert_eq!(cam.target_focus, Vec3::ZERO);
+        // Home is the configured view, not the intro view
+        assert_eq!(cam.home_state.unwrap().radius, 5.0);
+
+        for _ in 0..200 {
+            update(&mut app, 0.05);
+        }
+        let cam = camera(&app, entity);
+        assert!(approx_eq!(f32, cam.yaw.unwrap(), 0.0, epsilon = 0.001));
+        assert!(approx_eq!(f32, cam.radius.unwrap(), 5.0, epsilon = 0.001));
+        assert!(cam.focus.distance(Vec3::ZERO) < 0.001);
+    }
+
+    #[test]
+    fn touch_orbit_uses_orbit_sensitivity_by_default() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                orbit_sensitivity: 2.0,
+                touch_orbit_sensitivity: None,
+                orbit_smoothness: 0.0,
+                ..initial_camera()
+            },
+        );
+        app.insert_resource(TouchTracker::one_finger_drag(Vec2::new(100.0, 0.0)));
+        update(&mut app, 0.1);
+        let expected = -100.0 / 1000.0 * TAU * 2.0;
+        assert!(approx_eq!(f32, camera(&app, entity).yaw.unwrap(), expected));
+    }
+
+    #[test]
+    fn touch_orbit_sensitivity_overrides_orbit_sensitivity() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                orbit_sensitivity: 2.0,
+                touch_orbit_sensitivity: Some(0.5),
+                orbit_smoothness: 0.0,
+                ..initial_camera()
+            },
+        );
+        app.insert_resource(TouchTracker::one_finger_drag(Vec2::new(100.0, 0.0)));
+        update(&mut app, 0.1);
+        let expected = -100.0 / 1000.0 * TAU * 0.5;
+        assert!(approx_eq!(f32, camera(&app, entity).yaw.unwrap(), expected));
+    }
+
+    #[test]
+    fn mouse_orbit_ignores_touch_orbit_sensitivity() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                orbit_sensitivity: 2.0,
+                touch_orbit_sensitivity: Some(0.5),
+                orbit_smoothness: 0.0,
+                ..initial_camera()
+            },
+        );
+        app.insert_resource(MouseKeyTracker {
+            orbit: Vec2::new(100.0, 0.0),
+            ..default()
+        });
+        update(&mut app, 0.1);
+        let expected = -100.0 / 1000.0 * TAU * 2.0;
+        assert!(approx_eq!(f32, camera(&app, entity).yaw.unwrap(), expected));
+    }
+
+    #[test]
+    fn touch_controls_change_waits_for_fresh_touch() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                orbit_smoothness: 0.0,
+                pan_smoothness: 0.0,
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.0);
+        app.insert_resource(ActiveCameraData {
+            entity: Some(entity),
+            ..default()
+        });
+        // Switch schemes while a one finger orbit is underway
+        app.world_mut()
+            .get_mut::<PanOrbitCamera>(entity)
+            .unwrap()
+            .touch_controls = TouchControls::TwoFingerOrbit;
+        app.insert_resource(
+            TouchTracker::one_finger_drag(Vec2::new(100.0, 0.0))
+                .with_controls(TouchControls::OneFingerOrbit),
+        );
+        update(&mut app, 0.1);
+        // Still orbiting, rather than jumping to a pan
+        assert!(camera(&app, entity).yaw.unwrap() < 0.0);
+        assert_eq!(camera(&app, entity).focus, Vec3::ZERO);
+
+        // A fresh touch uses the new scheme
+        let yaw = camera(&app, entity).yaw.unwrap();
+        app.insert_resource(TouchTracker::one_finger_drag(Vec2::new(100.0, 0.0)));
+        update(&mut app, 0.1);
+        assert_eq!(camera(&app, entity).yaw.unwrap(), yaw);
+        assert_ne!(camera(&app, entity).focus, Vec3::ZERO);
+    }
+
+    #[test]
+    fn pinch_ignored_during_touch_pinch_delay() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                touch_pinch_delay: 0.05,
+                zoom_smoothness: 0.0,
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.0);
+        app.insert_resource(ActiveCameraData {
+            entity: Some(entity),
+            ..default()
+        });
+        // A big jump as the fingers settle
+        app.insert_resource(TouchTracker::two_finger_pinch(200.0, 0.0));
+        update(&mut app, 0.02);
+        assert_eq!(camera(&app, entity).target_radius, 5.0);
+        app.insert_resource(TouchTracker::two_finger_pinch(20.0, 0.04));
+        update(&mut app, 0.02);
+        assert_eq!(camera(&app, entity).target_radius, 5.0);
+
+        // After the delay, pinching zooms in
+        app.insert_resource(TouchTracker::two_finger_pinch(20.0, 0.06));
+        update(&mut app, 0.02);
+        assert!(camera(&app, entity).target_radius < 5.0);
+    }
+
+    #[test]
+    fn input_priority_both_combines_touch_and_mouse() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                input_priority: InputPriority::Both,
+                orbit_smoothness: 0.0,
+                ..initial_camera()
+            },
+        );
+        app.insert_resource(TouchTracker::one_finger_drag(Vec2::new(100.0, 0.0)));
+        app.insert_resource(MouseKeyTracker {
+            orbit: Vec2::new(200.0, 0.0),
+            ..default()
+        });
+        update(&mut app, 0.1);
+        // 100 px of touch and 200 px of mouse, in the 1000 px wide fallback viewport
+        let expected = -0.1 * TAU - 0.2 * TAU;
+        assert!(approx_eq!(f32, camera(&app, entity).yaw.unwrap(), expected));
+    }
+
+    #[test]
+    fn input_priority_mouse_first_ignores_touch() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                input_priority: InputPriority::MouseFirst,
+                orbit_smoothness: 0.0,
+                ..initial_camera()
+            },
+        );
+        app.insert_resource(TouchTracker::one_finger_drag(Vec2::new(100.0, 0.0)));
+        app.insert_resource(MouseKeyTracker {
+            orbit: Vec2::new(200.0, 0.0),
+            ..default()
+        });
+        update(&mut app, 0.1);
+        // Only the 200 px of mouse, in the 1000 px wide fallback viewport
+        let expected = -0.2 * TAU;
+        assert!(approx_eq!(f32, camera(&app, entity).yaw.unwrap(), expected));
+    }
+
+    #[test]
+    fn input_priority_touch_first_ignores_mouse() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                input_priority: InputPriority::TouchFirst,
+                orbit_smoothness: 0.0,
+                ..initial_camera()
+            },
+        );
+        app.insert_resource(TouchTracker::one_finger_drag(Vec2::new(100.0, 0.0)));
+        app.insert_resource(MouseKeyTracker {
+            orbit: Vec2::new(200.0, 0.0),
+            ..default()
+        });
+        update(&mut app, 0.1);
+        // Only the 100 px of touch, in the 1000 px wide fallback viewport
+        let expected = -0.1 * TAU;
+        assert!(approx_eq!(f32, camera(&app, entity).yaw.unwrap(), expected));
+    }
+
+    #[test]
+    fn pivot_forward_offset_centers_orbit_on_fixed_pivot() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                pivot_forward_offset: 1.0,
+                orbit_smoothness: 0.0,
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.0);
+        let pivot = Vec3::new(0.0, 0.0, -1.0);
+        let mut previous_focus_distance = 0.0;
+        for i in 0..4 {
+            app.world_mut()
+                .get_mut::<PanOrbitCamera>(entity)
+                .unwrap()
+                .target_yaw = i as f32 * 0.5;
+            update(&mut app, 0.1);
+            let transform = *app.world().get::<Transform>(entity).unwrap();
+            // Circles the pivot rather than the focus, while still looking at the focus
+            assert!(approx_eq!(
+                f32,
+                transform.translation.distance(pivot),
+                5.0,
+                epsilon = 0.0001
+            ));
+            assert!(transform
+                .forward()
+                .abs_diff_eq(-transform.translation.normalize(), 0.0001));
+            // So unlike a smaller radius, the distance to the focus changes around the arc
+            let focus_distance = transform.translation.length();
+            if i == 0 {
+                assert!(approx_eq!(f32, focus_distance, 4.0, epsilon = 0.0001));
+            } else {
+                assert!(focus_distance > previous_focus_distance);
             }
+            previous_focus_distance = focus_distance;
+        }
+    }
+
+    #[test]
+    fn pivot_forward_offset_beyond_radius_still_looks_at_focus() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                pivot_forward_offset: 2.0,
+                radius: Some(0.5),
+                target_radius: 0.5,
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.0);
+        let transform = *app.world().get::<Transform>(entity).unwrap();
+        // The camera is beyond the focus, so it turns round to face it
+        assert!(transform.translation.z < 0.0);
+        assert!(transform
+            .forward()
+            .abs_diff_eq(-transform.translation.normalize(), 0.0001));
+        assert!(transform.up().abs_diff_eq(Vec3::Y, 0.0001));
+    }
+
+    #[test]
+    fn inverted_limits_are_swapped() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                yaw_lower_limit: Some(1.0),
+                yaw_upper_limit: Some(-1.0),
+                pitch_lower_limit: Some(0.5),
+                pitch_upper_limit: Some(-0.5),
+                zoom_lower_limit: 10.0,
+                zoom_upper_limit: Some(2.0),
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.01);
+        // Values within the range between the limits aren't pinned to either of them
+        let pan_orbit = camera(&app, entity);
+        assert_eq!(pan_orbit.target_yaw, 0.0);
+        assert_eq!(pan_orbit.target_pitch, 0.0);
+        assert_eq!(pan_orbit.target_radius, 5.0);
+
+        // Values outside the range are clamped to the nearest limit
+        let set_targets = |app: &mut App, yaw: f32, pitch: f32, radius: f32| {
+            let mut pan_orbit = app.world_mut().get_mut::<PanOrbitCamera>(entity).unwrap();
+            pan_orbit.target_yaw = yaw;
+            pan_orbit.target_pitch = pitch;
+            pan_orbit.target_radius = radius;
+        };
+        set_targets(&mut app, 2.0, 1.0, 20.0);
+        update(&mut app, 0.01);
+        let pan_orbit = camera(&app, entity);
+        assert_eq!(pan_orbit.target_yaw, 1.0);
+        assert_eq!(pan_orbit.target_pitch, 0.5);
+        assert_eq!(pan_orbit.target_radius, 10.0);
+        set_targets(&mut app, -2.0, -1.0, 1.0);
+        update(&mut app, 0.01);
+        let pan_orbit = camera(&app, entity);
+        assert_eq!(pan_orbit.target_yaw, -1.0);
+        assert_eq!(pan_orbit.target_pitch, -0.5);
+        assert_eq!(pan_orbit.target_radius, 2.0);
+    }
+
+    #[test]
+    fn fast_zoom_does_not_overshoot_limit() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                zoom_upper_limit: Some(10.0),
+                zoom_smoothness: 0.9,
+                ..initial_camera()
+            },
+        );
+        app.world_mut()
+            .resource_mut::<MouseKeyTracker>()
+            .scroll_line = -1000.0;
+        update(&mut app, 0.01);
+        app.world_mut()
+            .resource_mut::<MouseKeyTracker>()
+            .scroll_line = 0.0;
+        assert_eq!(camera(&app, entity).target_radius, 10.0);
+        let mut previous = 0.0;
+        for _ in 0..500 {
+            update(&mut app, 0.05);
+            let pan_orbit = camera(&app, entity);
+            let radius = pan_orbit.radius.unwrap();
+            assert_eq!(pan_orbit.target_radius, 10.0);
+            assert!(radius <= 10.0 && radius >= previous);
+            previous = radius;
+        }
+        assert_eq!(previous, 10.0);
+    }
+
+    #[test]
+    fn reversing_away_from_yaw_limit_responds_immediately() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                yaw_lower_limit: Some(-0.5),
+                ..initial_camera()
+            },
+        );
+        // Keep dragging into the limit for a while
+        for _ in 0..10 {
+            app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(1000.0, 0.0);
+            update(&mut app, 0.01);
+            assert_eq!(camera(&app, entity).target_yaw, -0.5);
+        }
+        // A small drag back moves away from the limit straight away
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(-10.0, 0.0);
+        update(&mut app, 0.01);
+        assert!(approx_eq!(
+            f32,
+            camera(&app, entity).target_yaw,
+            -0.5 + 10.0 / 1000.0 * TAU,
+            epsilon = 0.0001
+        ));
+    }
+
+    #[test]
+    fn timed_pan_smoothing_arrives_within_duration() {
+        for distance in [1.0, 1000.0] {
+            let mut app = setup_app(pan_orbit_camera);
+            let entity = spawn_camera(
+                &mut app,
+                PanOrbitCamera {
+                    pan_smoothing: PanSmoothing::Timed { duration: 0.5 },
+                    ..initial_camera()
+                },
+            );
+            update(&mut app, 0.0);
+            let target = Vec3::new(distance, 0.0, 0.0);
+            app.world_mut()
+                .get_mut::<PanOrbitCamera>(entity)
+                .unwrap()
+                .target_focus = target;
+            update(&mut app, 0.25);
+            let focus = camera(&app, entity).focus;
+            // Eases out, so more than halfway after half the time
+            assert!(focus.x > distance * 0.5 && focus.x < distance);
+            update(&mut app, 0.25);
+            assert_eq!(camera(&app, entity).focus, target);
+        }
+    }
+
+    #[test]
+    fn orbit_around_cursor_circles_hovered_point() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                orbit_around_cursor: true,
+                ..initial_camera()
+            },
+        );
+        update(&mut app, 0.0);
+        let eye_before = app.world().get::<Transform>(entity).unwrap().translation;
+        // A point off to the side, 3 units in front of the camera
+        let point = Vec3::new(1.0, 1.0, 2.0);
+        app.insert_resource(CursorPivot { point: Some(point) });
+        // Orbit by a tiny amount, so the eye barely moves around the new pivot
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(0.001, 0.0);
+        update(&mut app, 0.01);
+        let pan_orbit = camera(&app, entity);
+        assert_eq!(pan_orbit.focus, point);
+        assert_eq!(pan_orbit.target_focus, point);
+        let distance = eye_before.distance(point);
+        assert!(approx_eq!(
+            f32,
+            pan_orbit.target_radius,
+            distance,
+            epsilon = 0.0001
+        ));
+        let transform = *app.world().get::<Transform>(entity).unwrap();
+        assert!(transform.translation.abs_diff_eq(eye_before, 0.001));
+
+        // Orbiting, with the default smoothing, circles the point, which stays in the center of
+        // the view
+        for _ in 0..10 {
+            app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(-50.0, 20.0);
+            update(&mut app, 0.01);
+            let transform = *app.world().get::<Transform>(entity).unwrap();
+            let screen_position = camera(&app, entity)
+                .focus_screen_position(Vec2::splat(100.0), &Projection::default())
+                .unwrap();
+            assert!(screen_position.abs_diff_eq(Vec2::splat(50.0), 0.001));
+            assert!(transform
+                .forward()
+                .abs_diff_eq((point - transform.translation).normalize(), 0.0001));
+            assert!(approx_eq!(
+                f32,
+                transform.translation.distance(point),
+                distance,
+                epsilon = 0.001
+            ));
+        }
+        let eye = app.world().get::<Transform>(entity).unwrap().translation;
+        assert!(eye.distance(eye_before) > 0.5);
+    }
+
+    #[test]
+    fn projection_switch_preserves_apparent_size() {
+        use bevy::render::camera::{CameraProjection, ScalingMode};
+
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = app
+            .world_mut()
+            .spawn((
+                PanOrbitCamera {
+                    projection_switch: ProjectionSwitch::PreserveApparentSize,
+                    ..initial_camera()
+                },
+                Projection::default(),
+            ))
+            .id();
+        // The height on screen, in normalized device coordinates, of a unit above the focus
+        let projected_height = |app: &mut App| {
+            let world = app.world_mut();
+            let mut projection = world.get::<Projection>(entity).unwrap().clone();
+            // Bring the orthographic area up to date with the scale, as the renderer would
+            projection.update(1000.0, 1000.0);
+            let transform = world.get::<Transform>(entity).unwrap();
+            let clip = projection.get_clip_from_view()
+                * transform.compute_matrix().inverse()
+                * Vec3::Y.extend(1.0);
+            clip.y / clip.w
+        };
+        update(&mut app, 0.01);
+        let perspective_height = projected_height(&mut app);
+
+        let mut ortho = OrthographicProjection {
+            scaling_mode: ScalingMode::FixedVertical {
+                viewport_height: 1.0,
+            },
+            ..OrthographicProjection::default_3d()
+        };
+        ortho.update(1000.0, 1000.0);
+        let perspective = std::mem::replace(
+            &mut *app.world_mut().get_mut::<Projection>(entity).unwrap(),
+            Projection::Orthographic(ortho),
+        );
+        update(&mut app, 0.01);
+        let ortho_height = projected_height(&mut app);
+        assert!(approx_eq!(
+            f32,
+            ortho_height,
+            perspective_height,
+            epsilon = 0.0001
+        ));
+
+        // And back again
+        *app.world_mut().get_mut::<Projection>(entity).unwrap() = perspective;
+        update(&mut app, 0.01);
+        assert!(approx_eq!(
+            f32,
+            projected_height(&mut app),
+            perspective_height,
+            epsilon = 0.0001
+        ));
+        assert!(approx_eq!(
+            f32,
+            camera(&app, entity).radius.unwrap(),
+            5.0,
+            epsilon = 0.0001
+        ));
+    }
+
+    fn camera_at_pole(pole_behavior: PoleBehavior) -> PanOrbitCamera {
+        PanOrbitCamera {
+            pitch: Some(PI / 2.0),
+            target_pitch: PI / 2.0,
+            pole_behavior,
+            ..initial_camera()
         }
     }
+
+    #[test]
+    fn free_pole_behavior_yaws_at_pole() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(&mut app, camera_at_pole(PoleBehavior::Free));
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(100.0, 0.0);
+        update(&mut app, 0.01);
+        let expected = -100.0 / 1000.0 * TAU;
+        assert!(approx_eq!(f32, camera(&app, entity).target_yaw, expected));
+    }
+
+    #[test]
+    fn freeze_yaw_ignores_horizontal_orbit_only_at_pole() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(&mut app, camera_at_pole(PoleBehavior::FreezeYaw));
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(100.0, 0.0);
+        update(&mut app, 0.01);
+        assert_eq!(camera(&app, entity).target_yaw, 0.0);
+
+        // Away from the pole, the yaw changes as usual
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(0.0, -100.0);
+        update(&mut app, 0.01);
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = Vec2::new(100.0, 0.0);
+        update(&mut app, 0.01);
+        let expected = -100.0 / 1000.0 * TAU;
+        assert!(approx_eq!(f32, camera(&app, entity).target_yaw, expected));
+    }
+
+    #[test]
+    fn no_orbit_or_pan_without_sizes_if_fallback_disabled() {
+        let mut app = setup_app(pan_orbit_camera);
+        let entity = spawn_camera(
+            &mut app,
+            PanOrbitCamera {
+                fallback_viewport_size: None,
+                ..initial_camera()
+            },
+        );
+        let mut tracker = app.world_mut().resource_mut::<MouseKeyTracker>();
+        tracker.orbit = Vec2::new(100.0, 0.0);
+        tracker.pan = Vec2::new(100.0, 0.0);
+        update(&mut app, 0.01);
+        assert_eq!(camera(&app, entity).target_yaw, 0.0);
+        assert_eq!(camera(&app, entity).target_focus, Vec3::ZERO);
+    }
 }
@@ -0,0 +1,129 @@
+use bevy::prelude::*;
+
+use crate::{ActiveCameraData, CardinalView, PanOrbitCamera};
+
+/// Optional plugin that adds Blender style numpad shortcuts to the active `PanOrbitCamera`, for
+/// snapping to the cardinal views:
+///  - Numpad 1: front (Ctrl for back)
+///  - Numpad 3: right (Ctrl for left)
+///  - Numpad 7: top (Ctrl for bottom)
+///
+/// The camera smoothly orbits to the new view, according to `orbit_smoothness`. Views are relative
+/// to the camera's `axis`. Requires `PanOrbitCameraPlugin`.
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_panorbit_camera::{NumpadViewsPlugin, PanOrbitCameraPlugin};
+/// fn main() {
+///     App::new()
+///         .add_plugins(DefaultPlugins)
+///         .add_plugins((PanOrbitCameraPlugin, NumpadViewsPlugin))
+///         .run();
+/// }
+/// ```
+pub struct NumpadViewsPlugin;
+
+impl Plugin for NumpadViewsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, numpad_views);
+    }
+}
+
+fn numpad_views(
+    key_input: Res<ButtonInput<KeyCode>>,
+    active_cam: Res<ActiveCameraData>,
+    mut orbit_cameras: Query<&mut PanOrbitCamera>,
+) {
+    let Some(mut pan_orbit) = active_cam
+        .entity
+        .and_then(|entity| orbit_cameras.get_mut(entity).ok())
+    else {
+        return;
+    };
+    if !pan_orbit.enabled {
+        return;
+    }
+    let ctrl = key_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    let view = if key_input.just_pressed(KeyCode::Numpad1) {
+        Some(if ctrl {
+            CardinalView::Back
+        } else {
+            CardinalView::Front
+        })
+    } else if key_input.just_pressed(KeyCode::Numpad3) {
+        Some(if ctrl {
+            CardinalView::Left
+        } else {
+            CardinalView::Right
+        })
+    } else if key_input.just_pressed(KeyCode::Numpad7) {
+        Some(if ctrl {
+            CardinalView::Bottom
+        } else {
+            CardinalView::Top
+        })
+    } else {
+        None
+    };
+    if let Some(view) = view {
+        pan_orbit.set_view(view);
+    }
+}
+
+#[cfg(test)]
+mod numpad_views_tests {
+    use super::*;
+    use float_cmp::approx_eq;
+    use std::f32::consts::PI;
+
+    fn setup_app() -> (App, Entity) {
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<ActiveCameraData>()
+            .add_systems(Update, numpad_views);
+        let entity = app.world_mut().spawn(PanOrbitCamera::default()).id();
+        app.world_mut().resource_mut::<ActiveCameraData>().entity = Some(entity);
+        (app, entity)
+    }
+
+    fn press(app: &mut App, keys: &[KeyCode]) {
+        let mut key_input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        key_input.reset_all();
+        for key in keys {
+            key_input.press(*key);
+        }
+        app.update();
+    }
+
+    fn assert_target(app: &App, entity: Entity, yaw: f32, pitch: f32) {
+        let pan_orbit = app.world().get::<PanOrbitCamera>(entity).unwrap();
+        assert!(approx_eq!(f32, pan_orbit.target_yaw, yaw, epsilon = 0.0001));
+        assert!(approx_eq!(
+            f32,
+            pan_orbit.target_pitch,
+            pitch,
+            epsilon = 0.0001
+        ));
+    }
+
+    #[test]
+    fn numpad_keys_snap_to_views() {
+        let (mut app, entity) = setup_app();
+        press(&mut app, &[KeyCode::Numpad7]);
+        assert_target(&app, entity, 0.0, PI / 2.0);
+        press(&mut app, &[KeyCode::ControlLeft, KeyCode::Numpad7]);
+        assert_target(&app, entity, 0.0, -PI / 2.0);
+        press(&mut app, &[KeyCode::Numpad3]);
+        assert_target(&app, entity, PI / 2.0, 0.0);
+        press(&mut app, &[KeyCode::ControlRight, KeyCode::Numpad1]);
+        assert_target(&app, entity, PI, 0.0);
+    }
+
+    #[test]
+    fn inactive_camera_is_unaffected() {
+        let (mut app, entity) = setup_app();
+        app.world_mut().resource_mut::<ActiveCameraData>().entity = None;
+        press(&mut app, &[KeyCode::Numpad7]);
+        assert_target(&app, entity, 0.0, 0.0);
+    }
+}
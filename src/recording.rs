@@ -0,0 +1,288 @@
+use bevy::prelude::*;
+
+use crate::input::MouseKeyTracker;
+use crate::{ActiveCameraData, CameraInput, FALLBACK_DT};
+
+/// The orbit, pan, and zoom input for one frame of a `CameraInputRecording`, in the same units as
+/// `CameraInput`.
+#[derive(Reflect, Default, Debug, Copy, Clone, PartialEq)]
+pub struct CameraInputFrame {
+    /// When this input happened, in seconds since the recording started.
+    pub time: f32,
+    /// Orbit, in pixels.
+    pub orbit: Vec2,
+    /// Pan, in pixels.
+    pub pan: Vec2,
+    /// Line-based zoom, in scroll wheel lines, where positive zooms in.
+    pub scroll_line: f32,
+    /// Pixel-based zoom, e.g. from a trackpad, in the same units as `scroll_line`.
+    pub scroll_pixel: f32,
+}
+
+/// What a `CameraInputRecording` is currently doing
+#[derive(Reflect, Default, Debug, Copy, Clone, PartialEq)]
+enum RecordingState {
+    #[default]
+    Idle,
+    Recording {
+        start: f32,
+    },
+    Playing {
+        start: f32,
+        next_frame: usize,
+    },
+}
+
+/// Insert this resource to record the mouse and `CameraInput` input that moves the active
+/// `PanOrbitCamera`, and replay it later, e.g. for automated tours or reproducible tests. While
+/// playing, the recorded input is used instead of live mouse and `CameraInput` input. Touch,
+/// keyboard, `SixDofInput`, and the joystick axes of `CameraInput` are not recorded.
+///
+/// Frames are replayed according to their timestamps, so the same input is applied over the same
+/// time, whatever the frame rate. For exactly the same camera movement every time, also use
+/// `DisableSmoothing`. The recording only drives the active camera, so for a tour you'll probably
+/// want to choose it with `ExplicitActiveCamera`.
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_panorbit_camera::CameraInputRecording;
+/// fn toggle_recording(
+///     key_input: Res<ButtonInput<KeyCode>>,
+///     mut recording: ResMut<CameraInputRecording>,
+/// ) {
+///     if key_input.just_pressed(KeyCode::KeyR) {
+///         recording.start_recording();
+///     }
+///     if key_input.just_pressed(KeyCode::KeyP) {
+///         recording.play();
+///     }
+/// }
+/// ```
+#[derive(Resource, Reflect, Default, Debug, Clone, PartialEq)]
+pub struct CameraInputRecording {
+    frames: Vec<CameraInputFrame>,
+    state: RecordingState,
+}
+
+impl CameraInputRecording {
+    /// Clear any previous recording and start recording, from the next frame.
+    pub fn start_recording(&mut self) {
+        self.frames.clear();
+        self.state = RecordingState::Recording { start: f32::NAN };
+    }
+
+    /// Stop recording or playing.
+    pub fn stop(&mut self) {
+        self.state = RecordingState::Idle;
+    }
+
+    /// Replace the recording with `frames`, e.g. a recording that was saved earlier. Frames should
+    /// be in order of `time`. Stops recording or playing.
+    pub fn load(&mut self, frames: impl IntoIterator<Item = CameraInputFrame>) {
+        self.frames = frames.into_iter().collect();
+        self.state = RecordingState::Idle;
+    }
+
+    /// Start playing the recording from the beginning, from the next frame. Playing stops by
+    /// itself after the last frame.
+    pub fn play(&mut self) {
+        self.state = RecordingState::Playing {
+            start: f32::NAN,
+            next_frame: 0,
+        };
+    }
+
+    /// The recorded frames, e.g. for saving the recording.
+    pub fn frames(&self) -> &[CameraInputFrame] {
+        &self.frames
+    }
+
+    /// Whether input is being recorded.
+    pub fn is_recording(&self) -> bool {
+        matches!(self.state, RecordingState::Recording { .. })
+    }
+
+    /// Whether the recording is being played.
+    pub fn is_playing(&self) -> bool {
+        matches!(self.state, RecordingState::Playing { .. })
+    }
+}
+
+/// Records input into, or replays input from, `CameraInputRecording`
+pub fn record_camera_input(
+    recording: Option<ResMut<CameraInputRecording>>,
+    mut mouse_key_tracker: ResMut<MouseKeyTracker>,
+    mut camera_input: ResMut<CameraInput>,
+    active_cam: Res<ActiveCameraData>,
+    time: Option<Res<Time>>,
+    mut fallback_elapsed: Local<f32>,
+) {
+    let Some(mut recording) = recording else {
+        return;
+    };
+    // Without a `Time` resource, count each frame as lasting `FALLBACK_DT`
+    *fallback_elapsed += FALLBACK_DT;
+    let now = time.map_or(*fallback_elapsed, |time| time.elapsed_secs());
+    let recording = &mut *recording;
+    match &mut recording.state {
+        RecordingState::Idle => {}
+        RecordingState::Recording { start } => {
+            if start.is_nan() {
+                *start = now;
+            }
+            let mut frame = CameraInputFrame {
+                time: now - *start,
+                orbit: mouse_key_tracker.orbit,
+                pan: mouse_key_tracker.pan,
+                scroll_line: mouse_key_tracker.scroll_line,
+                scroll_pixel: mouse_key_tracker.scroll_pixel,
+            };
+            if camera_input
+                .camera
+                .is_none_or(|camera| active_cam.entity == Some(camera))
+            {
+                frame.orbit += camera_input.orbit;
+                frame.pan += camera_input.pan;
+                frame.scroll_line += camera_input.zoom;
+            }
+            if frame.orbit != Vec2::ZERO
+                || frame.pan != Vec2::ZERO
+                || frame.scroll_line != 0.0
+                || frame.scroll_pixel != 0.0
+            {
+                recording.frames.push(frame);
+            }
+        }
+        RecordingState::Playing { start, next_frame } => {
+            if start.is_nan() {
+                *start = now;
+            }
+            // Apply every frame that's due, so nothing is lost if this frame took longer
+            let elapsed = now - *start;
+            let mut frame = CameraInputFrame::default();
+            while let Some(due) = recording
+                .frames
+                .get(*next_frame)
+                .filter(|due| due.time <= elapsed)
+            {
+                frame.orbit += due.orbit;
+                frame.pan += due.pan;
+                frame.scroll_line += due.scroll_line;
+                frame.scroll_pixel += due.scroll_pixel;
+                *next_frame += 1;
+            }
+            mouse_key_tracker.orbit = frame.orbit;
+            mouse_key_tracker.pan = frame.pan;
+            mouse_key_tracker.scroll_line = frame.scroll_line;
+            mouse_key_tracker.scroll_pixel = frame.scroll_pixel;
+            camera_input.orbit = Vec2::ZERO;
+            camera_input.pan = Vec2::ZERO;
+            camera_input.zoom = 0.0;
+            if *next_frame >= recording.frames.len() {
+                recording.state = RecordingState::Idle;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn setup_app() -> App {
+        let mut app = App::new();
+        app.init_resource::<MouseKeyTracker>()
+            .init_resource::<CameraInput>()
+            .init_resource::<ActiveCameraData>()
+            .init_resource::<Time>()
+            .init_resource::<CameraInputRecording>()
+            .add_systems(Update, record_camera_input);
+        app
+    }
+
+    fn update(app: &mut App, orbit: Vec2) {
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(0.1));
+        app.world_mut().resource_mut::<MouseKeyTracker>().orbit = orbit;
+        app.update();
+    }
+
+    #[test]
+    fn records_and_replays_input() {
+        let mut app = setup_app();
+        app.world_mut()
+            .resource_mut::<CameraInputRecording>()
+            .start_recording();
+        update(&mut app, Vec2::new(1.0, 0.0));
+        update(&mut app, Vec2::ZERO);
+        update(&mut app, Vec2::new(0.0, 2.0));
+        app.world_mut()
+            .resource_mut::<CameraInputRecording>()
+            .stop();
+        let recording = app.world().resource::<CameraInputRecording>();
+        // Frames without input aren't stored
+        assert_eq!(recording.frames().len(), 2);
+        let gap = recording.frames()[1].time - recording.frames()[0].time;
+        assert!((gap - 0.2).abs() < 1e-4);
+
+        // Live input is replaced by the recorded input
+        app.world_mut()
+            .resource_mut::<CameraInputRecording>()
+            .play();
+        let mut replayed = Vec::new();
+        for _ in 0..3 {
+            update(&mut app, Vec2::new(100.0, 100.0));
+            replayed.push(app.world().resource::<MouseKeyTracker>().orbit);
+        }
+        assert_eq!(
+            replayed,
+            [Vec2::new(1.0, 0.0), Vec2::ZERO, Vec2::new(0.0, 2.0)]
+        );
+        assert!(!app.world().resource::<CameraInputRecording>().is_playing());
+        // Live input again once finished
+        update(&mut app, Vec2::new(100.0, 100.0));
+        assert_eq!(
+            app.world().resource::<MouseKeyTracker>().orbit,
+            Vec2::new(100.0, 100.0)
+        );
+    }
+
+    #[test]
+    fn replay_catches_up_on_long_frames() {
+        let mut app = setup_app();
+        app.world_mut()
+            .resource_mut::<CameraInputRecording>()
+            .load([
+                CameraInputFrame {
+                    time: 0.0,
+                    orbit: Vec2::new(1.0, 0.0),
+                    ..default()
+                },
+                CameraInputFrame {
+                    time: 0.05,
+                    orbit: Vec2::new(2.0, 0.0),
+                    ..default()
+                },
+                CameraInputFrame {
+                    time: 0.1,
+                    orbit: Vec2::new(4.0, 0.0),
+                    ..default()
+                },
+            ]);
+        app.world_mut()
+            .resource_mut::<CameraInputRecording>()
+            .play();
+        update(&mut app, Vec2::ZERO);
+        assert_eq!(
+            app.world().resource::<MouseKeyTracker>().orbit,
+            Vec2::new(1.0, 0.0)
+        );
+        update(&mut app, Vec2::ZERO);
+        assert_eq!(
+            app.world().resource::<MouseKeyTracker>().orbit,
+            Vec2::new(6.0, 0.0)
+        );
+    }
+}
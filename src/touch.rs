@@ -1,6 +1,10 @@
-use bevy::input::touch::Touch;
+use bevy::input::touch::{Touch, TouchInput, TouchPhase};
 use bevy::math::Vec2;
 use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy::window::PrimaryWindow;
+
+use crate::{input_window, viewport_contains, ActiveCameraData, PanOrbitCamera, FALLBACK_DT};
 
 /// The control scheme to use for touch input. Given that some touch gestures don't make sense
 /// being changed (e.g. pinch to zoom), there is just a set if different schemes rather than
@@ -59,9 +63,30 @@ pub struct TwoFingerGestures {
 pub struct TouchTracker {
     curr_pressed: (Option<Touch>, Option<Touch>),
     prev_pressed: (Option<Touch>, Option<Touch>),
+    /// The active camera's `touch_controls` when the current touch started
+    controls: Option<TouchControls>,
+    /// How long two fingers had been down at the start of this frame's two finger gestures
+    pinch_time: f32,
+    /// How long two fingers have been down, up to the end of this frame
+    two_finger_time: f32,
 }
 
 impl TouchTracker {
+    /// The control scheme for the current touch, which is the active camera's `touch_controls`
+    /// when the touch started. It stays the same until every finger is lifted, so changing
+    /// `touch_controls` mid-gesture doesn't suddenly turn an orbit into a pan. `None` while there
+    /// are no touches.
+    pub(crate) fn controls(&self) -> Option<TouchControls> {
+        self.controls
+    }
+
+    /// How long, in seconds, the current two finger touch had been going at the start of this
+    /// frame's `TwoFingerGestures`, so it's `0.0` for the first frame of a pinch. Used to ignore
+    /// the pinch for `PanOrbitCamera::touch_pinch_delay` while the fingers settle.
+    pub(crate) fn pinch_time(&self) -> f32 {
+        self.pinch_time
+    }
+
     /// Calculate and return mobile gesture data for this frame
     pub fn get_touch_gestures(&self) -> TouchGestures {
         // The below matches only match when the previous and current frames have the same number
@@ -130,14 +155,114 @@ impl TouchTracker {
     }
 }
 
-/// Read touch input and save it in TouchTracker resource for easy consumption by the main system
-pub fn touch_tracker(touches: Res<Touches>, mut touch_tracker: ResMut<TouchTracker>) {
-    let pressed: Vec<&Touch> = touches.iter().collect();
+#[cfg(test)]
+impl TouchTracker {
+    /// A tracker where one finger moved by `motion` since the previous frame
+    pub(crate) fn one_finger_drag(motion: Vec2) -> Self {
+        let touch = |position| {
+            Touch::from(&TouchInput {
+                phase: TouchPhase::Moved,
+                position,
+                window: Entity::PLACEHOLDER,
+                force: None,
+                id: 0,
+            })
+        };
+        Self {
+            curr_pressed: (Some(touch(motion)), None),
+            prev_pressed: (Some(touch(Vec2::ZERO)), None),
+            controls: None,
+            pinch_time: 0.0,
+            two_finger_time: 0.0,
+        }
+    }
+
+    /// A tracker where two fingers moved apart by `pinch` since the previous frame, in a pinch
+    /// that had been going for `pinch_time` seconds
+    pub(crate) fn two_finger_pinch(pinch: f32, pinch_time: f32) -> Self {
+        let touch = |id, x| {
+            Touch::from(&TouchInput {
+                phase: TouchPhase::Moved,
+                position: Vec2::new(x, 0.0),
+                window: Entity::PLACEHOLDER,
+                force: None,
+                id,
+            })
+        };
+        Self {
+            curr_pressed: (
+                Some(touch(0, -pinch / 2.0)),
+                Some(touch(1, 100.0 + pinch / 2.0)),
+            ),
+            prev_pressed: (Some(touch(0, 0.0)), Some(touch(1, 100.0))),
+            controls: None,
+            pinch_time,
+            two_finger_time: pinch_time,
+        }
+    }
+
+    /// The same tracker, for a touch that started with the given control scheme
+    pub(crate) fn with_controls(self, controls: TouchControls) -> Self {
+        Self {
+            controls: Some(controls),
+            ..self
+        }
+    }
+}
+
+/// Read touch input and save it in TouchTracker resource for easy consumption by the main system.
+/// Only touches that started inside the active camera's viewport, in its input window, are used,
+/// like the mouse cursor check when choosing the active camera, so touches on another viewport or
+/// window don't move it. `Touches` doesn't record which window a touch is in, so that's taken from
+/// the `TouchInput` event that started it. The active camera's `touch_controls` are recorded when a
+/// touch starts and kept until every finger is lifted.
+#[allow(clippy::too_many_arguments)]
+pub fn touch_tracker(
+    time: Option<Res<Time>>,
+    touches: Res<Touches>,
+    mut touch_events: EventReader<TouchInput>,
+    mut touch_windows: Local<HashMap<u64, Entity>>,
+    mut touch_tracker: ResMut<TouchTracker>,
+    active_cam: Res<ActiveCameraData>,
+    orbit_cameras: Query<(&Camera, &PanOrbitCamera)>,
+    primary_windows: Query<Entity, With<PrimaryWindow>>,
+    windows: Query<&Window>,
+) {
+    let active_camera = active_cam
+        .entity
+        .and_then(|entity| orbit_cameras.get(entity).ok());
+    let active_viewport = active_camera.and_then(|(camera, pan_orbit)| {
+        let (window, _, renders_to_window) =
+            input_window(camera, pan_orbit, &primary_windows, &windows)?;
+        Some((camera, window, renders_to_window))
+    });
+    for event in touch_events.read() {
+        if event.phase == TouchPhase::Started {
+            touch_windows.insert(event.id, event.window);
+        }
+    }
+    touch_windows.retain(|id, _| touches.get_pressed(*id).is_some());
+    // Use where the touch started, so a drag that leaves the viewport keeps going, like the mouse
+    let pressed: Vec<&Touch> = touches
+        .iter()
+        .filter(|touch| {
+            active_viewport.is_none_or(|(camera, window, renders_to_window)| {
+                touch_windows
+                    .get(&touch.id())
+                    .is_none_or(|touch_window| *touch_window == window)
+                    && viewport_contains(camera, renders_to_window, touch.start_position())
+            })
+        })
+        .collect();
 
     match pressed.len() {
         0 => {
             touch_tracker.curr_pressed = (None, None);
             touch_tracker.prev_pressed = (None, None);
+            touch_tracker.controls = None;
+            touch_tracker.pinch_time = 0.0;
+            touch_tracker.two_finger_time = 0.0;
+            return;
         }
         1 => {
             let touch: &Touch = pressed.first().unwrap();
@@ -147,9 +272,21 @@ pub fn touch_tracker(touches: Res<Touches>, mut touch_tracker: ResMut<TouchTrack
         2 => {
             let touch1: &Touch = pressed.first().unwrap();
             let touch2: &Touch = pressed.last().unwrap();
+            // The first frame with two fingers has no gestures yet, so start timing from there
+            if matches!(touch_tracker.curr_pressed, (Some(_), Some(_))) {
+                let dt = time.map_or(FALLBACK_DT, |time| time.delta_secs());
+                touch_tracker.pinch_time = touch_tracker.two_finger_time;
+                touch_tracker.two_finger_time += dt;
+            } else {
+                touch_tracker.pinch_time = 0.0;
+                touch_tracker.two_finger_time = 0.0;
+            }
             touch_tracker.prev_pressed = touch_tracker.curr_pressed;
             touch_tracker.curr_pressed = (Some(*touch1), Some(*touch2));
         }
         _ => {}
     }
+    if touch_tracker.controls.is_none() {
+        touch_tracker.controls = active_camera.map(|(_, pan_orbit)| pan_orbit.touch_controls);
+    }
 }
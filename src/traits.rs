@@ -2,7 +2,8 @@ pub trait OptionalClamp {
     type N: PartialOrd;
 
     /// Clamp a value between two other values. The other values are optional. If both
-    /// `min` and `max` are `None`, then the return value is equal to `self`.
+    /// `min` and `max` are `None`, then the return value is equal to `self`. If `min` is greater
+    /// than `max`, they are swapped, so the value is still clamped to the range between them.
     fn clamp_optional(&self, min: Option<Self::N>, max: Option<Self::N>) -> Self::N;
 }
 
@@ -10,6 +11,10 @@ impl OptionalClamp for f32 {
     type N = f32;
 
     fn clamp_optional(&self, min: Option<Self::N>, max: Option<Self::N>) -> Self::N {
+        let (min, max) = match (min, max) {
+            (Some(min), Some(max)) if min > max => (Some(max), Some(min)),
+            limits => limits,
+        };
         let mut new_val = *self;
         if let Some(min) = min {
             new_val = f32::max(new_val, min);
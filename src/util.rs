@@ -1,6 +1,9 @@
 use bevy::prelude::*;
+use std::f32::consts::{PI, TAU};
 
-const EPSILON: f32 = 0.001;
+/// The threshold used for snapping and comparing values, in radians for angles, or world units
+/// (scaled by `PanOrbitCamera::world_scale`) for distances
+pub const EPSILON: f32 = 0.001;
 
 pub fn calculate_from_translation_and_focus(translation: Vec3, focus: Vec3) -> (f32, f32, f32) {
     let comp_vec = translation - focus;
@@ -17,44 +20,305 @@ pub fn calculate_from_translation_and_focus(translation: Vec3, focus: Vec3) -> (
     (yaw, pitch, radius)
 }
 
-/// Update `transform` based on yaw, pitch, and the camera's focus and radius
+/// Update `transform` based on yaw, pitch, and the camera's focus, radius, and axis
+#[allow(clippy::too_many_arguments)]
 pub fn update_orbit_transform(
     yaw: f32,
     pitch: f32,
+    radius: f32,
+    focus: Vec3,
+    axis: [Vec3; 3],
+    pivot_forward_offset: f32,
+    transform: &mut Transform,
+    projection: &mut Projection,
+) {
+    update_orbit_transform_from_rotation(
+        axis_rotation(axis) * rotation_from_yaw_pitch(yaw, pitch),
+        radius,
+        focus,
+        pivot_offset(axis, pivot_forward_offset),
+        transform,
+        projection,
+    );
+}
+
+/// Where the orbit is centered, relative to `focus`, for `PanOrbitCamera::pivot_forward_offset`.
+/// This is along the view direction at zero yaw and pitch, so it stays put while orbiting.
+pub fn pivot_offset(axis: [Vec3; 3], pivot_forward_offset: f32) -> Vec3 {
+    axis_rotation(axis) * Vec3::NEG_Z * pivot_forward_offset
+}
+
+/// Update `transform` based on a rotation around `focus`, and the camera's focus and radius.
+/// The orbit is centered at `focus + pivot_offset`, and if that's not `focus`, the camera is turned
+/// by the smallest rotation that makes it look at `focus` from wherever it ends up on the orbit.
+pub fn update_orbit_transform_from_rotation(
+    rotation: Quat,
     mut radius: f32,
     focus: Vec3,
+    pivot_offset: Vec3,
     transform: &mut Transform,
     projection: &mut Projection,
 ) {
     let mut new_transform = Transform::IDENTITY;
     if let Projection::Orthographic(ref mut p) = *projection {
         p.scale = radius;
-        // (near + far) / 2.0 ensures that objects near `focus` are not clipped
-        radius = (p.near + p.far) / 2.0;
+        radius = ortho_focus_distance(p);
+    }
+    new_transform.rotation = rotation;
+    new_transform.translation +=
+        focus + pivot_offset + new_transform.rotation * Vec3::new(0.0, 0.0, radius);
+    if let Some(to_focus) = (focus - new_transform.translation)
+        .try_normalize()
+        .filter(|_| pivot_offset != Vec3::ZERO)
+    {
+        // Turn the least amount, so there's no more roll than the orbit already has. When the
+        // camera is right in front of the focus, turn round about its up axis, so it stays upright
+        let forward = rotation * Vec3::NEG_Z;
+        let turn = if forward.dot(to_focus) < EPSILON - 1.0 {
+            Quat::from_axis_angle(rotation * Vec3::Y, PI)
+        } else {
+            Quat::from_rotation_arc(forward, to_focus)
+        };
+        new_transform.rotation = turn * rotation;
     }
-    new_transform.rotation *= Quat::from_rotation_y(yaw) * Quat::from_rotation_x(-pitch);
-    new_transform.translation += focus + new_transform.rotation * Vec3::new(0.0, 0.0, radius);
     *transform = new_transform;
 }
 
+/// Turn `transform` to look at `point`, keeping its up direction as close as possible. Does nothing
+/// if `point` is where the camera is, as there's no direction to look in.
+pub fn look_at_point(transform: &mut Transform, point: Vec3) {
+    if point != transform.translation {
+        let up = transform.up();
+        transform.look_at(point, up);
+    }
+}
+
+/// The height of the view at the focus per unit of `radius`. For perspective projection `radius`
+/// is the distance to the focus, and for orthographic it's the scale. Comparing this before and
+/// after a projection switch gives the factor that keeps things at the focus the same size.
+pub fn view_height_per_radius(projection: &Projection) -> f32 {
+    match projection {
+        Projection::Perspective(p) => 2.0 * (p.fov / 2.0).tan(),
+        Projection::Orthographic(p) => p.area.height() / p.scale,
+    }
+}
+
+/// The radius at which a sphere of `bounds_radius` around the focus fits in view, leaving a margin
+/// of `padding` (as a fraction of the viewport) on each side. For orthographic projection, this is
+/// the scale. Returns `None` if the projection has no size.
+pub fn radius_to_fit(bounds_radius: f32, projection: &Projection, padding: f32) -> Option<f32> {
+    // The fraction of the viewport that the bounds can take up
+    let fill = 1.0 - 2.0 * padding.clamp(0.0, 0.49);
+    match projection {
+        Projection::Perspective(p) => {
+            let half_fov_y = p.fov / 2.0;
+            let half_height = half_fov_y.tan() * fill;
+            let half_fov = half_height
+                .atan()
+                .min((half_height * p.aspect_ratio).atan());
+            (half_fov > 0.0).then(|| bounds_radius / half_fov.sin())
+        }
+        Projection::Orthographic(p) => {
+            // The area is proportional to the scale, so find the area at a scale of 1
+            let unit_size = (p.area.size() / p.scale).min_element() * fill;
+            (unit_size > 0.0).then(|| 2.0 * bounds_radius / unit_size)
+        }
+    }
+}
+
+/// Where `ndc` (normalized device coordinates, from -1 to 1 with y up) lands on the plane through
+/// the focus that faces the camera, as an offset from the focus in view space. For perspective
+/// projection `radius` is the distance to the focus, and for orthographic it's the scale.
+pub fn focus_plane_offset(projection: &Projection, ndc: Vec2, radius: f32) -> Vec2 {
+    match projection {
+        Projection::Perspective(p) => {
+            let half_height = (p.fov / 2.0).tan() * radius;
+            ndc * Vec2::new(half_height * p.aspect_ratio, half_height)
+        }
+        Projection::Orthographic(p) if p.scale > 0.0 => {
+            // The area is for the current scale, so rescale it to `radius`
+            (p.area.center() + p.area.half_size() * ndc) * radius / p.scale
+        }
+        Projection::Orthographic(_) => Vec2::ZERO,
+    }
+}
+
+/// The distance from an orthographic camera to its focus. Halfway between the near and far planes
+/// ensures that objects near the focus are not clipped.
+pub fn ortho_focus_distance(projection: &OrthographicProjection) -> f32 {
+    (projection.near + projection.far) / 2.0
+}
+
+/// The rotation around the focus that corresponds to the given yaw and pitch
+pub fn rotation_from_yaw_pitch(yaw: f32, pitch: f32) -> Quat {
+    Quat::from_rotation_y(yaw) * Quat::from_rotation_x(-pitch)
+}
+
+/// Calculate yaw and pitch from a rotation around the focus. Any roll is discarded.
+pub fn yaw_pitch_from_rotation(rotation: Quat) -> (f32, f32) {
+    yaw_pitch_from_direction(rotation * Vec3::Z)
+}
+
+/// Calculate yaw and pitch from the direction from the focus to the camera
+pub fn yaw_pitch_from_direction(dir: Vec3) -> (f32, f32) {
+    let yaw = dir.x.atan2(dir.z);
+    let pitch = dir.normalize_or_zero().y.clamp(-1.0, 1.0).asin();
+    (yaw, pitch)
+}
+
+/// The rotation that maps the global X, Y, and Z axes onto `axis`
+pub fn axis_rotation(axis: [Vec3; 3]) -> Quat {
+    Quat::from_mat3(&Mat3::from_cols(axis[0], axis[1], axis[2]))
+}
+
+/// The axes that `rotation` maps the global X, Y, and Z axes onto
+pub fn axis_from_rotation(rotation: Quat) -> [Vec3; 3] {
+    [rotation * Vec3::X, rotation * Vec3::Y, rotation * Vec3::Z]
+}
+
+/// Offset `angle` by a multiple of a full turn, so that it's as close as possible to `reference`
+pub fn wrap_angle_near(angle: f32, reference: f32) -> f32 {
+    // Leave angles that are already close enough alone, rather than adding rounding errors
+    if (angle - reference).abs() < PI {
+        return angle;
+    }
+    reference + (angle - reference + PI).rem_euclid(TAU) - PI
+}
+
+/// Whether `pitch` has the camera looking (almost) straight up or down
+pub fn is_near_pole(pitch: f32) -> bool {
+    let from_pole = (pitch - PI / 2.0).rem_euclid(PI);
+    !(EPSILON..=PI - EPSILON).contains(&from_pole)
+}
+
+/// Moves `pitch` out of the keep-out band between `lower` and `upper`. Moving into the band from
+/// `from` continues across it to the far edge, so orbiting skips over the band rather than stopping
+/// inside it. If `from` is also inside the band, `pitch` moves to the nearer edge.
+pub fn skip_pitch_keepout(from: f32, pitch: f32, (lower, upper): (f32, f32)) -> f32 {
+    if pitch <= lower || pitch >= upper {
+        pitch
+    } else if from <= lower || (from < upper && pitch - lower >= upper - pitch) {
+        upper
+    } else {
+        lower
+    }
+}
+
+/// Rotates `rotation` around its view direction so that the roll, i.e. the tilt of the horizon
+/// relative to `Vec3::Y`, is at most `max_roll`. Looking straight up or down, the roll is
+/// undefined, so the rotation is returned unchanged.
+pub fn clamp_roll(rotation: Quat, max_roll: f32) -> Quat {
+    let forward = rotation * Vec3::NEG_Z;
+    let right = rotation * Vec3::X;
+    let level_right = forward.cross(Vec3::Y);
+    if level_right.length_squared() < EPSILON {
+        return rotation;
+    }
+    let level_right = level_right.normalize();
+    let roll = level_right
+        .cross(right)
+        .dot(forward)
+        .atan2(level_right.dot(right));
+    let clamped = roll.clamp(-max_roll, max_roll);
+    if clamped == roll {
+        return rotation;
+    }
+    Quat::from_axis_angle(forward, clamped - roll) * rotation
+}
+
+/// Turns the unit vector `dir` towards `cone_axis`, just far enough that it's within `half_angle`
+/// radians of it. `cone_axis` doesn't need to be normalized. If `cone_axis` is zero, `dir` is
+/// returned unchanged.
+pub fn clamp_to_cone(dir: Vec3, cone_axis: Vec3, half_angle: f32) -> Vec3 {
+    let Some(cone_axis) = cone_axis.try_normalize() else {
+        return dir;
+    };
+    if dir.angle_between(cone_axis) <= half_angle {
+        return dir;
+    }
+    // Directly opposite the axis, every way round is as short, so pick one
+    let turn_axis = cone_axis
+        .cross(dir)
+        .try_normalize()
+        .unwrap_or_else(|| cone_axis.any_orthonormal_vector());
+    Quat::from_axis_angle(turn_axis, half_angle) * cone_axis
+}
+
+/// Snaps `radius` to the nearest of `levels` (sorted in ascending order), then moves `steps` levels
+/// towards a smaller radius, clamping at either end
+pub fn step_zoom_level(levels: &[f32], radius: f32, steps: i32) -> f32 {
+    let Some(nearest) = levels
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| (*a - radius).abs().total_cmp(&(*b - radius).abs()))
+        .map(|(index, _)| index)
+    else {
+        return radius;
+    };
+    let index = (nearest as i32 - steps).clamp(0, levels.len() as i32 - 1);
+    levels[index as usize]
+}
+
+/// Moves a ratchet with detents at every multiple of `step`, as its input moves from `from` to
+/// `to`. If the input crosses a detent, the ratchet moves to the last one crossed, otherwise it
+/// stays at `current`.
+pub fn ratchet(from: f32, to: f32, step: f32, current: f32) -> f32 {
+    if (from / step).floor() == (to / step).floor() {
+        current
+    } else if to > from {
+        (to / step).floor() * step
+    } else {
+        (to / step).ceil() * step
+    }
+}
+
+/// The multiplier applied to orbit sensitivity when it scales with zoom
+pub fn zoom_sensitivity_scale(radius: f32, reference: f32, exponent: f32) -> f32 {
+    (radius / reference).powf(exponent)
+}
+
 pub fn approx_equal(a: f32, b: f32) -> bool {
     (a - b).abs() < EPSILON
 }
 
-pub fn lerp_and_snap_f32(from: f32, to: f32, smoothness: f32, dt: f32) -> f32 {
+/// How far to move towards the target this frame. A smoothness of zero always moves all the way,
+/// even if no time has passed.
+pub fn lerp_factor(smoothness: f32, dt: f32) -> f32 {
+    if smoothness <= 0.0 {
+        return 1.0;
+    }
     let t = smoothness.powi(7);
-    let mut new_value = from.lerp(to, 1.0 - t.powf(dt));
-    if smoothness < 1.0 && approx_equal(new_value, to) {
+    1.0 - t.powf(dt)
+}
+
+/// Lerp towards `to`, snapping to it once within `snap_threshold`
+pub fn lerp_and_snap_f32(from: f32, to: f32, smoothness: f32, dt: f32, snap_threshold: f32) -> f32 {
+    let mut new_value = from.lerp(to, lerp_factor(smoothness, dt));
+    if smoothness < 1.0 && (new_value - to).abs() < snap_threshold {
         new_value = to;
     }
     new_value
 }
 
-pub fn lerp_and_snap_vec3(from: Vec3, to: Vec3, smoothness: f32, dt: f32) -> Vec3 {
-    let t = smoothness.powi(7);
-    let mut new_value = from.lerp(to, 1.0 - t.powf(dt));
-    if smoothness < 1.0 && approx_equal((new_value - to).length(), 0.0) {
-        new_value.x = to.x;
+/// Lerp towards `to`, snapping to it once within `snap_threshold`
+pub fn lerp_and_snap_vec3(
+    from: Vec3,
+    to: Vec3,
+    smoothness: f32,
+    dt: f32,
+    snap_threshold: f32,
+) -> Vec3 {
+    let mut new_value = from.lerp(to, lerp_factor(smoothness, dt));
+    if smoothness < 1.0 && (new_value - to).length() < snap_threshold {
+        new_value = to;
+    }
+    new_value
+}
+
+pub fn slerp_and_snap_quat(from: Quat, to: Quat, smoothness: f32, dt: f32) -> Quat {
+    let mut new_value = from.slerp(to, lerp_factor(smoothness, dt));
+    if smoothness < 1.0 && approx_equal(new_value.angle_between(to), 0.0) {
+        new_value = to;
     }
     new_value
 }
@@ -63,7 +327,6 @@ pub fn lerp_and_snap_vec3(from: Vec3, to: Vec3, smoothness: f32, dt: f32) -> Vec
 mod calculate_from_translation_and_focus_tests {
     use super::*;
     use float_cmp::approx_eq;
-    use std::f32::consts::PI;
 
     #[test]
     fn zero() {
@@ -116,6 +379,253 @@ mod calculate_from_translation_and_focus_tests {
     }
 }
 
+#[cfg(test)]
+mod is_near_pole_tests {
+    use super::*;
+
+    #[test]
+    fn poles_are_near_pole() {
+        assert!(is_near_pole(PI / 2.0));
+        assert!(is_near_pole(-PI / 2.0));
+        assert!(is_near_pole(PI / 2.0 - 0.0001));
+        assert!(is_near_pole(PI * 1.5));
+    }
+
+    #[test]
+    fn other_pitches_are_not_near_pole() {
+        assert!(!is_near_pole(0.0));
+        assert!(!is_near_pole(PI / 4.0));
+        assert!(!is_near_pole(PI / 2.0 - 0.01));
+        assert!(!is_near_pole(PI));
+    }
+}
+
+#[cfg(test)]
+mod skip_pitch_keepout_tests {
+    use super::*;
+
+    #[test]
+    fn outside_band_is_unchanged() {
+        assert_eq!(skip_pitch_keepout(0.0, 0.2, (0.3, 0.6)), 0.2);
+        assert_eq!(skip_pitch_keepout(0.0, 0.3, (0.3, 0.6)), 0.3);
+        assert_eq!(skip_pitch_keepout(0.0, 0.7, (0.3, 0.6)), 0.7);
+    }
+
+    #[test]
+    fn entering_band_jumps_across() {
+        assert_eq!(skip_pitch_keepout(0.0, 0.35, (0.3, 0.6)), 0.6);
+        assert_eq!(skip_pitch_keepout(0.3, 0.55, (0.3, 0.6)), 0.6);
+        assert_eq!(skip_pitch_keepout(1.0, 0.55, (0.3, 0.6)), 0.3);
+    }
+
+    #[test]
+    fn inside_band_moves_to_nearer_edge() {
+        assert_eq!(skip_pitch_keepout(0.4, 0.35, (0.3, 0.6)), 0.3);
+        assert_eq!(skip_pitch_keepout(0.4, 0.55, (0.3, 0.6)), 0.6);
+    }
+}
+
+#[cfg(test)]
+mod clamp_roll_tests {
+    use super::*;
+
+    fn roll(rotation: Quat) -> f32 {
+        let forward = rotation * Vec3::NEG_Z;
+        let level_right = forward.cross(Vec3::Y).normalize();
+        let right = rotation * Vec3::X;
+        level_right
+            .cross(right)
+            .dot(forward)
+            .atan2(level_right.dot(right))
+    }
+
+    #[test]
+    fn clamps_roll_keeping_view_direction() {
+        let view = Quat::from_rotation_y(0.5) * Quat::from_rotation_x(-0.3);
+        // Roll is measured around the view direction, so it's opposite to rotation around Z
+        let rotation = view * Quat::from_rotation_z(1.0);
+        let clamped = clamp_roll(rotation, 0.2);
+        assert!((roll(clamped) + 0.2).abs() < 0.0001);
+        assert!((clamped * Vec3::NEG_Z).abs_diff_eq(rotation * Vec3::NEG_Z, 0.0001));
+        let clamped = clamp_roll(view * Quat::from_rotation_z(-2.0), 0.2);
+        assert!((roll(clamped) - 0.2).abs() < 0.0001);
+    }
+
+    #[test]
+    fn leaves_roll_within_limit_unchanged() {
+        let rotation = Quat::from_rotation_y(0.5) * Quat::from_rotation_z(0.1);
+        assert_eq!(clamp_roll(rotation, 0.2), rotation);
+    }
+
+    #[test]
+    fn straight_down_is_unchanged() {
+        let rotation = Quat::from_rotation_x(-PI / 2.0) * Quat::from_rotation_z(1.0);
+        assert_eq!(clamp_roll(rotation, 0.2), rotation);
+    }
+}
+
+#[cfg(test)]
+mod clamp_to_cone_tests {
+    use super::*;
+
+    #[test]
+    fn inside_cone_is_unchanged() {
+        let dir = Vec3::new(0.1, 0.0, -1.0).normalize();
+        assert_eq!(clamp_to_cone(dir, Vec3::NEG_Z, 0.5), dir);
+        assert_eq!(clamp_to_cone(dir, Vec3::ZERO, 0.5), dir);
+    }
+
+    #[test]
+    fn outside_cone_is_moved_to_edge() {
+        let clamped = clamp_to_cone(Vec3::X, Vec3::NEG_Z * 2.0, PI / 4.0);
+        assert!(clamped.abs_diff_eq(Vec3::new(1.0, 0.0, -1.0).normalize(), 0.0001));
+        // Opposite the axis still ends up on the edge
+        let clamped = clamp_to_cone(Vec3::Z, Vec3::NEG_Z, PI / 4.0);
+        assert!((clamped.angle_between(Vec3::NEG_Z) - PI / 4.0).abs() < 0.0001);
+    }
+}
+
+#[cfg(test)]
+mod step_zoom_level_tests {
+    use super::*;
+
+    const LEVELS: [f32; 4] = [1.0, 2.0, 4.0, 8.0];
+
+    #[test]
+    fn steps_from_nearest_level() {
+        assert_eq!(step_zoom_level(&LEVELS, 4.0, 1), 2.0);
+        assert_eq!(step_zoom_level(&LEVELS, 3.5, 1), 2.0);
+        assert_eq!(step_zoom_level(&LEVELS, 4.0, -1), 8.0);
+        assert_eq!(step_zoom_level(&LEVELS, 4.2, 0), 4.0);
+    }
+
+    #[test]
+    fn clamps_at_ends() {
+        assert_eq!(step_zoom_level(&LEVELS, 2.0, 5), 1.0);
+        assert_eq!(step_zoom_level(&LEVELS, 2.0, -5), 8.0);
+    }
+
+    #[test]
+    fn no_levels_leaves_radius_unchanged() {
+        assert_eq!(step_zoom_level(&[], 3.0, 1), 3.0);
+    }
+}
+
+#[cfg(test)]
+mod radius_to_fit_tests {
+    use super::*;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn perspective_fits_narrowest_fov() {
+        let projection = |aspect_ratio: f32| {
+            Projection::Perspective(PerspectiveProjection {
+                fov: PI / 2.0,
+                aspect_ratio,
+                ..default()
+            })
+        };
+        let radius = radius_to_fit(1.0, &projection(2.0), 0.0).unwrap();
+        assert!(approx_eq!(f32, radius, 2.0_f32.sqrt()));
+        // Taller than it is wide, so the horizontal fov is narrower
+        let radius = radius_to_fit(1.0, &projection(0.5), 0.0).unwrap();
+        assert!(approx_eq!(f32, radius, 5.0_f32.sqrt(), epsilon = 0.0001));
+    }
+
+    #[test]
+    fn orthographic_fits_smallest_dimension() {
+        let projection = Projection::Orthographic(OrthographicProjection {
+            scale: 2.0,
+            area: Rect::new(-1.0, -2.0, 1.0, 2.0),
+            ..OrthographicProjection::default_3d()
+        });
+        assert_eq!(radius_to_fit(1.0, &projection, 0.0), Some(2.0));
+    }
+
+    #[test]
+    fn larger_padding_gives_larger_radius() {
+        let perspective = Projection::Perspective(default());
+        let orthographic = Projection::Orthographic(OrthographicProjection {
+            area: Rect::new(-1.0, -1.0, 1.0, 1.0),
+            ..OrthographicProjection::default_3d()
+        });
+        for projection in [perspective, orthographic] {
+            let radius = |padding| radius_to_fit(1.0, &projection, padding).unwrap();
+            assert!(radius(0.1) > radius(0.0));
+            assert!(radius(0.2) > radius(0.1));
+        }
+        // Orthographic scales linearly, so a 25% margin on each side doubles the scale
+        let orthographic = Projection::Orthographic(OrthographicProjection {
+            area: Rect::new(-1.0, -1.0, 1.0, 1.0),
+            ..OrthographicProjection::default_3d()
+        });
+        assert_eq!(radius_to_fit(1.0, &orthographic, 0.25), Some(2.0));
+    }
+
+    #[test]
+    fn orthographic_without_area_is_none() {
+        let projection = Projection::Orthographic(OrthographicProjection {
+            area: Rect::default(),
+            ..OrthographicProjection::default_3d()
+        });
+        assert_eq!(radius_to_fit(1.0, &projection, 0.0), None);
+    }
+}
+
+#[cfg(test)]
+mod focus_plane_offset_tests {
+    use super::*;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn perspective_scales_with_radius() {
+        let projection = Projection::Perspective(PerspectiveProjection {
+            fov: PI / 2.0,
+            aspect_ratio: 2.0,
+            ..default()
+        });
+        let offset = focus_plane_offset(&projection, Vec2::new(1.0, -0.5), 3.0);
+        assert!(approx_eq!(f32, offset.x, 6.0, epsilon = 0.0001));
+        assert!(approx_eq!(f32, offset.y, -1.5, epsilon = 0.0001));
+        assert_eq!(focus_plane_offset(&projection, Vec2::ZERO, 3.0), Vec2::ZERO);
+    }
+
+    #[test]
+    fn orthographic_rescales_area() {
+        let projection = Projection::Orthographic(OrthographicProjection {
+            scale: 2.0,
+            area: Rect::new(-2.0, -1.0, 2.0, 1.0),
+            ..OrthographicProjection::default_3d()
+        });
+        assert_eq!(
+            focus_plane_offset(&projection, Vec2::new(1.0, 1.0), 4.0),
+            Vec2::new(4.0, 2.0)
+        );
+    }
+}
+
+#[cfg(test)]
+mod zoom_sensitivity_scale_tests {
+    use super::*;
+
+    #[test]
+    fn is_one_at_reference_radius() {
+        assert_eq!(zoom_sensitivity_scale(5.0, 5.0, 1.0), 1.0);
+        assert_eq!(zoom_sensitivity_scale(5.0, 5.0, 0.5), 1.0);
+    }
+
+    #[test]
+    fn scales_linearly_with_exponent_one() {
+        assert_eq!(zoom_sensitivity_scale(10.0, 5.0, 1.0), 2.0);
+        assert_eq!(zoom_sensitivity_scale(2.5, 5.0, 1.0), 0.5);
+    }
+
+    #[test]
+    fn exponent_changes_curve() {
+        assert_eq!(zoom_sensitivity_scale(20.0, 5.0, 0.5), 2.0);
+    }
+}
+
 #[cfg(test)]
 mod approx_equal_tests {
     use super::*;
@@ -142,27 +652,41 @@ mod lerp_and_snap_f32_tests {
 
     #[test]
     fn lerps_when_output_outside_snap_threshold() {
-        let out = lerp_and_snap_f32(1.0, 2.0, 0.5, 1.0);
+        let out = lerp_and_snap_f32(1.0, 2.0, 0.5, 1.0, EPSILON);
         // Due to the frame rate independence, this value is not easily predictable
         assert_eq!(out, 1.9921875);
     }
 
     #[test]
     fn snaps_to_target_when_inside_threshold() {
-        let out = lerp_and_snap_f32(1.9991, 2.0, 0.5, 1.0);
+        let out = lerp_and_snap_f32(1.9991, 2.0, 0.5, 1.0, EPSILON);
         assert_eq!(out, 2.0);
-        let out = lerp_and_snap_f32(1.9991, 2.0, 0.1, 1.0);
+        let out = lerp_and_snap_f32(1.9991, 2.0, 0.1, 1.0, EPSILON);
         assert_eq!(out, 2.0);
-        let out = lerp_and_snap_f32(1.9991, 2.0, 0.9, 1.0);
+        let out = lerp_and_snap_f32(1.9991, 2.0, 0.9, 1.0, EPSILON);
         assert_eq!(out, 2.0);
     }
 
     #[test]
     fn does_not_snap_if_smoothness_is_one() {
         // Smoothness of one results in the value not changing, so it doesn't make sense to snap
-        let out = lerp_and_snap_f32(1.9991, 2.0, 1.0, 1.0);
+        let out = lerp_and_snap_f32(1.9991, 2.0, 1.0, 1.0, EPSILON);
         assert_eq!(out, 1.9991);
     }
+
+    #[test]
+    fn zero_smoothness_reaches_target_without_time_passing() {
+        let out = lerp_and_snap_f32(1.0, 2.0, 0.0, 0.0, EPSILON);
+        assert_eq!(out, 2.0);
+    }
+
+    #[test]
+    fn snap_threshold_scales() {
+        let out = lerp_and_snap_f32(1999.1, 2000.0, 0.5, 1.0, EPSILON * 1000.0);
+        assert_eq!(out, 2000.0);
+        let out = lerp_and_snap_f32(0.0019, 0.002, 0.9, 1.0, EPSILON * 0.001);
+        assert_ne!(out, 0.002);
+    }
 }
 
 #[cfg(test)]
@@ -171,25 +695,152 @@ mod lerp_and_snap_vec3_tests {
 
     #[test]
     fn lerps_when_output_outside_snap_threshold() {
-        let out = lerp_and_snap_vec3(Vec3::ZERO, Vec3::X, 0.5, 1.0);
+        let out = lerp_and_snap_vec3(Vec3::ZERO, Vec3::X, 0.5, 1.0, EPSILON);
         // Due to the frame rate independence, this value is not easily predictable
         assert_eq!(out, Vec3::new(0.9921875, 0.0, 0.0));
     }
 
     #[test]
     fn snaps_to_target_when_inside_threshold() {
-        let out = lerp_and_snap_vec3(Vec3::X * 0.9991, Vec3::X, 0.5, 1.0);
+        let out = lerp_and_snap_vec3(Vec3::X * 0.9991, Vec3::X, 0.5, 1.0, EPSILON);
         assert_eq!(out, Vec3::X);
-        let out = lerp_and_snap_vec3(Vec3::X * 0.9991, Vec3::X, 0.1, 1.0);
+        let out = lerp_and_snap_vec3(Vec3::X * 0.9991, Vec3::X, 0.1, 1.0, EPSILON);
         assert_eq!(out, Vec3::X);
-        let out = lerp_and_snap_vec3(Vec3::X * 0.9991, Vec3::X, 0.9, 1.0);
+        let out = lerp_and_snap_vec3(Vec3::X * 0.9991, Vec3::X, 0.9, 1.0, EPSILON);
         assert_eq!(out, Vec3::X);
     }
 
+    #[test]
+    fn snaps_all_components_to_target() {
+        let to = Vec3::new(1.0, 2.0, 3.0);
+        let out = lerp_and_snap_vec3(to * 0.9999, to, 0.5, 1.0, EPSILON);
+        assert_eq!(out, to);
+        // Movement on only y and z
+        let from = Vec3::new(1.0, 2.0004, 2.9996);
+        let out = lerp_and_snap_vec3(from, to, 0.9, 1.0, EPSILON);
+        assert_eq!(out, to);
+    }
+
     #[test]
     fn does_not_snap_if_smoothness_is_one() {
         // Smoothness of one results in the value not changing, so it doesn't make sense to snap
-        let out = lerp_and_snap_vec3(Vec3::X * 0.9991, Vec3::X, 1.0, 1.0);
+        let out = lerp_and_snap_vec3(Vec3::X * 0.9991, Vec3::X, 1.0, 1.0, EPSILON);
         assert_eq!(out, Vec3::X * 0.9991);
     }
 }
+
+#[cfg(test)]
+mod yaw_pitch_from_rotation_tests {
+    use super::*;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn round_trips_yaw_and_pitch() {
+        let (yaw, pitch) = yaw_pitch_from_rotation(rotation_from_yaw_pitch(0.5, -0.3));
+        assert!(approx_eq!(f32, yaw, 0.5, epsilon = 0.0001));
+        assert!(approx_eq!(f32, pitch, -0.3, epsilon = 0.0001));
+    }
+
+    #[test]
+    fn past_the_pole_flips_yaw() {
+        let (yaw, pitch) = yaw_pitch_from_rotation(rotation_from_yaw_pitch(0.0, PI * 0.75));
+        assert!(approx_eq!(f32, yaw.abs(), PI, epsilon = 0.0001));
+        assert!(approx_eq!(f32, pitch, PI / 4.0, epsilon = 0.0001));
+    }
+}
+
+#[cfg(test)]
+mod slerp_and_snap_quat_tests {
+    use super::*;
+
+    #[test]
+    fn slerps_when_output_outside_snap_threshold() {
+        let out = slerp_and_snap_quat(Quat::IDENTITY, Quat::from_rotation_y(1.0), 0.5, 1.0);
+        assert!(out.angle_between(Quat::from_rotation_y(1.0)) > 0.001);
+        assert!(out.angle_between(Quat::IDENTITY) > 0.001);
+    }
+
+    #[test]
+    fn snaps_to_target_when_inside_threshold() {
+        let target = Quat::from_rotation_y(1.0);
+        let out = slerp_and_snap_quat(Quat::from_rotation_y(0.9991), target, 0.5, 1.0);
+        assert_eq!(out, target);
+    }
+
+    #[test]
+    fn does_not_snap_if_smoothness_is_one() {
+        let from = Quat::from_rotation_y(0.9991);
+        let out = slerp_and_snap_quat(from, Quat::from_rotation_y(1.0), 1.0, 1.0);
+        assert_eq!(out, from);
+    }
+}
+
+#[cfg(test)]
+mod axis_rotation_tests {
+    use super::*;
+
+    #[test]
+    fn default_axis_is_identity() {
+        assert_eq!(axis_rotation([Vec3::X, Vec3::Y, Vec3::Z]), Quat::IDENTITY);
+    }
+
+    #[test]
+    fn z_up_axis_maps_y_to_z() {
+        let rotation = axis_rotation([Vec3::X, Vec3::Z, Vec3::NEG_Y]);
+        assert!((rotation * Vec3::Y).abs_diff_eq(Vec3::Z, 0.0001));
+    }
+
+    #[test]
+    fn round_trips_axis() {
+        let axis = [Vec3::X, Vec3::Z, Vec3::NEG_Y];
+        let round_tripped = axis_from_rotation(axis_rotation(axis));
+        for i in 0..3 {
+            assert!(round_tripped[i].abs_diff_eq(axis[i], 0.0001));
+        }
+    }
+}
+
+#[cfg(test)]
+mod wrap_angle_near_tests {
+    use super::*;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn unchanged_when_already_near() {
+        assert_eq!(wrap_angle_near(1.0, 1.2), 1.0);
+    }
+
+    #[test]
+    fn wraps_across_pi() {
+        assert!(approx_eq!(f32, wrap_angle_near(-3.1, 3.1), TAU - 3.1));
+    }
+
+    #[test]
+    fn keeps_accumulated_turns() {
+        assert!(approx_eq!(
+            f32,
+            wrap_angle_near(0.5, TAU * 2.0 + 0.4),
+            TAU * 2.0 + 0.5,
+            epsilon = 0.0001
+        ));
+    }
+}
+
+#[cfg(test)]
+mod ratchet_tests {
+    use super::*;
+
+    #[test]
+    fn stays_between_detents() {
+        assert_eq!(ratchet(0.1, 0.4, 0.5, 0.1), 0.1);
+        assert_eq!(ratchet(0.4, 0.1, 0.5, 0.1), 0.1);
+    }
+
+    #[test]
+    fn moves_to_last_detent_crossed() {
+        assert_eq!(ratchet(0.4, 0.6, 0.5, 0.1), 0.5);
+        assert_eq!(ratchet(0.4, 1.2, 0.5, 0.1), 1.0);
+        assert_eq!(ratchet(0.6, 0.4, 0.5, 0.6), 0.5);
+        assert_eq!(ratchet(0.1, -0.6, 0.5, 0.1), -0.5);
+    }
+}